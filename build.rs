@@ -76,6 +76,15 @@ fn main() {
     );
     println!("cargo:rustc-link-lib=dylib=refprop");
 
+    // Let the FMU component find a REFPROP library bundled next to it. The target OS must be read
+    // from the environment, since `cfg(target_os)` describes the host the build script runs on.
+    #[cfg(feature = "fmi")]
+    {
+        if env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "linux") {
+            println!("cargo:rustc-cdylib-link-arg=-Wl,-rpath,$ORIGIN");
+        }
+    }
+
     // Instruct Cargo to rerun the build script if Fortran files or CMakeLists.txt change.
     println!("cargo:rerun-if-changed=REFPROP_FORTRAN/");
     println!(
@@ -120,4 +129,32 @@ fn main() {
         "cargo:rustc-env=BINDINGS_PATH={}",
         bindings_out_path.display()
     );
+
+    // ======================
+    // C Header Generation
+    // ======================
+    #[cfg(feature = "capi")]
+    generate_c_header();
+}
+
+/// Generates the C header `refprop_sys.h` for the C ABI exported by `src/capi.rs` into `OUT_DIR`.
+///
+/// Build scripts may only write to `OUT_DIR`; the copy committed as `include/refprop_sys.h` is
+/// checked against the generated header by a test in `src/capi.rs`.
+#[cfg(feature = "capi")]
+fn generate_c_header() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("Unable to read cbindgen.toml.");
+
+    cbindgen::Builder::new()
+        .with_src(crate_dir.join("src").join("capi.rs"))
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate the C header with cbindgen.")
+        .write_to_file(out_dir.join("refprop_sys.h"));
+
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
 }
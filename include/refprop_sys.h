@@ -0,0 +1,198 @@
+/* Generated by cbindgen from src/capi.rs. Do not edit. */
+
+#ifndef REFPROP_SYS_H
+#define REFPROP_SYS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Maximum number of components of a fluid.
+#define REFPROP_MAX_COMPONENTS 20
+
+// Outcome of a C API call, mirroring the variants of `RefpropError`.
+typedef enum RefpropStatus {
+  REFPROP_STATUS_OK = 0,
+  REFPROP_STATUS_INITIALIZATION_ERROR = 1,
+  REFPROP_STATUS_CALCULATION_ERROR = 2,
+  REFPROP_STATUS_INVALID_INPUT = 3,
+  REFPROP_STATUS_UTF8_ERROR = 4,
+  REFPROP_STATUS_MUTEX_POISONED = 5,
+  REFPROP_STATUS_UNKNOWN_ERROR = 6,
+} RefpropStatus;
+
+// Pair of independent properties of a flash calculation, in the default molar units.
+//
+// `refprop_flash` takes the pair as an `int`, so that out-of-range values are rejected.
+typedef enum RefpropInputPair {
+  REFPROP_INPUT_PAIR_TP = 0,
+  REFPROP_INPUT_PAIR_PH = 1,
+  REFPROP_INPUT_PAIR_PS = 2,
+  REFPROP_INPUT_PAIR_PD = 3,
+  REFPROP_INPUT_PAIR_PE = 4,
+  REFPROP_INPUT_PAIR_TD = 5,
+  REFPROP_INPUT_PAIR_DH = 6,
+  REFPROP_INPUT_PAIR_DS = 7,
+  REFPROP_INPUT_PAIR_DE = 8,
+  REFPROP_INPUT_PAIR_HS = 9,
+  REFPROP_INPUT_PAIR_TQ = 10,
+  REFPROP_INPUT_PAIR_PQ = 11,
+} RefpropInputPair;
+
+// Opaque handle to a fluid definition.
+typedef struct RefpropFluid RefpropFluid;
+
+// Thermodynamic state calculated by `refprop_flash`, in the default molar units.
+typedef struct RefpropState {
+  // Temperature [K]
+  double T;
+  // Pressure [kPa]
+  double P;
+  // Density [mol/L]
+  double D;
+  // Density of the liquid phase [mol/L]
+  double Dl;
+  // Density of the vapor phase [mol/L]
+  double Dv;
+  // Vapor quality on a molar basis [mol/mol]
+  double q;
+  // Internal energy [J/mol]
+  double e;
+  // Enthalpy [J/mol]
+  double h;
+  // Entropy [J/mol-K]
+  double s;
+  // Isochoric heat capacity [J/mol-K]; NaN for two-phase states
+  double Cv;
+  // Isobaric heat capacity [J/mol-K]; NaN for two-phase states
+  double Cp;
+  // Speed of sound [m/s]
+  double w;
+  // Number of components, i.e. of valid entries in `x` and `y`
+  size_t ncomp;
+  // Composition of the liquid phase (mole fractions)
+  double x[REFPROP_MAX_COMPONENTS];
+  // Composition of the vapor phase (mole fractions)
+  double y[REFPROP_MAX_COMPONENTS];
+} RefpropState;
+
+// Transport properties calculated by `refprop_transport`.
+typedef struct RefpropTransport {
+  // Dynamic viscosity [uPa-s]
+  double eta;
+  // Thermal conductivity [W/(m-K)]
+  double tcx;
+} RefpropTransport;
+
+// Critical point calculated by `refprop_critical`.
+typedef struct RefpropCritical {
+  // Critical temperature [K]
+  double Tc;
+  // Critical pressure [kPa]
+  double Pc;
+  // Critical density [mol/L]
+  double Dc;
+} RefpropCritical;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Sets the path of the REFPROP installation; a null `path` tries the RPPREFIX environment
+// variable and then the fallback directories of `set_path_fallbacks`.
+//
+// # Safety
+//
+// `path` must be null or a valid NUL-terminated string.
+enum RefpropStatus refprop_set_path(const char *path);
+
+// Creates a handle for a predefined mixture (e.g. "R454B") or a pure fluid (e.g. "WATER").
+//
+// Returns null on failure; the error is available from `refprop_last_error`. The handle must be
+// released with `refprop_fluid_free`.
+//
+// # Safety
+//
+// `name` must be a valid NUL-terminated string.
+struct RefpropFluid *refprop_fluid_new_mixture(const char *name);
+
+// Creates a handle for a list of fluids separated by semicolons with the given mole fractions.
+//
+// `ncomp` must equal the number of fluids in the list. Returns null on failure; the error is available from `refprop_last_error`. The handle must be
+// released with `refprop_fluid_free`.
+//
+// # Safety
+//
+// `fluids` must be a valid NUL-terminated string and `z` must point to `ncomp` doubles.
+struct RefpropFluid *refprop_fluid_new(const char *fluids, const double *z, size_t ncomp);
+
+// Releases a fluid handle. Null handles are ignored.
+//
+// # Safety
+//
+// `fluid` must be null or a handle returned by `refprop_fluid_new` or `refprop_fluid_new_mixture`
+// that has not been released yet.
+void refprop_fluid_free(struct RefpropFluid *fluid);
+
+// Returns the number of components of a fluid, or 0 for a null handle.
+//
+// # Safety
+//
+// `fluid` must be null or a valid handle.
+size_t refprop_fluid_component_count(const struct RefpropFluid *fluid);
+
+// Copies the mole fractions of a fluid into `z`, which must hold `REFPROP_MAX_COMPONENTS` doubles.
+//
+// # Safety
+//
+// `fluid` must be a valid handle and `z` must point to `REFPROP_MAX_COMPONENTS` writable doubles.
+enum RefpropStatus refprop_fluid_composition(const struct RefpropFluid *fluid, double *z);
+
+// Performs a flash calculation for a pair of independent properties and writes the state to `out`.
+//
+// `pair` is one of the `RefpropInputPair` values; other values return `InvalidInput`.
+//
+// # Safety
+//
+// `fluid` must be a valid handle and `out` must point to a writable `RefpropState`.
+enum RefpropStatus refprop_flash(const struct RefpropFluid *fluid,
+                                 int pair,
+                                 double a,
+                                 double b,
+                                 struct RefpropState *out);
+
+// Calculates the transport properties at temperature `T` [K] and density `D` [mol/L].
+//
+// # Safety
+//
+// `fluid` must be a valid handle and `out` must point to a writable `RefpropTransport`.
+enum RefpropStatus refprop_transport(const struct RefpropFluid *fluid,
+                                     double T,
+                                     double D,
+                                     struct RefpropTransport *out);
+
+// Calculates the critical point of a fluid.
+//
+// # Safety
+//
+// `fluid` must be a valid handle and `out` must point to a writable `RefpropCritical`.
+enum RefpropStatus refprop_critical(const struct RefpropFluid *fluid, struct RefpropCritical *out);
+
+// Copies the message of the last error on the calling thread into `buffer` as a NUL-terminated
+// string, truncating it to `length - 1` bytes.
+//
+// Returns the length of the full message in bytes (excluding the NUL terminator), or 0 if the last
+// call succeeded. Passing a null `buffer` only queries the length.
+//
+// # Safety
+//
+// `buffer` must be null or point to `length` writable bytes.
+size_t refprop_last_error(char *buffer, size_t length);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* REFPROP_SYS_H */
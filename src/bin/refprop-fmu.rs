@@ -0,0 +1,133 @@
+//! Packages the shared library of this crate into an FMI 2.0 co-simulation FMU.
+//!
+//! ```text
+//! cargo rustc --release --lib --features fmi --crate-type cdylib
+//! cargo run --release --features fmi --bin refprop-fmu -- \
+//!     --library target/release/librefprop_sys.so --fluid R454B --output RefpropFluid.fmu
+//! ```
+//!
+//! Passing `--refprop-library` bundles the REFPROP shared library next to the component (the
+//! component is linked with an `$ORIGIN` run path), and `--refprop-dir` bundles the `FLUIDS` and
+//! `MIXTURES` directories of a REFPROP installation as FMU resources.
+
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use refprop_sys::fmi::{model_description, MODEL_IDENTIFIER};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+#[derive(Parser)]
+#[command(
+    name = "refprop-fmu",
+    version,
+    about = "Package a REFPROP fluid property FMU (FMI 2.0 co-simulation)"
+)]
+struct Cli {
+    /// Shared library of this crate built with the `fmi` feature
+    #[arg(long, default_value = "target/release/librefprop_sys.so")]
+    library: PathBuf,
+
+    /// Default fluid: a predefined mixture, a pure fluid, or fluids separated by semicolons
+    #[arg(long, default_value = "WATER")]
+    fluid: String,
+
+    /// Default comma-separated mole fractions for a list of fluids
+    #[arg(long, default_value = "")]
+    composition: String,
+
+    /// REFPROP shared library to bundle next to the component
+    #[arg(long)]
+    refprop_library: Option<PathBuf>,
+
+    /// REFPROP installation whose FLUIDS and MIXTURES directories are bundled as resources
+    #[arg(long)]
+    refprop_dir: Option<PathBuf>,
+
+    /// Path of the FMU to write
+    #[arg(long, short, default_value = "RefpropFluid.fmu")]
+    output: PathBuf,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(error) = package(&cli) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+    eprintln!("Wrote {}", cli.output.display());
+}
+
+fn package(cli: &Cli) -> io::Result<()> {
+    let mut fmu = ZipWriter::new(File::create(&cli.output)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    fmu.start_file("modelDescription.xml", options)?;
+    fmu.write_all(model_description(&cli.fluid, &cli.composition).as_bytes())?;
+
+    let component = format!("binaries/linux64/{}.so", MODEL_IDENTIFIER);
+    add_file(&mut fmu, &cli.library, &component, options)?;
+
+    if let Some(library) = &cli.refprop_library {
+        let name = library.file_name().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Invalid REFPROP library path.")
+        })?;
+        let target = format!("binaries/linux64/{}", name.to_string_lossy());
+        add_file(&mut fmu, library, &target, options)?;
+    }
+
+    if let Some(directory) = &cli.refprop_dir {
+        for subdirectory in ["FLUIDS", "MIXTURES"] {
+            let source = directory.join(subdirectory);
+            if !source.is_dir() {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} is not a directory.", source.display()),
+                ));
+            }
+            add_directory(
+                &mut fmu,
+                &source,
+                &format!("resources/{}", subdirectory),
+                options,
+            )?;
+        }
+    }
+
+    fmu.finish()?;
+    Ok(())
+}
+
+fn add_file(
+    fmu: &mut ZipWriter<File>,
+    source: &Path,
+    target: &str,
+    options: SimpleFileOptions,
+) -> io::Result<()> {
+    let bytes = fs::read(source).map_err(|e| {
+        io::Error::new(e.kind(), format!("Cannot read {}: {}", source.display(), e))
+    })?;
+    fmu.start_file(target, options)?;
+    fmu.write_all(&bytes)
+}
+
+fn add_directory(
+    fmu: &mut ZipWriter<File>,
+    source: &Path,
+    target: &str,
+    options: SimpleFileOptions,
+) -> io::Result<()> {
+    for entry in fs::read_dir(source)? {
+        let path = entry?.path();
+        let name = format!("{}/{}", target, path.file_name().unwrap().to_string_lossy());
+        if path.is_dir() {
+            add_directory(fmu, &path, &name, options)?;
+        } else {
+            add_file(fmu, &path, &name, options)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,352 @@
+//! Local HTTP/JSON property server backed by REFPROP.
+//!
+//! Every endpoint takes a JSON body with the fluid to load and returns the serialized result:
+//!
+//! ```text
+//! POST /flash       {"fluid": "R454B", "pair": "TP", "a": 300.0, "b": 101.325}
+//! POST /saturation  {"fluid": "WATER", "T_K": 373.15}            (or "P_kPa")
+//! POST /transport   {"fluid": "R32;R1234YF", "composition": [0.689, 0.311], "basis": "Mass",
+//!                    "T_K": 300.0, "D_mol_per_L": 0.05}
+//! POST /critical    {"fluid": "R454B"}
+//! GET  /health
+//! ```
+//!
+//! Inputs and outputs are in the default molar units. Errors are returned as
+//! `{"error": {"kind": "InvalidInput", "message": "..."}}` with a 4xx or 5xx status.
+//!
+//! REFPROP holds a single set of loaded fluids per process, so each request loads its fluid and
+//! runs its calculation while holding the global REFPROP lock; requests are parsed concurrently by
+//! the worker threads but evaluated one at a time.
+#![allow(non_snake_case)]
+
+use std::{
+    io::Read,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+};
+
+use clap::Parser;
+use refprop_sys::{
+    Composition, CompositionBasis, InputPair, RefpropError, RefpropFunctionLibrary, RefpropGuard,
+    SaturationAxis,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Maximum accepted size of a request body [bytes].
+const MAX_BODY_LENGTH: u64 = 64 * 1024;
+
+#[derive(Parser)]
+#[command(
+    name = "refprop-server",
+    version,
+    about = "HTTP/JSON server for REFPROP properties"
+)]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+
+    /// Number of worker threads accepting requests
+    #[arg(long, default_value_t = 4)]
+    threads: usize,
+
+    /// Path to the REFPROP installation (defaults to RPPREFIX, then the common installation directories)
+    #[arg(long)]
+    path: Option<String>,
+}
+
+/// Fluid loaded for a request: a predefined mixture or pure fluid, or a list of fluids with a composition.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct FluidSpec {
+    fluid: String,
+    #[serde(default)]
+    composition: Option<Vec<f64>>,
+    #[serde(default)]
+    basis: Option<CompositionBasis>,
+}
+
+#[derive(Deserialize)]
+struct FlashRequest {
+    #[serde(flatten)]
+    fluid: FluidSpec,
+    pair: String,
+    a: f64,
+    b: f64,
+}
+
+#[derive(Deserialize)]
+struct SaturationRequest {
+    #[serde(flatten)]
+    fluid: FluidSpec,
+    T_K: Option<f64>,
+    P_kPa: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct TransportRequest {
+    #[serde(flatten)]
+    fluid: FluidSpec,
+    T_K: f64,
+    D_mol_per_L: f64,
+}
+
+#[derive(Deserialize)]
+struct CriticalRequest {
+    #[serde(flatten)]
+    fluid: FluidSpec,
+}
+
+/// An error response: HTTP status and structured body.
+struct ApiError {
+    status: u16,
+    kind: String,
+    message: String,
+}
+
+impl From<RefpropError> for ApiError {
+    fn from(error: RefpropError) -> Self {
+        let status = match error {
+            RefpropError::InvalidInput(_) => 400,
+            RefpropError::CalculationError(_) => 422,
+            RefpropError::InitializationError(_) => 503,
+            RefpropError::Utf8Error(_)
+            | RefpropError::MutexPoisoned
+            | RefpropError::UnknownError(_) => 500,
+        };
+        ApiError {
+            status,
+            kind: error.kind().to_string(),
+            message: error.message(),
+        }
+    }
+}
+
+impl ApiError {
+    fn new(status: u16, kind: &str, message: impl Into<String>) -> Self {
+        ApiError {
+            status,
+            kind: kind.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Err(error) = RefpropFunctionLibrary::set_path(cli.path.as_deref()) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+
+    let server = match Server::http(&cli.addr) {
+        Ok(server) => Arc::new(server),
+        Err(error) => {
+            eprintln!("error: cannot listen on {}: {}", cli.addr, error);
+            std::process::exit(1);
+        }
+    };
+    eprintln!("Listening on http://{}", cli.addr);
+
+    let workers: Vec<_> = (0..cli.threads.max(1))
+        .map(|_| {
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(request);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+fn handle(mut request: Request) {
+    // A panicking request must not take its worker thread or the REFPROP lock down with it
+    let result =
+        panic::catch_unwind(AssertUnwindSafe(|| route(&mut request))).unwrap_or_else(|_| {
+            Err(ApiError::new(
+                500,
+                "UnknownError",
+                "The calculation panicked.",
+            ))
+        });
+    let (status, body) = match result {
+        Ok(body) => (200, body),
+        Err(error) => (
+            error.status,
+            json!({ "error": { "kind": error.kind, "message": error.message } }),
+        ),
+    };
+
+    let header = Header::from_bytes("Content-Type", "application/json").expect("valid header");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    let _ = request.respond(response);
+}
+
+fn route(request: &mut Request) -> Result<Value, ApiError> {
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    match (request.method(), path.as_str()) {
+        (Method::Get, "/health") => Ok(json!({ "status": "ok" })),
+        (Method::Post, "/flash") => flash(parse(request)?),
+        (Method::Post, "/saturation") => saturation(parse(request)?),
+        (Method::Post, "/transport") => transport(parse(request)?),
+        (Method::Post, "/critical") => critical(parse(request)?),
+        (_, "/health" | "/flash" | "/saturation" | "/transport" | "/critical") => Err(
+            ApiError::new(405, "MethodNotAllowed", "Method not allowed."),
+        ),
+        _ => Err(ApiError::new(
+            404,
+            "NotFound",
+            format!("No endpoint at {}.", path),
+        )),
+    }
+}
+
+/// Reads and deserializes the JSON body of a request.
+fn parse<T: DeserializeOwned>(request: &mut Request) -> Result<T, ApiError> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_LENGTH + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError::new(400, "BadRequest", format!("Unreadable body: {}", e)))?;
+    if body.len() as u64 > MAX_BODY_LENGTH {
+        return Err(ApiError::new(
+            413,
+            "PayloadTooLarge",
+            format!("Request bodies are limited to {} bytes.", MAX_BODY_LENGTH),
+        ));
+    }
+
+    serde_json::from_str(&body)
+        .map_err(|e| ApiError::new(400, "BadRequest", format!("Invalid JSON body: {}", e)))
+}
+
+/// Checks that the inputs of a request are finite numbers.
+fn check_finite(values: &[(&str, f64)]) -> Result<(), RefpropError> {
+    match values.iter().find(|(_, value)| !value.is_finite()) {
+        Some((name, value)) => Err(RefpropError::InvalidInput(format!(
+            "'{}' must be a finite number, got {}.",
+            name, value
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Acquires the REFPROP lock, recovering it if an earlier request panicked while holding it.
+///
+/// Recovering is safe because every request loads its fluid again after taking the lock.
+fn lock() -> Result<RefpropGuard, RefpropError> {
+    match RefpropFunctionLibrary::lock() {
+        Err(RefpropError::MutexPoisoned) => {
+            RefpropFunctionLibrary::clear_lock_poison();
+            RefpropFunctionLibrary::lock()
+        }
+        result => result,
+    }
+}
+
+/// Loads the fluid of a request and returns its mole fractions.
+///
+/// Must be called while holding the REFPROP lock, together with the calculation that follows.
+fn load(spec: &FluidSpec) -> Result<Vec<f64>, RefpropError> {
+    if spec.fluid.trim().is_empty() {
+        return Err(RefpropError::InvalidInput(
+            "'fluid' must not be empty.".to_string(),
+        ));
+    }
+
+    match &spec.composition {
+        Some(fractions) => {
+            let basis = spec.basis.unwrap_or(CompositionBasis::Mole);
+            let composition = Composition::new(fractions.clone(), basis)?;
+            RefpropFunctionLibrary::set_fluids(&spec.fluid)?;
+            composition.mole_fractions()
+        }
+        None if spec.fluid.contains(';') => Err(RefpropError::InvalidInput(
+            "A list of fluids requires 'composition'.".to_string(),
+        )),
+        None => RefpropFunctionLibrary::set_mixture_or_fluid(&spec.fluid),
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Value, ApiError> {
+    serde_json::to_value(value)
+        .map_err(|e| ApiError::new(500, "UnknownError", format!("Serialization failed: {}", e)))
+}
+
+fn flash(request: FlashRequest) -> Result<Value, ApiError> {
+    let pair: InputPair = request.pair.parse()?;
+    check_finite(&[("a", request.a), ("b", request.b)])?;
+
+    let _guard = lock()?;
+    let z = load(&request.fluid)?;
+    let output = RefpropFunctionLibrary::flash(pair, request.a, request.b, &z)?;
+
+    to_json(&output)
+}
+
+fn saturation(request: SaturationRequest) -> Result<Value, ApiError> {
+    let (axis, value) = match (request.T_K, request.P_kPa) {
+        (Some(T), None) => (SaturationAxis::Temperature, T),
+        (None, Some(P)) => (SaturationAxis::Pressure, P),
+        _ => {
+            return Err(RefpropError::InvalidInput(
+                "Exactly one of 'T_K' and 'P_kPa' is required.".to_string(),
+            )
+            .into())
+        }
+    };
+    check_finite(&[(axis.as_str(), value)])?;
+
+    let _guard = lock()?;
+    let z = load(&request.fluid)?;
+    let table = RefpropFunctionLibrary::saturation_table(axis, &[value], &z)?;
+
+    to_json(&table.rows[0])
+}
+
+fn transport(request: TransportRequest) -> Result<Value, ApiError> {
+    check_finite(&[("T_K", request.T_K), ("D_mol_per_L", request.D_mol_per_L)])?;
+
+    let _guard = lock()?;
+    let z = load(&request.fluid)?;
+    let output =
+        RefpropFunctionLibrary::transport_properties(request.T_K, request.D_mol_per_L, &z)?;
+
+    to_json(&output)
+}
+
+fn critical(request: CriticalRequest) -> Result<Value, ApiError> {
+    let _guard = lock()?;
+    let z = load(&request.fluid)?;
+    let output = RefpropFunctionLibrary::critical_parameters(&z)?;
+
+    to_json(&output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_recovers_from_poisoning() {
+        let result = thread::spawn(|| {
+            let _guard = RefpropFunctionLibrary::lock().unwrap();
+            panic!("poison the lock");
+        })
+        .join();
+        assert!(result.is_err());
+
+        assert!(lock().is_ok());
+        assert!(RefpropFunctionLibrary::lock().is_ok());
+    }
+}
@@ -0,0 +1,599 @@
+//! Command-line tool for quick property lookups with REFPROP.
+//!
+//! ```text
+//! refprop flash --fluid R454B --tp 300 101.325
+//! refprop --units "MASS SI" sat --fluid WATER --temperature 373.15
+//! refprop --json crit --fluid "R32;R1234YF" --composition 0.689,0.311 --mass
+//! refprop table --fluid R454B saturation --from 250 --to 320 --step 5
+//! ```
+#![allow(non_snake_case)]
+
+use std::{io, process::ExitCode};
+
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use refprop_sys::{
+    Composition, CompositionBasis, FlashOutput, InputPair, Quantity, RefpropError,
+    RefpropFunctionLibrary, SaturationAxis, SaturationStatus, StateRegion, Units,
+};
+use serde_json::{json, Map, Value};
+
+#[derive(Parser)]
+#[command(
+    name = "refprop",
+    version,
+    about = "Quick thermodynamic property lookups with REFPROP"
+)]
+struct Cli {
+    /// Path to the REFPROP installation (defaults to RPPREFIX, then the common installation directories)
+    #[arg(long, global = true)]
+    path: Option<String>,
+
+    /// Unit system of the inputs and outputs: DEFAULT, MOLAR SI, MASS SI, SI WITH C, MOLAR BASE SI, or MASS BASE SI
+    #[arg(long, global = true, default_value = "DEFAULT", value_parser = parse_units)]
+    units: Units,
+
+    /// Print JSON instead of human-readable output
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Flash calculation from a pair of independent properties
+    Flash {
+        #[command(flatten)]
+        fluid: FluidArgs,
+        #[command(flatten)]
+        pair: PairArgs,
+    },
+    /// Saturated liquid (bubble point) and vapor (dew point) states
+    Sat {
+        #[command(flatten)]
+        fluid: FluidArgs,
+        #[command(flatten)]
+        at: SaturationArgs,
+    },
+    /// Critical point
+    Crit {
+        #[command(flatten)]
+        fluid: FluidArgs,
+    },
+    /// Viscosity and thermal conductivity of a single-phase state
+    Transport {
+        #[command(flatten)]
+        fluid: FluidArgs,
+        #[command(flatten)]
+        pair: PairArgs,
+    },
+    /// Saturation or superheated vapor table, written as CSV (or JSON with --json) in the default units
+    Table {
+        #[command(flatten)]
+        fluid: FluidArgs,
+        #[command(subcommand)]
+        table: TableCommand,
+    },
+}
+
+impl Command {
+    fn fluid(&self) -> &FluidArgs {
+        match self {
+            Command::Flash { fluid, .. }
+            | Command::Sat { fluid, .. }
+            | Command::Crit { fluid }
+            | Command::Transport { fluid, .. }
+            | Command::Table { fluid, .. } => fluid,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum TableCommand {
+    /// Saturation table on evenly spaced temperatures or pressures
+    Saturation {
+        /// Independent variable of the table
+        #[arg(long, value_enum, default_value = "temperature")]
+        axis: Axis,
+        /// First value [K or kPa]
+        #[arg(long)]
+        from: f64,
+        /// Last value [K or kPa]
+        #[arg(long)]
+        to: f64,
+        /// Step between values [K or kPa]
+        #[arg(long)]
+        step: f64,
+    },
+    /// Superheated vapor table on a pressure/temperature grid
+    Superheated {
+        /// Comma-separated pressures [kPa]
+        #[arg(long, value_delimiter = ',', required = true)]
+        pressures: Vec<f64>,
+        /// Comma-separated temperatures [K]
+        #[arg(long, value_delimiter = ',', required = true)]
+        temperatures: Vec<f64>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Axis {
+    Temperature,
+    Pressure,
+}
+
+#[derive(Args)]
+struct FluidArgs {
+    /// Predefined mixture (e.g. R454B), pure fluid, or ';'-separated list of fluids
+    #[arg(long)]
+    fluid: String,
+
+    /// Comma-separated mole fractions of a list of fluids
+    #[arg(long, value_delimiter = ',')]
+    composition: Option<Vec<f64>>,
+
+    /// Interpret --composition as mass fractions
+    #[arg(long, requires = "composition")]
+    mass: bool,
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("pair").required(true).args([
+    "tp", "ph", "ps", "pd", "pe", "td", "dh", "ds", "de", "hs", "tq", "pq",
+])))]
+struct PairArgs {
+    /// Temperature and pressure
+    #[arg(long, num_args = 2, value_names = ["T", "P"], allow_negative_numbers = true)]
+    tp: Option<Vec<f64>>,
+    /// Pressure and enthalpy
+    #[arg(long, num_args = 2, value_names = ["P", "H"], allow_negative_numbers = true)]
+    ph: Option<Vec<f64>>,
+    /// Pressure and entropy
+    #[arg(long, num_args = 2, value_names = ["P", "S"], allow_negative_numbers = true)]
+    ps: Option<Vec<f64>>,
+    /// Pressure and density
+    #[arg(long, num_args = 2, value_names = ["P", "D"], allow_negative_numbers = true)]
+    pd: Option<Vec<f64>>,
+    /// Pressure and internal energy
+    #[arg(long, num_args = 2, value_names = ["P", "E"], allow_negative_numbers = true)]
+    pe: Option<Vec<f64>>,
+    /// Temperature and density
+    #[arg(long, num_args = 2, value_names = ["T", "D"], allow_negative_numbers = true)]
+    td: Option<Vec<f64>>,
+    /// Density and enthalpy
+    #[arg(long, num_args = 2, value_names = ["D", "H"], allow_negative_numbers = true)]
+    dh: Option<Vec<f64>>,
+    /// Density and entropy
+    #[arg(long, num_args = 2, value_names = ["D", "S"], allow_negative_numbers = true)]
+    ds: Option<Vec<f64>>,
+    /// Density and internal energy
+    #[arg(long, num_args = 2, value_names = ["D", "E"], allow_negative_numbers = true)]
+    de: Option<Vec<f64>>,
+    /// Enthalpy and entropy
+    #[arg(long, num_args = 2, value_names = ["H", "S"], allow_negative_numbers = true)]
+    hs: Option<Vec<f64>>,
+    /// Temperature and molar quality
+    #[arg(long, num_args = 2, value_names = ["T", "Q"], allow_negative_numbers = true)]
+    tq: Option<Vec<f64>>,
+    /// Pressure and molar quality
+    #[arg(long, num_args = 2, value_names = ["P", "Q"], allow_negative_numbers = true)]
+    pq: Option<Vec<f64>>,
+}
+
+impl PairArgs {
+    /// Returns the selected input pair and its two values.
+    fn selected(&self) -> (InputPair, f64, f64) {
+        let options = [
+            (InputPair::TP, &self.tp),
+            (InputPair::PH, &self.ph),
+            (InputPair::PS, &self.ps),
+            (InputPair::PD, &self.pd),
+            (InputPair::PE, &self.pe),
+            (InputPair::TD, &self.td),
+            (InputPair::DH, &self.dh),
+            (InputPair::DS, &self.ds),
+            (InputPair::DE, &self.de),
+            (InputPair::HS, &self.hs),
+            (InputPair::TQ, &self.tq),
+            (InputPair::PQ, &self.pq),
+        ];
+        options
+            .into_iter()
+            .find_map(|(pair, values)| values.as_ref().map(|v| (pair, v[0], v[1])))
+            .expect("clap requires exactly one input pair")
+    }
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("at").required(true).args(["temperature", "pressure"])))]
+struct SaturationArgs {
+    /// Saturation temperature
+    #[arg(long)]
+    temperature: Option<f64>,
+    /// Saturation pressure
+    #[arg(long)]
+    pressure: Option<f64>,
+}
+
+/// The loaded fluid: its mole fractions and molar mass [g/mol].
+struct Fluid {
+    z: Vec<f64>,
+    molar_mass: f64,
+}
+
+/// A named output value.
+enum Field {
+    Scalar(Quantity, f64),
+    /// Value of a single phase and the molar mass [g/mol] of that phase, used for mass units
+    PhaseScalar(Quantity, f64, f64),
+    Fractions(Vec<f64>),
+    Text(String),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<(), RefpropError> {
+    let units = &cli.units;
+    if let Command::Table { .. } = cli.command {
+        if !matches!(units, Units::Default) {
+            return Err(RefpropError::InvalidInput(
+                "Tables are written in the default units; omit --units.".to_string(),
+            ));
+        }
+    }
+    RefpropFunctionLibrary::set_path(cli.path.as_deref())?;
+    let fluid = load(cli.command.fluid())?;
+
+    let fields = match &cli.command {
+        Command::Flash { pair, .. } => state_fields(&flash(units, &fluid, pair)?)?,
+        Command::Sat { at, .. } => saturation_fields(units, &fluid, at)?,
+        Command::Crit { .. } => {
+            let critical = RefpropFunctionLibrary::critical_parameters(&fluid.z)?;
+            vec![
+                ("Tc", Field::Scalar(Quantity::Temperature, critical.Tc)),
+                ("Pc", Field::Scalar(Quantity::Pressure, critical.Pc)),
+                ("Dc", Field::Scalar(Quantity::Density, critical.Dc)),
+            ]
+        }
+        Command::Transport { pair, .. } => {
+            let state = flash(units, &fluid, pair)?;
+            let transport =
+                RefpropFunctionLibrary::transport_properties(state.T, state.D, &fluid.z)?;
+            vec![
+                ("T", Field::Scalar(Quantity::Temperature, state.T)),
+                ("P", Field::Scalar(Quantity::Pressure, state.P)),
+                ("D", Field::Scalar(Quantity::Density, state.D)),
+                ("eta", Field::Scalar(Quantity::Viscosity, transport.eta)),
+                (
+                    "tcx",
+                    Field::Scalar(Quantity::ThermalConductivity, transport.tcx),
+                ),
+            ]
+        }
+        Command::Table { table, .. } => return write_table(table, &fluid, cli.json),
+    };
+
+    print_fields(&fields, units, fluid.molar_mass, cli.json)
+}
+
+/// Parses a unit system, accepting only the systems the output can be converted to.
+fn parse_units(s: &str) -> Result<Units, String> {
+    let units = s.parse::<Units>().map_err(|e| e.to_string())?;
+    units.label(Quantity::Temperature).map_err(|_| {
+        format!(
+            "Unsupported unit system: {}; use DEFAULT, MOLAR SI, MASS SI, SI WITH C, MOLAR BASE SI, or MASS BASE SI",
+            s
+        )
+    })?;
+
+    Ok(units)
+}
+
+/// Loads the fluid with `set_fluids` and its composition, or else with `set_mixture_or_fluid`.
+fn load(args: &FluidArgs) -> Result<Fluid, RefpropError> {
+    let z = match &args.composition {
+        Some(fractions) => {
+            RefpropFunctionLibrary::set_fluids(&args.fluid)?;
+            let basis = if args.mass {
+                CompositionBasis::Mass
+            } else {
+                CompositionBasis::Mole
+            };
+            Composition::new(fractions.clone(), basis)?.mole_fractions()?
+        }
+        None if args.fluid.contains(';') => {
+            return Err(RefpropError::InvalidInput(
+                "A list of fluids requires --composition.".to_string(),
+            ))
+        }
+        None => RefpropFunctionLibrary::set_mixture_or_fluid(&args.fluid)?,
+    };
+    let molar_mass = RefpropFunctionLibrary::calc_molar_mass(&z)?;
+
+    Ok(Fluid { z, molar_mass })
+}
+
+/// Converts the inputs of a pair to the default units and flashes them.
+fn flash(units: &Units, fluid: &Fluid, pair: &PairArgs) -> Result<FlashOutput, RefpropError> {
+    let (pair, a, b) = pair.selected();
+    let (qa, qb) = pair.quantities();
+    let a = units.to_default(qa, a, fluid.molar_mass)?;
+    let b = units.to_default(qb, b, fluid.molar_mass)?;
+
+    RefpropFunctionLibrary::flash(pair, a, b, &fluid.z)
+}
+
+fn state_fields(state: &FlashOutput) -> Result<Vec<(&'static str, Field)>, RefpropError> {
+    let mut fields = vec![
+        (
+            "region",
+            Field::Text(StateRegion::from_quality(state.q).as_str().to_string()),
+        ),
+        ("T", Field::Scalar(Quantity::Temperature, state.T)),
+        ("P", Field::Scalar(Quantity::Pressure, state.P)),
+        ("D", Field::Scalar(Quantity::Density, state.D)),
+        ("q", Field::Scalar(Quantity::Quality, state.q)),
+        ("e", Field::Scalar(Quantity::Energy, state.e)),
+        ("h", Field::Scalar(Quantity::Energy, state.h)),
+        ("s", Field::Scalar(Quantity::Entropy, state.s)),
+    ];
+    if let Some(Cv) = state.Cv {
+        fields.push(("Cv", Field::Scalar(Quantity::Entropy, Cv)));
+    }
+    if let Some(Cp) = state.Cp {
+        fields.push(("Cp", Field::Scalar(Quantity::Entropy, Cp)));
+    }
+    fields.push(("w", Field::Scalar(Quantity::Speed, state.w)));
+    if (0.0..=1.0).contains(&state.q) {
+        let liquid_molar_mass = RefpropFunctionLibrary::calc_molar_mass(&state.x)?;
+        let vapor_molar_mass = RefpropFunctionLibrary::calc_molar_mass(&state.y)?;
+        fields.extend([
+            (
+                "Dl",
+                Field::PhaseScalar(Quantity::Density, state.Dl, liquid_molar_mass),
+            ),
+            (
+                "Dv",
+                Field::PhaseScalar(Quantity::Density, state.Dv, vapor_molar_mass),
+            ),
+            ("x", Field::Fractions(state.x.clone())),
+            ("y", Field::Fractions(state.y.clone())),
+        ]);
+    }
+    Ok(fields)
+}
+
+fn saturation_fields(
+    units: &Units,
+    fluid: &Fluid,
+    at: &SaturationArgs,
+) -> Result<Vec<(&'static str, Field)>, RefpropError> {
+    let (axis, value) = match (at.temperature, at.pressure) {
+        (Some(T), _) => (
+            SaturationAxis::Temperature,
+            units.to_default(Quantity::Temperature, T, fluid.molar_mass)?,
+        ),
+        (None, Some(P)) => (
+            SaturationAxis::Pressure,
+            units.to_default(Quantity::Pressure, P, fluid.molar_mass)?,
+        ),
+        (None, None) => unreachable!("clap requires a temperature or a pressure"),
+    };
+
+    let table = RefpropFunctionLibrary::saturation_table(axis, &[value], &fluid.z)?;
+    let row = &table.rows[0];
+    if let SaturationStatus::Failed(message) = &row.status {
+        return Err(RefpropError::CalculationError(message.clone()));
+    }
+
+    let mut fields = vec![("status", Field::Text(row.status.as_str().to_string()))];
+    if let Some(l) = &row.liquid {
+        let molar_mass = RefpropFunctionLibrary::calc_molar_mass(&l.composition)?;
+        fields.extend([
+            ("Tl", Field::Scalar(Quantity::Temperature, l.T)),
+            ("Pl", Field::Scalar(Quantity::Pressure, l.P)),
+            ("Dl", Field::PhaseScalar(Quantity::Density, l.D, molar_mass)),
+            ("hl", Field::PhaseScalar(Quantity::Energy, l.h, molar_mass)),
+            ("sl", Field::PhaseScalar(Quantity::Entropy, l.s, molar_mass)),
+            ("x", Field::Fractions(l.composition.clone())),
+        ]);
+    }
+    if let Some(v) = &row.vapor {
+        let molar_mass = RefpropFunctionLibrary::calc_molar_mass(&v.composition)?;
+        fields.extend([
+            ("Tv", Field::Scalar(Quantity::Temperature, v.T)),
+            ("Pv", Field::Scalar(Quantity::Pressure, v.P)),
+            ("Dv", Field::PhaseScalar(Quantity::Density, v.D, molar_mass)),
+            ("hv", Field::PhaseScalar(Quantity::Energy, v.h, molar_mass)),
+            ("sv", Field::PhaseScalar(Quantity::Entropy, v.s, molar_mass)),
+            ("y", Field::Fractions(v.composition.clone())),
+        ]);
+    }
+    Ok(fields)
+}
+
+fn write_table(table: &TableCommand, fluid: &Fluid, json: bool) -> Result<(), RefpropError> {
+    let stdout = io::stdout().lock();
+    let written = match table {
+        TableCommand::Saturation {
+            axis,
+            from,
+            to,
+            step,
+        } => {
+            if *step <= 0.0 || to < from {
+                return Err(RefpropError::InvalidInput(
+                    "The table range requires --from <= --to and a positive --step.".to_string(),
+                ));
+            }
+            let n = ((to - from) / step + 1e-9).floor() as usize + 1;
+            let values: Vec<f64> = (0..n).map(|i| from + step * i as f64).collect();
+            let axis = match axis {
+                Axis::Temperature => SaturationAxis::Temperature,
+                Axis::Pressure => SaturationAxis::Pressure,
+            };
+            let table = RefpropFunctionLibrary::saturation_table(axis, &values, &fluid.z)?;
+            if json {
+                table.write_json(stdout)
+            } else {
+                table.write_csv(stdout)
+            }
+        }
+        TableCommand::Superheated {
+            pressures,
+            temperatures,
+        } => {
+            let table =
+                RefpropFunctionLibrary::superheated_table(pressures, temperatures, &fluid.z)?;
+            if json {
+                table.write_json(stdout)
+            } else {
+                table.write_csv(stdout)
+            }
+        }
+    };
+
+    written.map_err(|e| RefpropError::UnknownError(format!("Failed to write table: {}", e)))
+}
+
+/// Converts a scalar field from the default units and returns it with its unit.
+///
+/// Values of a single phase are converted with the molar mass of that phase, others with `molar_mass`.
+fn convert(
+    field: &Field,
+    units: &Units,
+    molar_mass: f64,
+) -> Result<(f64, &'static str), RefpropError> {
+    let (quantity, value, molar_mass) = match *field {
+        Field::Scalar(quantity, value) => (quantity, value, molar_mass),
+        Field::PhaseScalar(quantity, value, phase_molar_mass) => {
+            (quantity, value, phase_molar_mass)
+        }
+        Field::Fractions(_) | Field::Text(_) => unreachable!("not a scalar field"),
+    };
+    Ok((
+        units.from_default(quantity, value, molar_mass)?,
+        units.label(quantity)?,
+    ))
+}
+
+fn print_fields(
+    fields: &[(&str, Field)],
+    units: &Units,
+    molar_mass: f64,
+    json: bool,
+) -> Result<(), RefpropError> {
+    if json {
+        let mut object = Map::new();
+        for (name, field) in fields {
+            let value = match field {
+                Field::Scalar(..) | Field::PhaseScalar(..) => {
+                    let (value, unit) = convert(field, units, molar_mass)?;
+                    json!({ "value": value, "unit": unit })
+                }
+                Field::Fractions(fractions) => json!(fractions),
+                Field::Text(text) => json!(text),
+            };
+            object.insert(name.to_string(), value);
+        }
+        println!("{}", Value::Object(object));
+    } else {
+        for (name, field) in fields {
+            match field {
+                Field::Scalar(..) | Field::PhaseScalar(..) => {
+                    let (value, unit) = convert(field, units, molar_mass)?;
+                    println!("{:>6} = {:>14.6} {}", name, value, unit)
+                }
+                Field::Fractions(fractions) => println!("{:>6} = {:?}", name, fractions),
+                Field::Text(text) => println!("{:>6} = {}", name, text),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("refprop").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_parse_pair() {
+        let cli = parse(&["flash", "--fluid", "R454B", "--ph", "101.325", "-200"]).unwrap();
+        let Command::Flash { pair, .. } = &cli.command else {
+            panic!("expected the flash command");
+        };
+        assert_eq!(pair.selected(), (InputPair::PH, 101.325, -200.0));
+
+        // Exactly one input pair is required
+        assert!(parse(&["flash", "--fluid", "R454B"]).is_err());
+        assert!(
+            parse(&["flash", "--fluid", "R454B", "--tp", "300", "100", "--pq", "100", "0"])
+                .is_err()
+        );
+        // --mass only applies to a composition
+        assert!(parse(&["crit", "--fluid", "R32;R1234YF", "--mass"]).is_err());
+        assert!(parse(&["--units", "IMPERIAL", "crit", "--fluid", "R32"]).is_err());
+    }
+
+    #[test]
+    fn test_tables_require_default_units() {
+        let cli = parse(&[
+            "--units",
+            "MASS SI",
+            "table",
+            "--fluid",
+            "R454B",
+            "superheated",
+            "--pressures",
+            "100",
+            "--temperatures",
+            "300",
+        ])
+        .unwrap();
+
+        // Rejected before REFPROP is loaded
+        assert!(matches!(run(&cli), Err(RefpropError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_unsupported_units_are_rejected() {
+        assert!(parse_units("mass-si").is_ok());
+        assert!(
+            Cli::try_parse_from(["refprop", "--units", "MASS SI", "crit", "--fluid", "R32"])
+                .is_ok()
+        );
+        for name in ["ENGLISH", "MOLAR ENGLISH", "MKS", "CGS", "MIXED", "MEUNITS"] {
+            assert!(parse_units(name).is_err(), "{} should be rejected", name);
+            assert!(
+                Cli::try_parse_from(["refprop", "--units", name, "crit", "--fluid", "R32"])
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_phase_fields_use_their_molar_mass() -> Result<(), RefpropError> {
+        let units: Units = "MASS SI".parse()?;
+        let bulk = Field::Scalar(Quantity::Density, 10.0);
+        let liquid = Field::PhaseScalar(Quantity::Density, 10.0, 50.0);
+
+        let (bulk, _) = convert(&bulk, &units, 100.0)?;
+        let (liquid, _) = convert(&liquid, &units, 100.0)?;
+        assert_eq!(bulk, 2.0 * liquid);
+        Ok(())
+    }
+}
@@ -0,0 +1,383 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    catalogue::{read_text, subdirectory},
+    setup::set_path::active_path,
+    FluidCatalogue, FluidEntry, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Origin of the interaction parameters of a binary pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BinaryParameterSource {
+    /// Parameters fitted to experimental data, listed in `HMX.BNC`.
+    Fitted,
+    /// Parameters listed in `HMX.BNC` but obtained from an estimation scheme rather than fitted.
+    Estimated,
+    /// Pair not listed in `HMX.BNC`; REFPROP estimates the parameters when the fluids are loaded.
+    Missing,
+}
+
+/// Binary entry of an `HMX.BNC` file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryEntry {
+    /// Names of the pair as given in the file (e.g., "R32/R1234YF")
+    pub name: String,
+    /// CAS numbers of the two components
+    pub cas: (String, String),
+    /// Three-character mixing model (e.g., "KW0")
+    pub model: String,
+    /// Parameters of the mixing model
+    pub parameters: Vec<f64>,
+    /// Whether the comments of the entry describe the parameters as estimated
+    pub estimated: bool,
+}
+
+impl BinaryEntry {
+    /// Returns whether the entry describes the pair of the given CAS numbers, in either order.
+    pub fn matches(&self, first: &str, second: &str) -> bool {
+        (self.cas.0 == first && self.cas.1 == second)
+            || (self.cas.0 == second && self.cas.1 == first)
+    }
+}
+
+/// Binary interaction parameters of REFPROP's `HMX.BNC` file.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryParameterFile {
+    entries: Vec<BinaryEntry>,
+}
+
+/// Coverage of one binary pair, part of a `BinaryCoverage` report.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryPairCoverage {
+    /// Fluid file of the first component (without extension)
+    pub first: String,
+    /// Fluid file of the second component (without extension)
+    pub second: String,
+    /// Origin of the interaction parameters
+    pub source: BinaryParameterSource,
+    /// Mixing model listed in `HMX.BNC`, or `None` for missing pairs
+    pub model: Option<String>,
+}
+
+/// Report of the interaction parameters available for every binary pair of a set of components.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryCoverage {
+    /// Coverage of each pair, in the order of the components
+    pub pairs: Vec<BinaryPairCoverage>,
+}
+
+impl BinaryCoverage {
+    /// Returns `true` if every pair has fitted parameters.
+    pub fn is_complete(&self) -> bool {
+        self.pairs
+            .iter()
+            .all(|pair| pair.source == BinaryParameterSource::Fitted)
+    }
+
+    /// Returns the pairs with the given origin of their parameters.
+    pub fn with_source(
+        &self,
+        source: BinaryParameterSource,
+    ) -> impl Iterator<Item = &BinaryPairCoverage> {
+        self.pairs.iter().filter(move |pair| pair.source == source)
+    }
+}
+
+/// Returns whether `cas` looks like a CAS registry number (e.g., "75-10-5").
+fn is_cas(cas: &str) -> bool {
+    let parts: Vec<&str> = cas.split('-').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Returns the `HMX.BNC` file in the `FLUIDS` subdirectory of a REFPROP directory.
+pub(crate) fn hmx_path(root: &Path) -> Result<PathBuf, RefpropError> {
+    subdirectory(root, "FLUIDS")
+        .map(|directory| directory.join("HMX.BNC"))
+        .filter(|path| path.is_file())
+        .ok_or_else(|| {
+            RefpropError::InvalidInput(format!("No FLUIDS/HMX.BNC in {}", root.display()))
+        })
+}
+
+/// Returns the REFPROP directory last set with `set_path`.
+pub(crate) fn active_root() -> Result<PathBuf, RefpropError> {
+    active_path().ok_or_else(|| {
+        RefpropError::InitializationError("No REFPROP path has been set with set_path".to_string())
+    })
+}
+
+impl BinaryParameterFile {
+    /// Parses the text of an `HMX.BNC` file.
+    ///
+    /// Each binary entry of the `#BNC` section consists of comment lines starting with `?` (the first
+    /// of which names the pair, e.g. `?R32/R1234YF`), a line with the CAS numbers of the components
+    /// separated by a slash, and a line with the mixing model and its parameters. Entries whose
+    /// comments mention estimation are marked as estimated.
+    pub fn parse(text: &str) -> Self {
+        let mut entries = Vec::new();
+        let mut in_bnc = true;
+        let mut name = String::new();
+        let mut comments = String::new();
+        let mut pending: Option<(String, (String, String), bool)> = None;
+
+        for line in text.lines() {
+            if line.starts_with('#') {
+                in_bnc = line.starts_with("#BNC");
+                pending = None;
+                continue;
+            }
+            if !in_bnc {
+                continue;
+            }
+
+            let trimmed = line.trim();
+            if let Some(comment) = trimmed.strip_prefix('?') {
+                // A comment starting with a pair name (e.g. `?R32/R1234YF`) opens a new entry
+                if let Some(pair) = comment.split_whitespace().next().filter(|first| {
+                    first.contains('/') && !comment.starts_with(char::is_whitespace)
+                }) {
+                    name = pair.to_string();
+                    comments.clear();
+                }
+                comments.push_str(&comment.to_lowercase());
+                comments.push('\n');
+                continue;
+            }
+            if trimmed.is_empty() || trimmed.starts_with('!') {
+                continue;
+            }
+
+            let mut tokens = trimmed.split_whitespace();
+            let Some(first) = tokens.next() else {
+                continue;
+            };
+
+            if let Some((a, b)) = first
+                .split_once('/')
+                .filter(|(a, b)| is_cas(a) && is_cas(b))
+            {
+                let estimated = comments.contains("estimat") || comments.contains("predict");
+                pending = Some((
+                    std::mem::take(&mut name),
+                    (a.to_string(), b.to_string()),
+                    estimated,
+                ));
+                comments.clear();
+            } else if let Some((name, cas, estimated)) = pending.take() {
+                // The first line after the CAS numbers holds the model and its parameters
+                let parameters = tokens
+                    .take_while(|token| !token.starts_with('!'))
+                    .map_while(|token| token.parse::<f64>().ok())
+                    .collect();
+                entries.push(BinaryEntry {
+                    name,
+                    cas,
+                    model: first.to_uppercase(),
+                    parameters,
+                    estimated,
+                });
+            }
+        }
+
+        BinaryParameterFile { entries }
+    }
+
+    /// Reads and parses an `HMX.BNC` file.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the file cannot be read.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, RefpropError> {
+        Ok(Self::parse(&read_text(path.as_ref())?))
+    }
+
+    /// Reads the `HMX.BNC` file of the directory last set with `RefpropFunctionLibrary::set_path`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InitializationError` if `set_path` has not been called.
+    /// - Returns `RefpropError::InvalidInput` if the file does not exist or cannot be read.
+    pub fn from_active_path() -> Result<Self, RefpropError> {
+        Self::read(hmx_path(&active_root()?)?)
+    }
+
+    /// Returns the binary entries of the file.
+    pub fn entries(&self) -> &[BinaryEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry of the pair of the given CAS numbers, in either order.
+    pub fn entry(&self, first: &str, second: &str) -> Option<&BinaryEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.matches(first, second))
+    }
+
+    /// Reports the interaction parameters available for every binary pair of the given fluids.
+    ///
+    /// Pairs involving a fluid without a CAS number (e.g., a pseudo-pure fluid) are reported as missing.
+    pub fn coverage(&self, fluids: &[&FluidEntry]) -> BinaryCoverage {
+        let mut pairs = Vec::new();
+        for (i, first) in fluids.iter().enumerate() {
+            for second in &fluids[i + 1..] {
+                let entry = match (&first.cas, &second.cas) {
+                    (Some(a), Some(b)) => self.entry(a, b),
+                    _ => None,
+                };
+                let source = match entry {
+                    Some(entry) if entry.estimated => BinaryParameterSource::Estimated,
+                    Some(_) => BinaryParameterSource::Fitted,
+                    None => BinaryParameterSource::Missing,
+                };
+                pairs.push(BinaryPairCoverage {
+                    first: first.file_name.clone(),
+                    second: second.file_name.clone(),
+                    source,
+                    model: entry.map(|entry| entry.model.clone()),
+                });
+            }
+        }
+
+        BinaryCoverage { pairs }
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Reports the binary interaction parameters available for the fluids of a `set_fluids` string.
+    ///
+    /// The fluids are looked up in the catalogue of the directory last set with `set_path` and their
+    /// pairs in its `HMX.BNC` file, without calling REFPROP. Checking the report before `set_fluids`
+    /// reveals pairs for which REFPROP would silently estimate the interaction parameters.
+    ///
+    /// # Parameters
+    ///
+    /// - `fluids`: Fluid names separated by `|`, `;`, or `*`, as accepted by `set_fluids`.
+    ///
+    /// # Returns
+    ///
+    /// - `BinaryCoverage`: The coverage of every binary pair.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InitializationError` if `set_path` has not been called.
+    /// - Returns `RefpropError::InvalidInput` if a fluid is not in the catalogue or `HMX.BNC` cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{BinaryParameterSource, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///
+    ///     let coverage = RefpropFunctionLibrary::binary_coverage("R32;R1234YF;R134A")?;
+    ///     for pair in coverage.with_source(BinaryParameterSource::Missing) {
+    ///         println!("Estimated by REFPROP: {}/{}", pair.first, pair.second);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn binary_coverage(fluids: &str) -> Result<BinaryCoverage, RefpropError> {
+        let root = active_root()?;
+        let catalogue = FluidCatalogue::scan(&root)?;
+        let binary_parameters = BinaryParameterFile::read(hmx_path(&root)?)?;
+
+        let mut entries = Vec::new();
+        for fluid in fluids
+            .split(['|', ';', '*'])
+            .map(str::trim)
+            .filter(|fluid| !fluid.is_empty())
+        {
+            // Accept file names with a directory or an extension, e.g. "FLUIDS/R32.FLD"
+            let name = Path::new(fluid)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(fluid);
+            let name = name
+                .rsplit_once('.')
+                .filter(|(_, extension)| {
+                    extension.eq_ignore_ascii_case("FLD") || extension.eq_ignore_ascii_case("PPF")
+                })
+                .map_or(name, |(stem, _)| stem);
+            entries.push(
+                catalogue.find_fluid(name).ok_or_else(|| {
+                    RefpropError::InvalidInput(format!("Unknown fluid '{}'", fluid))
+                })?,
+            );
+        }
+
+        Ok(binary_parameters.coverage(&entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FluidFileKind;
+
+    const HMX_BNC: &str = "\
+HMX               !Mnemonic for mixture model, must be first line of this file.
+#BNC              !Binary mixing coefficients
+BNC
+? Binary mixing coefficients for the various mixing rules used with the HMX model
+!
+?R32/R1234YF                             [R32/R1234YF]
+?  fitted to VLE and PVT data
+  75-10-5/754-12-1                       !CAS numbers
+  KW0  1.0  0.9  1.0  1.0  0.          !betaT, gammaT, betaV, gammaV, Fij
+!
+?R32/R1336MZZZ                           [R32/R1336MZZ(Z)]
+?  estimated with the scheme of Bell
+  75-10-5/692-49-9                       !CAS numbers
+  KW0  1.0  1.1  1.0  1.0  0.
+!
+#MXM              !Mixture model specification
+XR0  xx/xx/xx
+";
+
+    fn fluid(name: &str, cas: &str) -> FluidEntry {
+        let text = format!("{}  !Short name\n{}  !CAS number\n", name, cas);
+        FluidEntry::parse(&text, name, FluidFileKind::Fluid, PathBuf::from(name)).unwrap()
+    }
+
+    #[test]
+    fn test_binary_coverage() {
+        let file = BinaryParameterFile::parse(HMX_BNC);
+        assert_eq!(file.entries().len(), 2);
+
+        let entry = file.entry("754-12-1", "75-10-5").unwrap();
+        assert_eq!(entry.name, "R32/R1234YF");
+        assert_eq!(entry.model, "KW0");
+        assert_eq!(entry.parameters, vec![1.0, 0.9, 1.0, 1.0, 0.0]);
+        assert!(!entry.estimated);
+
+        let (r32, r1234yf) = (fluid("R32", "75-10-5"), fluid("R1234YF", "754-12-1"));
+        let (r1336mzzz, r134a) = (fluid("R1336MZZZ", "692-49-9"), fluid("R134A", "811-97-2"));
+        let coverage = file.coverage(&[&r32, &r1234yf, &r1336mzzz, &r134a]);
+
+        let sources: Vec<BinaryParameterSource> =
+            coverage.pairs.iter().map(|pair| pair.source).collect();
+        assert_eq!(
+            &sources[..3],
+            &[
+                BinaryParameterSource::Fitted,
+                BinaryParameterSource::Estimated,
+                BinaryParameterSource::Missing
+            ]
+        );
+        assert!(!coverage.is_complete());
+        assert_eq!(
+            coverage.with_source(BinaryParameterSource::Missing).count(),
+            4
+        );
+        assert!(file.coverage(&[&r32, &r1234yf]).is_complete());
+    }
+}
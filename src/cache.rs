@@ -0,0 +1,270 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{
+    utils::{acquire_lock, setup_generation},
+    CompositionInput, FlashOutput, InputPair, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Hit and miss statistics of a `FlashCache`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheStats {
+    /// Number of calls answered from the cache
+    pub hits: u64,
+    /// Number of calls evaluated by REFPROP
+    pub misses: u64,
+    /// Number of entries dropped to stay within the capacity
+    pub evictions: u64,
+    /// Number of times the cache was emptied because the REFPROP setup changed
+    pub invalidations: u64,
+    /// Number of cached results
+    pub len: usize,
+    /// Maximum number of cached results
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Returns the fraction of calls answered from the cache, or 0 if there were no calls.
+    pub fn hit_rate(&self) -> f64 {
+        let calls = self.hits + self.misses;
+        if calls == 0 {
+            0.0
+        } else {
+            self.hits as f64 / calls as f64
+        }
+    }
+}
+
+/// Identifies a flash calculation by its routine, the bit patterns of its inputs, and its composition.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    pair: InputPair,
+    inputs: [u64; 2],
+    z: Vec<u64>,
+}
+
+struct CacheState {
+    /// Setup generation the cached results belong to
+    generation: u64,
+    /// Cached results with the tick of their last use
+    entries: HashMap<CacheKey, (FlashOutput, u64)>,
+    /// Keys ordered from least to most recently used
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+/// Least-recently-used cache in front of the flash routines.
+///
+/// Results are keyed on the input pair, the exact bit patterns of the inputs, and the composition,
+/// and belong to the REFPROP setup they were calculated with: loading fluids (`set_fluids`,
+/// `set_mixture`, `pure_fld`), changing the reference state (`set_reference_state`), changing mixing
+/// parameters (`set_mixing_parameters`), or changing flags and models (`set_flag`,
+/// `set_equation_of_state`) empties the cache on its next use. Failed calculations are not cached.
+///
+/// The cache can be shared between threads; every call holds the global REFPROP lock while the
+/// result is looked up or calculated.
+pub struct FlashCache {
+    state: Mutex<CacheState>,
+}
+
+impl FlashCache {
+    /// Creates a cache holding at most `capacity` results.
+    pub fn new(capacity: usize) -> Self {
+        FlashCache {
+            state: Mutex::new(CacheState {
+                generation: setup_generation(),
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                tick: 0,
+                stats: CacheStats {
+                    capacity,
+                    ..CacheStats::default()
+                },
+            }),
+        }
+    }
+
+    /// Returns the hit and miss statistics of the cache.
+    pub fn stats(&self) -> CacheStats {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        CacheStats {
+            len: state.entries.len(),
+            ..state.stats
+        }
+    }
+
+    /// Removes all cached results, keeping the statistics.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.entries.clear();
+        state.recency.clear();
+    }
+
+    /// Performs a flash calculation for any supported pair of independent properties, reusing a cached result if possible.
+    ///
+    /// # Parameters
+    ///
+    /// - `pair`: The pair of independent properties.
+    /// - `a`: The first property of the pair, in the default molar units.
+    /// - `b`: The second property of the pair, in the default molar units.
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `FlashOutput`: A struct containing the calculated properties.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `RefpropFunctionLibrary::flash`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{FlashCache, InputPair, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let cache = FlashCache::new(1024);
+    ///     let first = cache.flash(InputPair::TP, 300.0, 101.325, &z)?;
+    ///     let second = cache.flash(InputPair::TP, 300.0, 101.325, &z)?;
+    ///
+    ///     assert_eq!(first.h, second.h);
+    ///     assert_eq!(cache.stats().hits, 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn flash(
+        &self,
+        pair: InputPair,
+        a: f64,
+        b: f64,
+        z: impl Into<CompositionInput<'_>>,
+    ) -> Result<FlashOutput, RefpropError> {
+        // Keep the REFPROP setup fixed between the lookup and the calculation
+        let _lock = acquire_lock()?;
+
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        let key = CacheKey {
+            pair,
+            inputs: [a.to_bits(), b.to_bits()],
+            z: z.iter().map(|fraction| fraction.to_bits()).collect(),
+        };
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let generation = setup_generation();
+        if state.generation != generation {
+            if !state.entries.is_empty() {
+                state.stats.invalidations += 1;
+            }
+            state.entries.clear();
+            state.recency.clear();
+            state.generation = generation;
+        }
+
+        state.tick += 1;
+        let tick = state.tick;
+        if let Some((output, last_used)) = state.entries.get_mut(&key) {
+            let output = output.clone();
+            let previous = std::mem::replace(last_used, tick);
+            state.recency.remove(&previous);
+            state.recency.insert(tick, key);
+            state.stats.hits += 1;
+            return Ok(output);
+        }
+
+        state.stats.misses += 1;
+        let output = RefpropFunctionLibrary::flash(pair, a, b, &*z)?;
+        if state.stats.capacity == 0 {
+            return Ok(output);
+        }
+
+        while state.entries.len() >= state.stats.capacity {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+            state.stats.evictions += 1;
+        }
+        state.recency.insert(tick, key.clone());
+        state.entries.insert(key, (output.clone(), tick));
+
+        Ok(output)
+    }
+
+    /// Performs a temperature-pressure flash (see `RefpropFunctionLibrary::tp_flash`), reusing a cached result if possible.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `P`: Pressure [kPa].
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `RefpropFunctionLibrary::tp_flash`.
+    pub fn tp_flash(
+        &self,
+        T: f64,
+        P: f64,
+        z: impl Into<CompositionInput<'_>>,
+    ) -> Result<FlashOutput, RefpropError> {
+        self.flash(InputPair::TP, T, P, z)
+    }
+
+    /// Performs a pressure-enthalpy flash (see `RefpropFunctionLibrary::ph_flash`), reusing a cached result if possible.
+    ///
+    /// # Parameters
+    ///
+    /// - `P`: Pressure [kPa].
+    /// - `h`: Enthalpy [J/mol].
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `RefpropFunctionLibrary::ph_flash`.
+    pub fn ph_flash(
+        &self,
+        P: f64,
+        h: f64,
+        z: impl Into<CompositionInput<'_>>,
+    ) -> Result<FlashOutput, RefpropError> {
+        self.flash(InputPair::PH, P, h, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flash_cache() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+
+        let cache = FlashCache::new(2);
+        let first = cache.tp_flash(300.0, 101.325, &z)?;
+        let second = cache.tp_flash(300.0, 101.325, &z)?;
+        assert_eq!(first.h.to_bits(), second.h.to_bits());
+
+        cache.tp_flash(310.0, 101.325, &z)?;
+        cache.tp_flash(320.0, 101.325, &z)?;
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 3, 1));
+
+        // Reloading the fluid invalidates the cached results
+        RefpropFunctionLibrary::set_mixture("R454B")?;
+        cache.tp_flash(320.0, 101.325, &z)?;
+        let stats = cache.stats();
+        assert_eq!((stats.misses, stats.invalidations, stats.len), (4, 1, 1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,591 @@
+//! C ABI of the safe wrapper layer, built with the `capi` feature.
+//!
+//! Fluids are represented by opaque `RefpropFluid` handles that remember how to load themselves.
+//! Every call taking a handle holds the global REFPROP lock while it (re)loads the fluid and runs
+//! the calculation, so handles can be used concurrently from several threads. Functions return a
+//! `RefpropStatus`; the message of the last error on the calling thread is retrieved with
+//! `refprop_last_error`. The C header `include/refprop_sys.h` is generated by cbindgen into
+//! `OUT_DIR` when the crate is built with the feature enabled, and a test checks that the committed
+//! copy is up to date.
+//!
+//! The crate is an `rlib` by default; the shared library exposing the C ABI is built with
+//!
+//! ```text
+//! cargo rustc --release --lib --features capi --crate-type cdylib
+//! ```
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_int, CStr},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{
+    utils::{fluids_generation, validate_fractions},
+    FlashOutput, InputPair, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Maximum number of components of a fluid.
+pub const REFPROP_MAX_COMPONENTS: usize = 20;
+
+/// Outcome of a C API call, mirroring the variants of `RefpropError`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefpropStatus {
+    Ok = 0,
+    InitializationError = 1,
+    CalculationError = 2,
+    InvalidInput = 3,
+    Utf8Error = 4,
+    MutexPoisoned = 5,
+    UnknownError = 6,
+}
+
+/// Pair of independent properties of a flash calculation, in the default molar units.
+///
+/// `refprop_flash` takes the pair as an `int`, so that out-of-range values are rejected.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefpropInputPair {
+    TP = 0,
+    PH = 1,
+    PS = 2,
+    PD = 3,
+    PE = 4,
+    TD = 5,
+    DH = 6,
+    DS = 7,
+    DE = 8,
+    HS = 9,
+    TQ = 10,
+    PQ = 11,
+}
+
+impl TryFrom<c_int> for RefpropInputPair {
+    type Error = RefpropError;
+
+    fn try_from(value: c_int) -> Result<Self, Self::Error> {
+        const PAIRS: [RefpropInputPair; 12] = [
+            RefpropInputPair::TP,
+            RefpropInputPair::PH,
+            RefpropInputPair::PS,
+            RefpropInputPair::PD,
+            RefpropInputPair::PE,
+            RefpropInputPair::TD,
+            RefpropInputPair::DH,
+            RefpropInputPair::DS,
+            RefpropInputPair::DE,
+            RefpropInputPair::HS,
+            RefpropInputPair::TQ,
+            RefpropInputPair::PQ,
+        ];
+        PAIRS
+            .into_iter()
+            .find(|pair| *pair as c_int == value)
+            .ok_or_else(|| {
+                RefpropError::InvalidInput(format!("'pair' {} is not a RefpropInputPair.", value))
+            })
+    }
+}
+
+impl From<RefpropInputPair> for InputPair {
+    fn from(pair: RefpropInputPair) -> Self {
+        match pair {
+            RefpropInputPair::TP => InputPair::TP,
+            RefpropInputPair::PH => InputPair::PH,
+            RefpropInputPair::PS => InputPair::PS,
+            RefpropInputPair::PD => InputPair::PD,
+            RefpropInputPair::PE => InputPair::PE,
+            RefpropInputPair::TD => InputPair::TD,
+            RefpropInputPair::DH => InputPair::DH,
+            RefpropInputPair::DS => InputPair::DS,
+            RefpropInputPair::DE => InputPair::DE,
+            RefpropInputPair::HS => InputPair::HS,
+            RefpropInputPair::TQ => InputPair::TQ,
+            RefpropInputPair::PQ => InputPair::PQ,
+        }
+    }
+}
+
+/// Thermodynamic state calculated by `refprop_flash`, in the default molar units.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RefpropState {
+    /// Temperature [K]
+    pub T: f64,
+    /// Pressure [kPa]
+    pub P: f64,
+    /// Density [mol/L]
+    pub D: f64,
+    /// Density of the liquid phase [mol/L]
+    pub Dl: f64,
+    /// Density of the vapor phase [mol/L]
+    pub Dv: f64,
+    /// Vapor quality on a molar basis [mol/mol]
+    pub q: f64,
+    /// Internal energy [J/mol]
+    pub e: f64,
+    /// Enthalpy [J/mol]
+    pub h: f64,
+    /// Entropy [J/mol-K]
+    pub s: f64,
+    /// Isochoric heat capacity [J/mol-K]; NaN for two-phase states
+    pub Cv: f64,
+    /// Isobaric heat capacity [J/mol-K]; NaN for two-phase states
+    pub Cp: f64,
+    /// Speed of sound [m/s]
+    pub w: f64,
+    /// Number of components, i.e. of valid entries in `x` and `y`
+    pub ncomp: usize,
+    /// Composition of the liquid phase (mole fractions)
+    pub x: [f64; REFPROP_MAX_COMPONENTS],
+    /// Composition of the vapor phase (mole fractions)
+    pub y: [f64; REFPROP_MAX_COMPONENTS],
+}
+
+impl From<&FlashOutput> for RefpropState {
+    fn from(output: &FlashOutput) -> Self {
+        let mut x = [0.0; REFPROP_MAX_COMPONENTS];
+        let mut y = [0.0; REFPROP_MAX_COMPONENTS];
+        x[..output.x.len()].copy_from_slice(&output.x);
+        y[..output.y.len()].copy_from_slice(&output.y);
+
+        RefpropState {
+            T: output.T,
+            P: output.P,
+            D: output.D,
+            Dl: output.Dl,
+            Dv: output.Dv,
+            q: output.q,
+            e: output.e,
+            h: output.h,
+            s: output.s,
+            Cv: output.Cv.unwrap_or(f64::NAN),
+            Cp: output.Cp.unwrap_or(f64::NAN),
+            w: output.w,
+            ncomp: output.x.len(),
+            x,
+            y,
+        }
+    }
+}
+
+/// Transport properties calculated by `refprop_transport`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RefpropTransport {
+    /// Dynamic viscosity [uPa-s]
+    pub eta: f64,
+    /// Thermal conductivity [W/(m-K)]
+    pub tcx: f64,
+}
+
+/// Critical point calculated by `refprop_critical`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RefpropCritical {
+    /// Critical temperature [K]
+    pub Tc: f64,
+    /// Critical pressure [kPa]
+    pub Pc: f64,
+    /// Critical density [mol/L]
+    pub Dc: f64,
+}
+
+/// How a fluid handle loads itself into REFPROP.
+enum FluidSource {
+    /// Predefined mixture or pure fluid, loaded with `set_mixture_or_fluid`
+    Named(String),
+    /// List of fluids, loaded with `set_fluids`
+    Fluids(String),
+}
+
+/// Opaque handle to a fluid definition.
+pub struct RefpropFluid {
+    source: FluidSource,
+    z: Vec<f64>,
+    /// Fluids generation right after this handle last loaded its fluid
+    loaded: AtomicU64,
+}
+
+impl RefpropFluid {
+    /// Loads the fluid unless it is still the active one. Must be called while holding the lock.
+    fn activate(&self) -> Result<(), RefpropError> {
+        if self.loaded.load(Ordering::SeqCst) == fluids_generation() {
+            return Ok(());
+        }
+        match &self.source {
+            FluidSource::Named(name) => {
+                RefpropFunctionLibrary::set_mixture_or_fluid(name)?;
+            }
+            FluidSource::Fluids(fluids) => RefpropFunctionLibrary::set_fluids(fluids)?,
+        }
+        self.loaded.store(fluids_generation(), Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<RefpropError>> = const { RefCell::new(None) };
+}
+
+/// Runs a C API call, recording its error and converting panics into `UnknownError`.
+fn status(call: impl FnOnce() -> Result<(), RefpropError>) -> RefpropStatus {
+    let result = panic::catch_unwind(AssertUnwindSafe(call)).unwrap_or_else(|_| {
+        Err(RefpropError::UnknownError(
+            "Panic in the REFPROP wrapper.".to_string(),
+        ))
+    });
+
+    let status = match &result {
+        Ok(()) => RefpropStatus::Ok,
+        Err(RefpropError::InitializationError(_)) => RefpropStatus::InitializationError,
+        Err(RefpropError::CalculationError(_)) => RefpropStatus::CalculationError,
+        Err(RefpropError::InvalidInput(_)) => RefpropStatus::InvalidInput,
+        Err(RefpropError::Utf8Error(_)) => RefpropStatus::Utf8Error,
+        Err(RefpropError::MutexPoisoned) => RefpropStatus::MutexPoisoned,
+        Err(RefpropError::UnknownError(_)) => RefpropStatus::UnknownError,
+    };
+    LAST_ERROR.with(|last| *last.borrow_mut() = result.err());
+    status
+}
+
+/// Converts a C string argument, rejecting null pointers and invalid UTF-8.
+unsafe fn c_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, RefpropError> {
+    if value.is_null() {
+        return Err(RefpropError::InvalidInput(format!("'{}' is null.", name)));
+    }
+    Ok(CStr::from_ptr(value).to_str()?)
+}
+
+/// Dereferences a handle argument, rejecting null pointers.
+unsafe fn fluid<'a>(fluid: *const RefpropFluid) -> Result<&'a RefpropFluid, RefpropError> {
+    fluid
+        .as_ref()
+        .ok_or_else(|| RefpropError::InvalidInput("'fluid' is null.".to_string()))
+}
+
+/// Dereferences an output argument, rejecting null pointers.
+unsafe fn output<'a, T>(out: *mut T) -> Result<&'a mut T, RefpropError> {
+    out.as_mut()
+        .ok_or_else(|| RefpropError::InvalidInput("'out' is null.".to_string()))
+}
+
+/// Sets the path of the REFPROP installation; a null `path` tries the RPPREFIX environment
+/// variable and then the fallback directories of `set_path_fallbacks`.
+///
+/// # Safety
+///
+/// `path` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_set_path(path: *const c_char) -> RefpropStatus {
+    status(|| {
+        let path = if path.is_null() {
+            None
+        } else {
+            Some(c_str(path, "path")?)
+        };
+        RefpropFunctionLibrary::set_path(path)
+    })
+}
+
+/// Creates a handle for a predefined mixture (e.g. "R454B") or a pure fluid (e.g. "WATER").
+///
+/// Returns null on failure; the error is available from `refprop_last_error`. The handle must be
+/// released with `refprop_fluid_free`.
+///
+/// # Safety
+///
+/// `name` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_fluid_new_mixture(name: *const c_char) -> *mut RefpropFluid {
+    let mut handle = ptr::null_mut();
+    status(|| {
+        let name = c_str(name, "name")?.to_string();
+        let _guard = RefpropFunctionLibrary::lock()?;
+        let z = RefpropFunctionLibrary::set_mixture_or_fluid(&name)?;
+        handle = Box::into_raw(Box::new(RefpropFluid {
+            source: FluidSource::Named(name),
+            z,
+            loaded: AtomicU64::new(fluids_generation()),
+        }));
+        Ok(())
+    });
+    handle
+}
+
+/// Creates a handle for a list of fluids separated by semicolons with the given mole fractions.
+///
+/// `ncomp` must equal the number of fluids in the list. Returns null on failure; the error is available from `refprop_last_error`. The handle must be
+/// released with `refprop_fluid_free`.
+///
+/// # Safety
+///
+/// `fluids` must be a valid NUL-terminated string and `z` must point to `ncomp` doubles.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_fluid_new(
+    fluids: *const c_char,
+    z: *const f64,
+    ncomp: usize,
+) -> *mut RefpropFluid {
+    let mut handle = ptr::null_mut();
+    status(|| {
+        let fluids = c_str(fluids, "fluids")?.to_string();
+        if z.is_null() || ncomp == 0 {
+            return Err(RefpropError::InvalidInput(
+                "'z' must hold at least one mole fraction.".to_string(),
+            ));
+        }
+        let z = std::slice::from_raw_parts(z, ncomp).to_vec();
+        validate_fractions(&z)?;
+
+        let _guard = RefpropFunctionLibrary::lock()?;
+        RefpropFunctionLibrary::set_fluids(&fluids)?;
+        let loaded = RefpropFunctionLibrary::loaded_fluids()?.ncomp();
+        if loaded != ncomp {
+            return Err(RefpropError::InvalidInput(format!(
+                "'z' holds {} mole fractions but {} components were loaded from '{}'.",
+                ncomp, loaded, fluids
+            )));
+        }
+        handle = Box::into_raw(Box::new(RefpropFluid {
+            source: FluidSource::Fluids(fluids),
+            z,
+            loaded: AtomicU64::new(fluids_generation()),
+        }));
+        Ok(())
+    });
+    handle
+}
+
+/// Releases a fluid handle. Null handles are ignored.
+///
+/// # Safety
+///
+/// `fluid` must be null or a handle returned by `refprop_fluid_new` or `refprop_fluid_new_mixture`
+/// that has not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_fluid_free(fluid: *mut RefpropFluid) {
+    if !fluid.is_null() {
+        drop(Box::from_raw(fluid));
+    }
+}
+
+/// Returns the number of components of a fluid, or 0 for a null handle.
+///
+/// # Safety
+///
+/// `fluid` must be null or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_fluid_component_count(fluid: *const RefpropFluid) -> usize {
+    fluid.as_ref().map_or(0, |fluid| fluid.z.len())
+}
+
+/// Copies the mole fractions of a fluid into `z`, which must hold `REFPROP_MAX_COMPONENTS` doubles.
+///
+/// # Safety
+///
+/// `fluid` must be a valid handle and `z` must point to `REFPROP_MAX_COMPONENTS` writable doubles.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_fluid_composition(
+    fluid: *const RefpropFluid,
+    z: *mut f64,
+) -> RefpropStatus {
+    status(|| {
+        let fluid = self::fluid(fluid)?;
+        if z.is_null() {
+            return Err(RefpropError::InvalidInput("'z' is null.".to_string()));
+        }
+        ptr::copy_nonoverlapping(fluid.z.as_ptr(), z, fluid.z.len());
+        Ok(())
+    })
+}
+
+/// Performs a flash calculation for a pair of independent properties and writes the state to `out`.
+///
+/// `pair` is one of the `RefpropInputPair` values; other values return `InvalidInput`.
+///
+/// # Safety
+///
+/// `fluid` must be a valid handle and `out` must point to a writable `RefpropState`.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_flash(
+    fluid: *const RefpropFluid,
+    pair: c_int,
+    a: f64,
+    b: f64,
+    out: *mut RefpropState,
+) -> RefpropStatus {
+    status(|| {
+        let fluid = self::fluid(fluid)?;
+        let pair = RefpropInputPair::try_from(pair)?;
+        let out = output(out)?;
+
+        let _guard = RefpropFunctionLibrary::lock()?;
+        fluid.activate()?;
+        let state = RefpropFunctionLibrary::flash(pair.into(), a, b, &fluid.z)?;
+        *out = RefpropState::from(&state);
+        Ok(())
+    })
+}
+
+/// Calculates the transport properties at temperature `T` [K] and density `D` [mol/L].
+///
+/// # Safety
+///
+/// `fluid` must be a valid handle and `out` must point to a writable `RefpropTransport`.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_transport(
+    fluid: *const RefpropFluid,
+    T: f64,
+    D: f64,
+    out: *mut RefpropTransport,
+) -> RefpropStatus {
+    status(|| {
+        let fluid = self::fluid(fluid)?;
+        let out = output(out)?;
+
+        let _guard = RefpropFunctionLibrary::lock()?;
+        fluid.activate()?;
+        let transport = RefpropFunctionLibrary::transport_properties(T, D, &fluid.z)?;
+        *out = RefpropTransport {
+            eta: transport.eta,
+            tcx: transport.tcx,
+        };
+        Ok(())
+    })
+}
+
+/// Calculates the critical point of a fluid.
+///
+/// # Safety
+///
+/// `fluid` must be a valid handle and `out` must point to a writable `RefpropCritical`.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_critical(
+    fluid: *const RefpropFluid,
+    out: *mut RefpropCritical,
+) -> RefpropStatus {
+    status(|| {
+        let fluid = self::fluid(fluid)?;
+        let out = output(out)?;
+
+        let _guard = RefpropFunctionLibrary::lock()?;
+        fluid.activate()?;
+        let critical = RefpropFunctionLibrary::critical_parameters(&fluid.z)?;
+        *out = RefpropCritical {
+            Tc: critical.Tc,
+            Pc: critical.Pc,
+            Dc: critical.Dc,
+        };
+        Ok(())
+    })
+}
+
+/// Copies the message of the last error on the calling thread into `buffer` as a NUL-terminated
+/// string, truncating it to `length - 1` bytes.
+///
+/// Returns the length of the full message in bytes (excluding the NUL terminator), or 0 if the last
+/// call succeeded. Passing a null `buffer` only queries the length.
+///
+/// # Safety
+///
+/// `buffer` must be null or point to `length` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn refprop_last_error(buffer: *mut c_char, length: usize) -> usize {
+    let message = LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map(|error| error.to_string())
+            .unwrap_or_default()
+    });
+
+    if !buffer.is_null() && length > 0 {
+        let n = message.len().min(length - 1);
+        ptr::copy_nonoverlapping(message.as_ptr() as *const c_char, buffer, n);
+        *buffer.add(n) = 0;
+    }
+    message.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capi_errors() {
+        let mut state = std::mem::MaybeUninit::<RefpropState>::uninit();
+        let code = unsafe {
+            refprop_flash(
+                ptr::null(),
+                RefpropInputPair::TP as c_int,
+                300.0,
+                101.325,
+                state.as_mut_ptr(),
+            )
+        };
+        assert_eq!(code, RefpropStatus::InvalidInput);
+
+        let mut buffer = [0 as c_char; 16];
+        let length = unsafe { refprop_last_error(buffer.as_mut_ptr(), buffer.len()) };
+        let message = unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_str().unwrap();
+        assert_eq!(length, "Invalid input: 'fluid' is null.".len());
+        assert_eq!(message, "Invalid input: ");
+
+        let z = [0.5, 0.6];
+        let handle = unsafe { refprop_fluid_new(c"R32;R125".as_ptr(), z.as_ptr(), z.len()) };
+        assert!(handle.is_null());
+        assert!(unsafe { refprop_last_error(ptr::null_mut(), 0) } > 0);
+
+        // A single mole fraction does not describe the two loaded fluids
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let z = [1.0];
+        let handle = unsafe { refprop_fluid_new(c"R32;R1234YF".as_ptr(), z.as_ptr(), z.len()) };
+        assert!(handle.is_null());
+        let mut buffer = [0 as c_char; 256];
+        unsafe { refprop_last_error(buffer.as_mut_ptr(), buffer.len()) };
+        let message = unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_str().unwrap();
+        assert!(message.contains("2 components were loaded"));
+    }
+
+    #[test]
+    fn test_capi_input_pair() {
+        assert_eq!(
+            RefpropInputPair::try_from(RefpropInputPair::PQ as c_int).unwrap(),
+            RefpropInputPair::PQ
+        );
+        assert!(RefpropInputPair::try_from(12).is_err());
+        assert!(RefpropInputPair::try_from(-1).is_err());
+
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let fluid = unsafe { refprop_fluid_new_mixture(c"WATER".as_ptr()) };
+        assert!(!fluid.is_null());
+        let mut state = std::mem::MaybeUninit::<RefpropState>::uninit();
+        let code = unsafe { refprop_flash(fluid, 42, 300.0, 101.325, state.as_mut_ptr()) };
+        let ok = unsafe {
+            refprop_flash(
+                fluid,
+                RefpropInputPair::TP as c_int,
+                300.0,
+                101.325,
+                state.as_mut_ptr(),
+            )
+        };
+        unsafe { refprop_fluid_free(fluid) };
+        assert_eq!(code, RefpropStatus::InvalidInput);
+        assert_eq!(ok, RefpropStatus::Ok);
+    }
+
+    #[test]
+    fn test_capi_header_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/refprop_sys.h"));
+        let committed = include_str!("../include/refprop_sys.h");
+        assert!(
+            generated == committed,
+            "include/refprop_sys.h is outdated; copy it from {}.",
+            concat!(env!("OUT_DIR"), "/refprop_sys.h")
+        );
+    }
+}
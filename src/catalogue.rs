@@ -0,0 +1,528 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{setup::set_path::active_path, RefpropError};
+
+/// Kind of a pure-fluid file in the `FLUIDS` directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FluidFileKind {
+    /// Fluid file with a reference equation of state (`.FLD`).
+    Fluid,
+    /// Pseudo-pure fluid file for a predefined mixture such as air (`.PPF`).
+    PseudoPure,
+}
+
+/// Header of a fluid file, as listed by `FluidCatalogue`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FluidEntry {
+    /// Name of the file without its extension, as accepted by `set_fluids` (e.g., "R32")
+    pub file_name: String,
+    /// Kind of the file
+    pub kind: FluidFileKind,
+    /// Short name (e.g., "R32")
+    pub short_name: String,
+    /// Full chemical name (e.g., "difluoromethane")
+    pub full_name: String,
+    /// CAS registry number (e.g., "75-10-5")
+    pub cas: Option<String>,
+    /// Chemical formula (e.g., "CH2F2")
+    pub formula: Option<String>,
+    /// Synonyms (e.g., "HFC-32")
+    pub synonyms: Vec<String>,
+    /// Molar mass [g/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "molar_mass_g_per_mol"))]
+    pub molar_mass: Option<f64>,
+    /// Location of the file
+    pub path: PathBuf,
+}
+
+/// Predefined mixture of a `.MIX` file, as listed by `FluidCatalogue`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MixtureEntry {
+    /// Name of the file without its extension, as accepted by `set_mixture` (e.g., "R454B")
+    pub file_name: String,
+    /// First line of the file, usually the name and nominal composition of the mixture
+    pub description: String,
+    /// Molar mass [g/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "molar_mass_g_per_mol"))]
+    pub molar_mass: f64,
+    /// Fluid files of the components without their extension (e.g., ["R32", "R1234YF"])
+    pub components: Vec<String>,
+    /// Mole fractions of the components
+    pub composition: Vec<f64>,
+    /// Location of the file
+    pub path: PathBuf,
+}
+
+/// Entry of the catalogue matching a search, returned by `FluidCatalogue::search`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CatalogueMatch<'a> {
+    /// A fluid or pseudo-pure fluid file.
+    Fluid(&'a FluidEntry),
+    /// A mixture file.
+    Mixture(&'a MixtureEntry),
+}
+
+/// Fluid and mixture files available in a REFPROP directory.
+///
+/// The catalogue is built by reading the headers of the `.FLD` and `.PPF` files in the `FLUIDS`
+/// subdirectory and of the `.MIX` files in the `MIXTURES` subdirectory, without calling REFPROP.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FluidCatalogue {
+    fluids: Vec<FluidEntry>,
+    mixtures: Vec<MixtureEntry>,
+}
+
+/// Returns the subdirectory of `root` with the given name, ignoring case.
+pub(crate) fn subdirectory(root: &Path, name: &str) -> Option<PathBuf> {
+    let exact = root.join(name);
+    if exact.is_dir() {
+        return Some(exact);
+    }
+    fs::read_dir(root)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .is_some_and(|file_name| file_name.eq_ignore_ascii_case(name))
+        })
+}
+
+/// Lists the files of `directory` with one of the given extensions (ignoring case), sorted by name.
+fn files_with_extension(
+    directory: &Path,
+    extensions: &[&str],
+) -> Result<Vec<PathBuf>, RefpropError> {
+    let entries = fs::read_dir(directory).map_err(|e| {
+        RefpropError::InvalidInput(format!("Cannot read {}: {}", directory.display(), e))
+    })?;
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path.extension().is_some_and(|extension| {
+                    extensions
+                        .iter()
+                        .any(|candidate| extension.eq_ignore_ascii_case(candidate))
+                })
+        })
+        .collect();
+    files.sort();
+
+    Ok(files)
+}
+
+/// Reads a text file, replacing invalid UTF-8 (some fluid files contain Latin-1 characters).
+pub(crate) fn read_text(path: &Path) -> Result<String, RefpropError> {
+    let bytes = fs::read(path).map_err(|e| {
+        RefpropError::InvalidInput(format!("Cannot read {}: {}", path.display(), e))
+    })?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Returns the file name of `path` without its extension.
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Normalises a name for comparison: lowercase, without spaces, hyphens, and underscores.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+impl FluidEntry {
+    /// Parses the header of a fluid file.
+    ///
+    /// The header consists of the lines before the first line starting with `#` (the start of the
+    /// equation of state), each holding a value followed by a `!` and its description, e.g.
+    /// `75-10-5     !CAS number`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the header has no short name.
+    pub fn parse(
+        text: &str,
+        file_name: &str,
+        kind: FluidFileKind,
+        path: PathBuf,
+    ) -> Result<Self, RefpropError> {
+        let mut entry = FluidEntry {
+            file_name: file_name.to_string(),
+            kind,
+            short_name: String::new(),
+            full_name: String::new(),
+            cas: None,
+            formula: None,
+            synonyms: Vec::new(),
+            molar_mass: None,
+            path,
+        };
+
+        for line in text.lines() {
+            if line.starts_with('#') {
+                break;
+            }
+            let Some((value, description)) = line.split_once('!') else {
+                continue;
+            };
+            let value = value.trim();
+            let description = description.to_lowercase();
+            if value.is_empty() {
+                continue;
+            }
+
+            if description.contains("short name") {
+                entry.short_name = value.to_string();
+            } else if description.contains("cas number") {
+                entry.cas = Some(value.to_string());
+            } else if description.contains("full name") {
+                entry.full_name = value.to_string();
+            } else if description.contains("chemical formula") {
+                entry.formula = Some(value.to_string());
+            } else if description.contains("synonym") {
+                entry.synonyms.extend(
+                    value
+                        .split([',', ';'])
+                        .map(str::trim)
+                        .filter(|synonym| !synonym.is_empty())
+                        .map(str::to_string),
+                );
+            } else if description.contains("molar mass") || description.contains("molecular weight")
+            {
+                entry.molar_mass = value
+                    .split_whitespace()
+                    .next()
+                    .and_then(|number| number.parse().ok());
+            }
+        }
+
+        if entry.short_name.is_empty() {
+            return Err(RefpropError::InvalidInput(format!(
+                "Fluid file '{}' has no short name in its header",
+                file_name
+            )));
+        }
+
+        Ok(entry)
+    }
+
+    /// Returns whether `query` is the file name, short name, full name, a synonym, or the CAS number of the fluid.
+    ///
+    /// Names are compared ignoring case, spaces, hyphens, and underscores, so "r-32" matches "R32".
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim();
+        if self.cas.as_deref() == Some(query) {
+            return true;
+        }
+        let query = normalize(query);
+        self.names().any(|name| normalize(name) == query)
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> {
+        [
+            self.file_name.as_str(),
+            self.short_name.as_str(),
+            self.full_name.as_str(),
+        ]
+        .into_iter()
+        .chain(self.synonyms.iter().map(String::as_str))
+    }
+}
+
+impl MixtureEntry {
+    /// Parses a mixture file.
+    ///
+    /// A mixture file holds a description line, a line starting with the molar mass (followed by
+    /// the estimated critical temperature, pressure, and density), the number of components, one
+    /// fluid file per line, and the mole fractions of the components.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the file does not follow this layout.
+    pub fn parse(text: &str, file_name: &str, path: PathBuf) -> Result<Self, RefpropError> {
+        let invalid = |reason: &str| {
+            RefpropError::InvalidInput(format!("Invalid mixture file '{}': {}", file_name, reason))
+        };
+
+        let mut lines = text.lines();
+        let description = lines
+            .next()
+            .ok_or_else(|| invalid("file is empty"))?
+            .trim()
+            .to_string();
+        let molar_mass = lines
+            .next()
+            .and_then(|line| line.split_whitespace().next())
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| invalid("missing molar mass"))?;
+        let ncomp: usize = lines
+            .next()
+            .and_then(|line| line.trim().parse().ok())
+            .filter(|&ncomp| (1..=20).contains(&ncomp))
+            .ok_or_else(|| invalid("missing or invalid number of components"))?;
+
+        let mut components = Vec::with_capacity(ncomp);
+        for _ in 0..ncomp {
+            let line = lines
+                .next()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .ok_or_else(|| invalid("missing component"))?;
+            components.push(file_stem(Path::new(line)));
+        }
+
+        let mut composition = Vec::with_capacity(ncomp);
+        for line in lines.by_ref() {
+            for number in line.split_whitespace() {
+                composition.push(
+                    number
+                        .parse::<f64>()
+                        .map_err(|_| invalid("invalid mole fraction"))?,
+                );
+            }
+            if composition.len() >= ncomp {
+                break;
+            }
+        }
+        if composition.len() != ncomp {
+            return Err(invalid(
+                "number of mole fractions does not match the components",
+            ));
+        }
+
+        Ok(MixtureEntry {
+            file_name: file_name.to_string(),
+            description,
+            molar_mass,
+            components,
+            composition,
+            path,
+        })
+    }
+
+    /// Returns whether `query` is the file name of the mixture or the first word of its description.
+    ///
+    /// Names are compared ignoring case, spaces, hyphens, and underscores.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = normalize(query);
+        normalize(&self.file_name) == query
+            || self
+                .description
+                .split([' ', ';', '[', '('])
+                .next()
+                .is_some_and(|name| normalize(name) == query)
+    }
+}
+
+impl FluidCatalogue {
+    /// Builds the catalogue of a REFPROP directory containing `FLUIDS` and `MIXTURES` subdirectories.
+    ///
+    /// Files that cannot be read or whose header cannot be parsed are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// - `root`: The REFPROP directory, as passed to `set_path`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if `root` has no `FLUIDS` subdirectory or a directory cannot be read.
+    pub fn scan(root: impl AsRef<Path>) -> Result<Self, RefpropError> {
+        let root = root.as_ref();
+        let fluids_directory = subdirectory(root, "FLUIDS").ok_or_else(|| {
+            RefpropError::InvalidInput(format!("No FLUIDS directory in {}", root.display()))
+        })?;
+
+        let mut catalogue = FluidCatalogue::default();
+        for path in files_with_extension(&fluids_directory, &["FLD", "PPF"])? {
+            let kind = match path.extension() {
+                Some(extension) if extension.eq_ignore_ascii_case("PPF") => {
+                    FluidFileKind::PseudoPure
+                }
+                _ => FluidFileKind::Fluid,
+            };
+            let Ok(text) = read_text(&path) else {
+                continue;
+            };
+            if let Ok(entry) = FluidEntry::parse(&text, &file_stem(&path), kind, path.clone()) {
+                catalogue.fluids.push(entry);
+            }
+        }
+
+        if let Some(mixtures_directory) = subdirectory(root, "MIXTURES") {
+            for path in files_with_extension(&mixtures_directory, &["MIX"])? {
+                let Ok(text) = read_text(&path) else {
+                    continue;
+                };
+                if let Ok(entry) = MixtureEntry::parse(&text, &file_stem(&path), path.clone()) {
+                    catalogue.mixtures.push(entry);
+                }
+            }
+        }
+
+        Ok(catalogue)
+    }
+
+    /// Builds the catalogue of the directory last set with `RefpropFunctionLibrary::set_path`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InitializationError` if `set_path` has not been called.
+    /// - Returns the errors of `scan`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{FluidCatalogue, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let catalogue = FluidCatalogue::from_active_path()?;
+    ///
+    ///     if let Some(fluid) = catalogue.find_fluid("HFC-32") {
+    ///         RefpropFunctionLibrary::set_fluids(&fluid.file_name)?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_active_path() -> Result<Self, RefpropError> {
+        let root = active_path().ok_or_else(|| {
+            RefpropError::InitializationError(
+                "No REFPROP path has been set with set_path".to_string(),
+            )
+        })?;
+        Self::scan(root)
+    }
+
+    /// Returns the fluid files, sorted by file name.
+    pub fn fluids(&self) -> &[FluidEntry] {
+        &self.fluids
+    }
+
+    /// Returns the mixture files, sorted by file name.
+    pub fn mixtures(&self) -> &[MixtureEntry] {
+        &self.mixtures
+    }
+
+    /// Returns the fluid whose file name, short name, full name, synonym, or CAS number is `query`.
+    pub fn find_fluid(&self, query: &str) -> Option<&FluidEntry> {
+        self.fluids.iter().find(|fluid| fluid.matches(query))
+    }
+
+    /// Returns the mixture whose file name is `query`.
+    pub fn find_mixture(&self, query: &str) -> Option<&MixtureEntry> {
+        self.mixtures.iter().find(|mixture| mixture.matches(query))
+    }
+
+    /// Returns the fluids and mixtures containing `query` in any of their names, synonyms, or CAS number.
+    ///
+    /// Names are compared ignoring case, spaces, hyphens, and underscores; exact matches come first.
+    pub fn search(&self, query: &str) -> Vec<CatalogueMatch<'_>> {
+        let normalized = normalize(query);
+        if normalized.is_empty() {
+            return Vec::new();
+        }
+
+        let mut exact = Vec::new();
+        let mut partial = Vec::new();
+        for fluid in &self.fluids {
+            if fluid.matches(query) {
+                exact.push(CatalogueMatch::Fluid(fluid));
+            } else if fluid
+                .names()
+                .chain(fluid.cas.as_deref())
+                .any(|name| normalize(name).contains(&normalized))
+            {
+                partial.push(CatalogueMatch::Fluid(fluid));
+            }
+        }
+        for mixture in &self.mixtures {
+            if mixture.matches(query) {
+                exact.push(CatalogueMatch::Mixture(mixture));
+            } else if normalize(&mixture.file_name).contains(&normalized)
+                || normalize(&mixture.description).contains(&normalized)
+            {
+                partial.push(CatalogueMatch::Mixture(mixture));
+            }
+        }
+
+        exact.extend(partial);
+        exact
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const R32_FLD: &str = "\
+R32                                   !Short name
+75-10-5                               !CAS number
+Difluoromethane                       !Full name
+CH2F2                                 !Chemical formula {CH2F2}
+HFC-32                                !Synonym
+52.024                                !Molar mass [g/mol]
+136.34                                !Triple point temperature [K]
+
+#EOS               !---Equation of state---
+FEQ                !Helmholtz equation of state for R-32
+";
+
+    const R454B_MIX: &str = "\
+R454B  [R32/1234yf (68.9/31.1)]
+ 62.6144  352.95  5271.3  6.885
+2
+R32.FLD
+R1234YF.FLD
+0.829524   0.170476
+0
+";
+
+    #[test]
+    fn test_catalogue() -> Result<(), RefpropError> {
+        let root = std::env::temp_dir().join(format!("refprop-catalogue-{}", std::process::id()));
+        fs::create_dir_all(root.join("FLUIDS")).unwrap();
+        fs::create_dir_all(root.join("MIXTURES")).unwrap();
+        fs::write(root.join("FLUIDS").join("R32.FLD"), R32_FLD).unwrap();
+        fs::write(root.join("FLUIDS").join("README.TXT"), "not a fluid").unwrap();
+        fs::write(root.join("MIXTURES").join("R454B.MIX"), R454B_MIX).unwrap();
+
+        let catalogue = FluidCatalogue::scan(&root);
+        fs::remove_dir_all(&root).unwrap();
+        let catalogue = catalogue?;
+
+        assert_eq!(catalogue.fluids().len(), 1);
+        let r32 = catalogue.find_fluid("75-10-5").unwrap();
+        assert_eq!(r32.full_name, "Difluoromethane");
+        assert_eq!(r32.synonyms, vec!["HFC-32".to_string()]);
+        assert_eq!(r32.molar_mass, Some(52.024));
+        assert!(catalogue.find_fluid("hfc32").is_some());
+        assert!(catalogue.find_fluid("R-3").is_none());
+
+        let r454b = catalogue.find_mixture("r454b").unwrap();
+        assert_eq!(r454b.components, vec!["R32", "R1234YF"]);
+        assert_eq!(r454b.composition, vec![0.829524, 0.170476]);
+
+        // R32 matches exactly, R454B because its description contains "R32"
+        let matches = catalogue.search("r32");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0], CatalogueMatch::Fluid(r32));
+
+        Ok(())
+    }
+}
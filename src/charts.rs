@@ -0,0 +1,401 @@
+mod svg;
+
+use crate::{
+    flash_routines::FlashOutput, utils::validate_composition, Basis, KrKqFlag, Phase, RefpropError,
+    RefpropFunctionLibrary,
+};
+
+/// Represents the kind of diagram a chart is projected onto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChartKind {
+    /// Pressure [kPa] versus enthalpy [J/mol], both on linear axes.
+    PressureEnthalpy,
+    /// Pressure [kPa] on a logarithmic axis versus enthalpy [J/mol].
+    LogPressureEnthalpy,
+    /// Temperature [K] versus entropy [J/mol-K].
+    TemperatureEntropy,
+}
+
+impl ChartKind {
+    /// Projects a state point onto the (x, y) coordinates of the diagram.
+    pub fn coordinates(&self, point: &StatePoint) -> (f64, f64) {
+        match self {
+            ChartKind::PressureEnthalpy | ChartKind::LogPressureEnthalpy => (point.h, point.P),
+            ChartKind::TemperatureEntropy => (point.s, point.T),
+        }
+    }
+
+    /// Returns the axis labels (x, y) of the diagram.
+    pub fn axis_labels(&self) -> (&'static str, &'static str) {
+        match self {
+            ChartKind::PressureEnthalpy | ChartKind::LogPressureEnthalpy => {
+                ("h [J/mol]", "P [kPa]")
+            }
+            ChartKind::TemperatureEntropy => ("s [J/mol-K]", "T [K]"),
+        }
+    }
+}
+
+/// Represents the kind of line drawn on a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineKind {
+    /// Saturated liquid (bubble point) line.
+    BubbleLine,
+    /// Saturated vapor (dew point) line.
+    DewLine,
+    /// Line of constant temperature [K].
+    Isotherm,
+    /// Line of constant entropy [J/mol-K].
+    Isentrope,
+    /// Line of constant density [mol/L].
+    Isochore,
+    /// Line of constant molar vapor quality [mol/mol].
+    Quality,
+}
+
+/// Represents a thermodynamic state on a chart line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatePoint {
+    /// Temperature [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_K"))]
+    pub T: f64,
+    /// Pressure [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "P_kPa"))]
+    pub P: f64,
+    /// Density [mol/L]
+    #[cfg_attr(feature = "serde", serde(rename = "D_mol_per_L"))]
+    pub D: f64,
+    /// Enthalpy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "h_J_per_mol"))]
+    pub h: f64,
+    /// Entropy [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "s_J_per_mol_K"))]
+    pub s: f64,
+}
+
+impl From<&FlashOutput> for StatePoint {
+    fn from(output: &FlashOutput) -> Self {
+        StatePoint {
+            T: output.T,
+            P: output.P,
+            D: output.D,
+            h: output.h,
+            s: output.s,
+        }
+    }
+}
+
+/// Represents a polyline of constant property on a chart.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChartLine {
+    /// Kind of the line
+    pub kind: LineKind,
+    /// Value of the constant property; `NaN` for the saturation lines
+    pub value: f64,
+    /// States along the line; points where REFPROP failed to converge are omitted
+    pub points: Vec<StatePoint>,
+}
+
+impl ChartLine {
+    /// Projects the points of the line onto the (x, y) coordinates of a diagram.
+    pub fn coordinates(&self, kind: ChartKind) -> Vec<(f64, f64)> {
+        self.points.iter().map(|p| kind.coordinates(p)).collect()
+    }
+}
+
+/// Specifies the ranges and lines of a property chart.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChartSpec {
+    /// Minimum temperature of the saturation dome, quality lines, and isochores [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_min_K"))]
+    pub T_min: f64,
+    /// Maximum temperature of the isochores [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_max_K"))]
+    pub T_max: f64,
+    /// Minimum pressure of the isotherms and isentropes [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "P_min_kPa"))]
+    pub P_min: f64,
+    /// Maximum pressure of the isotherms and isentropes [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "P_max_kPa"))]
+    pub P_max: f64,
+    /// Temperatures of the isotherms [K]
+    #[cfg_attr(feature = "serde", serde(rename = "isotherms_K"))]
+    pub isotherms: Vec<f64>,
+    /// Entropies of the isentropes [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "isentropes_J_per_mol_K"))]
+    pub isentropes: Vec<f64>,
+    /// Densities of the isochores [mol/L]
+    #[cfg_attr(feature = "serde", serde(rename = "isochores_mol_per_L"))]
+    pub isochores: Vec<f64>,
+    /// Molar vapor qualities of the constant-quality lines [mol/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "qualities_mol_per_mol"))]
+    pub qualities: Vec<f64>,
+    /// Number of points evaluated along each line
+    pub points_per_line: usize,
+}
+
+impl ChartSpec {
+    /// Creates a chart specification with the given ranges, no property lines, and 60 points per line.
+    pub fn new(T_min: f64, T_max: f64, P_min: f64, P_max: f64) -> Self {
+        ChartSpec {
+            T_min,
+            T_max,
+            P_min,
+            P_max,
+            isotherms: Vec::new(),
+            isentropes: Vec::new(),
+            isochores: Vec::new(),
+            qualities: Vec::new(),
+            points_per_line: 60,
+        }
+    }
+}
+
+/// Represents the lines of a property chart computed by the `property_chart` method.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyChart {
+    /// Critical point of the fluid, if it could be calculated
+    pub critical_point: Option<StatePoint>,
+    /// Bubble and dew lines of the saturation dome
+    pub dome: Vec<ChartLine>,
+    /// Isotherms, isentropes, isochores, and constant-quality lines
+    pub lines: Vec<ChartLine>,
+}
+
+impl PropertyChart {
+    /// Renders the chart as a standalone SVG document.
+    ///
+    /// # Parameters
+    ///
+    /// - `kind`: The diagram to project the chart lines onto.
+    /// - `overlay`: State points (e.g., the states of a cycle) drawn as markers joined in order.
+    /// - `width`: Width of the SVG image [px].
+    /// - `height`: Height of the SVG image [px].
+    ///
+    /// # Returns
+    ///
+    /// - `String`: The SVG document.
+    pub fn to_svg(
+        &self,
+        kind: ChartKind,
+        overlay: &[StatePoint],
+        width: f64,
+        height: f64,
+    ) -> String {
+        svg::render(self, kind, overlay, width, height)
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Computes the saturation dome and lines of constant property for the loaded fluid.
+    ///
+    /// The saturation dome and the constant-quality lines are calculated with `tq_flash` from `T_min`
+    /// up to the critical temperature (or `T_max`, if lower), the isotherms with `tp_flash` and the isentropes with `ps_flash`
+    /// on logarithmically spaced pressures between `P_min` and `P_max`, and the isochores with
+    /// `td_flash` between `T_min` and `T_max`. All properties are on a molar basis.
+    ///
+    /// **Note:** Points where REFPROP fails to converge (typically close to the critical point or
+    /// outside the range of validity of the equation of state) are omitted from the lines. The dome
+    /// is closed at the critical point only for pure fluids whose dome lies within `T_max`, and it is
+    /// empty if `T_min` lies above the critical temperature.
+    ///
+    /// # Parameters
+    ///
+    /// - `spec`: A `ChartSpec` struct specifying the ranges and lines of the chart.
+    /// - `z`: A slice containing the overall composition (mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `PropertyChart`: A struct containing the polylines of the chart.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if:
+    ///     - `z` contains more than 20 elements.
+    ///     - The sum of mole fractions in `z` does not equal 1 within a specified tolerance.
+    ///     - The ranges are empty, the pressures are not positive, or fewer than 2 points per line are requested.
+    /// - Returns `RefpropError::CalculationError` if the critical parameters cannot be calculated.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{ChartKind, ChartSpec, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let mut spec = ChartSpec::new(230.0, 400.0, 50.0, 6000.0);
+    ///     spec.isotherms = vec![260.0, 300.0, 340.0, 380.0];
+    ///     spec.qualities = vec![0.25, 0.5, 0.75];
+    ///
+    ///     let chart = RefpropFunctionLibrary::property_chart(&spec, &z)?;
+    ///     let svg = chart.to_svg(ChartKind::LogPressureEnthalpy, &[], 800.0, 600.0);
+    ///     println!("{}", svg);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn property_chart(spec: &ChartSpec, z: &[f64]) -> Result<PropertyChart, RefpropError> {
+        // Validate composition slice
+        validate_composition(z)?;
+
+        // Validate ranges
+        if spec.points_per_line < 2 {
+            return Err(RefpropError::InvalidInput(
+                "At least 2 points per line are required.".to_string(),
+            ));
+        }
+        if spec.T_min >= spec.T_max || spec.P_min >= spec.P_max || spec.P_min <= 0.0 {
+            return Err(RefpropError::InvalidInput(format!(
+                "Invalid chart ranges: T in [{}, {}] K, P in [{}, {}] kPa.",
+                spec.T_min, spec.T_max, spec.P_min, spec.P_max
+            )));
+        }
+
+        let n = spec.points_per_line;
+        let critical = Self::critical_parameters(z)?;
+        let critical_point = Self::td_flash(critical.Tc, critical.Dc, z)
+            .ok()
+            .map(|output| StatePoint::from(&output));
+
+        let (saturation_temperatures, reaches_critical) =
+            saturation_temperatures(spec.T_min, spec.T_max, critical.Tc, n);
+        // The bubble and dew lines of a mixture do not meet at the point returned by CRITPdll
+        let is_pure = z.iter().filter(|&&zi| zi > 0.0).count() == 1;
+        let closing_point = critical_point.filter(|_| reaches_critical && is_pure);
+        let pressures = logspace(spec.P_min, spec.P_max, n);
+        let temperatures = linspace(spec.T_min, spec.T_max, n);
+
+        let quality_line = |kind: LineKind, q: f64| {
+            let mut line = trace(kind, q, &saturation_temperatures, |T| {
+                Self::tq_flash(
+                    T,
+                    q,
+                    z,
+                    Basis::Molar,
+                    Phase::Unknown,
+                    KrKqFlag::QualityMolar,
+                )
+            });
+            // Close the line at the critical point
+            if let Some(point) = closing_point {
+                line.points.push(point);
+            }
+            line
+        };
+
+        let dome = vec![
+            ChartLine {
+                value: f64::NAN,
+                ..quality_line(LineKind::BubbleLine, 0.0)
+            },
+            ChartLine {
+                value: f64::NAN,
+                ..quality_line(LineKind::DewLine, 1.0)
+            },
+        ];
+
+        let mut lines = Vec::new();
+        for &T in &spec.isotherms {
+            lines.push(trace(LineKind::Isotherm, T, &pressures, |P| {
+                Self::tp_flash(T, P, z)
+            }));
+        }
+        for &s in &spec.isentropes {
+            lines.push(trace(LineKind::Isentrope, s, &pressures, |P| {
+                Self::ps_flash(P, s, z)
+            }));
+        }
+        for &D in &spec.isochores {
+            lines.push(trace(LineKind::Isochore, D, &temperatures, |T| {
+                Self::td_flash(T, D, z)
+            }));
+        }
+        for &q in &spec.qualities {
+            lines.push(quality_line(LineKind::Quality, q));
+        }
+
+        Ok(PropertyChart {
+            critical_point,
+            dome,
+            lines,
+        })
+    }
+}
+
+/// Returns the temperatures of the saturation and constant-quality lines, and whether they reach the
+/// critical temperature.
+///
+/// The lines stop just below the critical temperature, where the flash routines converge poorly, or at
+/// `T_max` if it is lower. No temperatures are returned if `T_min` lies above that limit.
+fn saturation_temperatures(T_min: f64, T_max: f64, Tc: f64, n: usize) -> (Vec<f64>, bool) {
+    let T_limit = Tc * 0.999;
+    let T_sat_max = T_max.min(T_limit);
+    if T_min >= T_sat_max {
+        return (Vec::new(), false);
+    }
+
+    (linspace(T_min, T_sat_max, n), T_max >= T_limit)
+}
+
+/// Evaluates a flash routine along a sequence of values, dropping the points that fail.
+fn trace<F>(kind: LineKind, value: f64, along: &[f64], mut flash: F) -> ChartLine
+where
+    F: FnMut(f64) -> Result<FlashOutput, RefpropError>,
+{
+    let points = along
+        .iter()
+        .filter_map(|&x| flash(x).ok())
+        .map(|output| StatePoint::from(&output))
+        .collect();
+
+    ChartLine {
+        kind,
+        value,
+        points,
+    }
+}
+
+/// Returns `n` evenly spaced values between `start` and `end` (inclusive).
+fn linspace(start: f64, end: f64, n: usize) -> Vec<f64> {
+    let step = (end - start) / (n - 1) as f64;
+    (0..n).map(|i| start + step * i as f64).collect()
+}
+
+/// Returns `n` logarithmically spaced values between `start` and `end` (inclusive).
+fn logspace(start: f64, end: f64, n: usize) -> Vec<f64> {
+    linspace(start.ln(), end.ln(), n)
+        .into_iter()
+        .map(f64::exp)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saturation_temperatures() {
+        let (temperatures, reaches_critical) = saturation_temperatures(250.0, 400.0, 350.0, 5);
+        assert_eq!(temperatures.len(), 5);
+        assert_eq!(temperatures[4], 350.0 * 0.999);
+        assert!(reaches_critical);
+
+        // The dome is cut at T_max without closing it at the critical point
+        let (temperatures, reaches_critical) = saturation_temperatures(250.0, 300.0, 350.0, 5);
+        assert_eq!(temperatures[4], 300.0);
+        assert!(!reaches_critical);
+
+        // A supercritical range has no saturation lines
+        let (temperatures, reaches_critical) = saturation_temperatures(360.0, 400.0, 350.0, 5);
+        assert!(temperatures.is_empty());
+        assert!(!reaches_critical);
+    }
+}
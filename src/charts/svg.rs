@@ -0,0 +1,375 @@
+use std::fmt::Write;
+
+use super::{ChartKind, ChartLine, LineKind, PropertyChart, StatePoint};
+
+// Margins around the plotting area [px]
+const MARGIN_LEFT: f64 = 80.0;
+const MARGIN_RIGHT: f64 = 30.0;
+const MARGIN_TOP: f64 = 20.0;
+const MARGIN_BOTTOM: f64 = 60.0;
+
+/// Maps data coordinates onto the pixel coordinates of the plotting area.
+struct Frame {
+    kind: ChartKind,
+    x_min: f64,
+    x_max: f64,
+    y_min: f64,
+    y_max: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Frame {
+    /// Transforms the y coordinate onto the axis scale (logarithmic for `LogPressureEnthalpy`).
+    fn scale_y(&self, y: f64) -> f64 {
+        match self.kind {
+            ChartKind::LogPressureEnthalpy => y.log10(),
+            _ => y,
+        }
+    }
+
+    fn px(&self, x: f64) -> f64 {
+        let plot_width = self.width - MARGIN_LEFT - MARGIN_RIGHT;
+        MARGIN_LEFT + (x - self.x_min) / (self.x_max - self.x_min) * plot_width
+    }
+
+    fn py(&self, y: f64) -> f64 {
+        let plot_height = self.height - MARGIN_TOP - MARGIN_BOTTOM;
+        let y = self.scale_y(y);
+        MARGIN_TOP + (self.y_max - y) / (self.y_max - self.y_min) * plot_height
+    }
+
+    fn contains(&self, (x, y): (f64, f64)) -> bool {
+        x.is_finite() && y.is_finite() && (self.kind != ChartKind::LogPressureEnthalpy || y > 0.0)
+    }
+}
+
+/// Renders a property chart as a standalone SVG document.
+pub(super) fn render(
+    chart: &PropertyChart,
+    kind: ChartKind,
+    overlay: &[StatePoint],
+    width: f64,
+    height: f64,
+) -> String {
+    let frame = frame(chart, kind, overlay, width, height);
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="12">"#,
+        w = width,
+        h = height
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect x="0" y="0" width="{}" height="{}" fill="white"/>"#,
+        width, height
+    );
+
+    draw_axes(&mut svg, &frame);
+
+    for line in &chart.lines {
+        draw_line(&mut svg, &frame, line);
+    }
+    for line in &chart.dome {
+        draw_line(&mut svg, &frame, line);
+    }
+
+    // Overlay the state points, joined in the order they were given
+    let overlay_points = overlay
+        .iter()
+        .map(|p| kind.coordinates(p))
+        .filter(|&xy| frame.contains(xy))
+        .collect::<Vec<(f64, f64)>>();
+    if overlay_points.len() > 1 {
+        let _ = writeln!(
+            svg,
+            r##"<polyline fill="none" stroke="#ff7f0e" stroke-width="2" points="{}"/>"##,
+            points_attribute(&frame, &overlay_points)
+        );
+    }
+    for (i, &(x, y)) in overlay_points.iter().enumerate() {
+        let _ = writeln!(
+            svg,
+            r##"<circle cx="{:.2}" cy="{:.2}" r="4" fill="#ff7f0e"/><text x="{:.2}" y="{:.2}" fill="#ff7f0e">{}</text>"##,
+            frame.px(x),
+            frame.py(y),
+            frame.px(x) + 6.0,
+            frame.py(y) - 6.0,
+            i + 1
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Determines the data bounds of the chart from all drawn points.
+fn frame(
+    chart: &PropertyChart,
+    kind: ChartKind,
+    overlay: &[StatePoint],
+    width: f64,
+    height: f64,
+) -> Frame {
+    let mut frame = Frame {
+        kind,
+        x_min: f64::INFINITY,
+        x_max: f64::NEG_INFINITY,
+        y_min: f64::INFINITY,
+        y_max: f64::NEG_INFINITY,
+        width,
+        height,
+    };
+
+    let points = chart
+        .dome
+        .iter()
+        .chain(chart.lines.iter())
+        .flat_map(|line| line.points.iter())
+        .chain(overlay.iter());
+    for point in points {
+        let (x, y) = kind.coordinates(point);
+        if !frame.contains((x, y)) {
+            continue;
+        }
+        let y = frame.scale_y(y);
+        frame.x_min = frame.x_min.min(x);
+        frame.x_max = frame.x_max.max(x);
+        frame.y_min = frame.y_min.min(y);
+        frame.y_max = frame.y_max.max(y);
+    }
+
+    // Fall back to a unit frame when there is nothing to draw
+    if !frame.x_min.is_finite() || frame.x_max <= frame.x_min {
+        frame.x_min = 0.0;
+        frame.x_max = 1.0;
+    }
+    if !frame.y_min.is_finite() || frame.y_max <= frame.y_min {
+        frame.y_min = 0.0;
+        frame.y_max = 1.0;
+    }
+
+    frame
+}
+
+/// Draws the plotting area, tick marks, grid lines, and axis labels.
+fn draw_axes(svg: &mut String, frame: &Frame) {
+    let (x_label, y_label) = frame.kind.axis_labels();
+    let bottom = frame.height - MARGIN_BOTTOM;
+    let right = frame.width - MARGIN_RIGHT;
+
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="black"/>"#,
+        MARGIN_LEFT,
+        MARGIN_TOP,
+        right - MARGIN_LEFT,
+        bottom - MARGIN_TOP
+    );
+
+    for x in linear_ticks(frame.x_min, frame.x_max) {
+        let px = frame.px(x);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{px:.2}" y1="{top}" x2="{px:.2}" y2="{bottom}" stroke="#e0e0e0"/><text x="{px:.2}" y="{label}" text-anchor="middle">{value}</text>"##,
+            px = px,
+            top = MARGIN_TOP,
+            bottom = bottom,
+            label = bottom + 16.0,
+            value = format_tick(x)
+        );
+    }
+
+    let y_ticks = match frame.kind {
+        ChartKind::LogPressureEnthalpy => log_ticks(frame.y_min, frame.y_max),
+        _ => linear_ticks(frame.y_min, frame.y_max),
+    };
+    for y in y_ticks {
+        let py = frame.py(y);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{left}" y1="{py:.2}" x2="{right}" y2="{py:.2}" stroke="#e0e0e0"/><text x="{label}" y="{text_y:.2}" text-anchor="end">{value}</text>"##,
+            left = MARGIN_LEFT,
+            right = right,
+            py = py,
+            label = MARGIN_LEFT - 6.0,
+            text_y = py + 4.0,
+            value = format_tick(y)
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r#"<text x="{:.2}" y="{:.2}" text-anchor="middle">{}</text>"#,
+        (MARGIN_LEFT + right) / 2.0,
+        frame.height - 16.0,
+        x_label
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="16" y="{y:.2}" text-anchor="middle" transform="rotate(-90 16 {y:.2})">{label}</text>"#,
+        y = (MARGIN_TOP + bottom) / 2.0,
+        label = y_label
+    );
+}
+
+/// Draws a chart line with a style depending on its kind and labels it at its last point.
+fn draw_line(svg: &mut String, frame: &Frame, line: &ChartLine) {
+    let points = line
+        .coordinates(frame.kind)
+        .into_iter()
+        .filter(|&xy| frame.contains(xy))
+        .collect::<Vec<(f64, f64)>>();
+    if points.len() < 2 {
+        return;
+    }
+
+    let (stroke, width, dash, unit) = match line.kind {
+        LineKind::BubbleLine | LineKind::DewLine => ("#000000", 2.0, "", ""),
+        LineKind::Isotherm => ("#d62728", 1.0, "", " K"),
+        LineKind::Isentrope => ("#2ca02c", 1.0, "", " J/mol-K"),
+        LineKind::Isochore => ("#1f77b4", 1.0, "4 2", " mol/L"),
+        LineKind::Quality => ("#7f7f7f", 1.0, "2 2", ""),
+    };
+
+    let _ = writeln!(
+        svg,
+        r#"<polyline fill="none" stroke="{}" stroke-width="{}" stroke-dasharray="{}" points="{}"/>"#,
+        stroke,
+        width,
+        dash,
+        points_attribute(frame, &points)
+    );
+
+    if line.value.is_finite() {
+        let (x, y) = points[points.len() - 1];
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{:.2}" fill="{}" font-size="10">{}{}</text>"#,
+            frame.px(x) + 3.0,
+            frame.py(y) - 3.0,
+            stroke,
+            format_tick(line.value),
+            unit
+        );
+    }
+}
+
+/// Formats points as the value of an SVG `points` attribute.
+fn points_attribute(frame: &Frame, points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .map(|&(x, y)| format!("{:.2},{:.2}", frame.px(x), frame.py(y)))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Returns evenly spaced, rounded tick values covering `[min, max]`.
+fn linear_ticks(min: f64, max: f64) -> Vec<f64> {
+    let raw_step = (max - min) / 6.0;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 2.5, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|&s| s >= raw_step)
+        .unwrap_or(10.0 * magnitude);
+
+    let mut ticks = Vec::new();
+    let mut tick = (min / step).ceil() * step;
+    while tick <= max + 1e-9 * step {
+        ticks.push(tick);
+        tick += step;
+    }
+    ticks
+}
+
+/// Returns tick values at 1, 2, and 5 times each power of ten within `[10^min, 10^max]`.
+fn log_ticks(log_min: f64, log_max: f64) -> Vec<f64> {
+    let mut ticks = Vec::new();
+    for decade in (log_min.floor() as i32)..=(log_max.ceil() as i32) {
+        for mantissa in [1.0, 2.0, 5.0] {
+            let tick = mantissa * 10f64.powi(decade);
+            let log_tick = tick.log10();
+            if log_tick >= log_min - 1e-9 && log_tick <= log_max + 1e-9 {
+                ticks.push(tick);
+            }
+        }
+    }
+    ticks
+}
+
+/// Formats a tick value without superfluous decimals.
+fn format_tick(value: f64) -> String {
+    if value == value.round() && value.abs() < 1e9 {
+        format!("{}", value as i64)
+    } else {
+        let formatted = format!("{:.3}", value);
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(T: f64, P: f64, h: f64, s: f64) -> StatePoint {
+        StatePoint { T, P, D: 1.0, h, s }
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let chart = PropertyChart {
+            critical_point: None,
+            dome: vec![ChartLine {
+                kind: LineKind::BubbleLine,
+                value: f64::NAN,
+                points: vec![
+                    point(250.0, 200.0, 1000.0, 5.0),
+                    point(300.0, 1500.0, 5000.0, 20.0),
+                ],
+            }],
+            lines: vec![ChartLine {
+                kind: LineKind::Isotherm,
+                value: 300.0,
+                points: vec![
+                    point(300.0, 100.0, 30000.0, 120.0),
+                    point(300.0, 1500.0, 28000.0, 100.0),
+                ],
+            }],
+        };
+        let overlay = [
+            point(280.0, 800.0, 2000.0, 10.0),
+            point(320.0, 1500.0, 29000.0, 110.0),
+        ];
+
+        let svg = render(
+            &chart,
+            ChartKind::LogPressureEnthalpy,
+            &overlay,
+            800.0,
+            600.0,
+        );
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert_eq!(svg.matches("<circle").count(), 2);
+        assert!(svg.contains("300 K"));
+    }
+
+    #[test]
+    fn test_ticks() {
+        assert_eq!(
+            linear_ticks(0.0, 12.0),
+            vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0, 12.0]
+        );
+        assert_eq!(log_ticks(2.0, 3.0), vec![100.0, 200.0, 500.0, 1000.0]);
+        assert_eq!(format_tick(2.5), "2.5");
+        assert_eq!(format_tick(300.0), "300");
+    }
+}
@@ -0,0 +1,315 @@
+//! Columnar output of flash calculations, built with the `arrow` and `polars` features.
+//!
+//! Columns carry the same unit-bearing names as a `FlashOutput` serialized on a molar basis (e.g.,
+//! `T_K`, `h_J_per_mol`), followed by one `x_<component>` and one `y_<component>` column per loaded
+//! component, named after its fluid file (e.g., `x_R1234YF`). Values that are undefined (`Cv` and `Cp` in the two-phase region) or belong to failed
+//! points are null.
+
+use crate::{utils::acquire_lock, Basis, FlashOutput, RefpropError, RefpropFunctionLibrary};
+#[cfg(feature = "polars")]
+use crate::{CompositionInput, InputPair};
+
+/// Named columns of nullable values.
+type Columns = Vec<(String, Vec<Option<f64>>)>;
+
+/// Accessor of a nullable property of a `FlashOutput`.
+type Accessor = fn(&FlashOutput) -> Option<f64>;
+
+/// Names and accessors of the scalar columns of a `FlashOutput`.
+const SCALAR_COLUMNS: [(&str, Accessor); 12] = [
+    ("T_K", |output| Some(output.T)),
+    ("P_kPa", |output| Some(output.P)),
+    ("D_mol_per_L", |output| Some(output.D)),
+    ("Dl_mol_per_L", |output| Some(output.Dl)),
+    ("Dv_mol_per_L", |output| Some(output.Dv)),
+    ("q_mol_per_mol", |output| Some(output.q)),
+    ("e_J_per_mol", |output| Some(output.e)),
+    ("h_J_per_mol", |output| Some(output.h)),
+    ("s_J_per_mol_K", |output| Some(output.s)),
+    ("Cv_J_per_mol_K", |output| output.Cv),
+    ("Cp_J_per_mol_K", |output| output.Cp),
+    ("w_m_per_s", |output| Some(output.w)),
+];
+
+/// Splits flash results into named columns; `None` entries become null rows.
+///
+/// The component columns are named after the fluid files currently loaded in REFPROP, whose names
+/// are stable and upper-case unlike the short names of the fluids (e.g., `R1234YF` for `R1234yf`).
+fn flash_columns(outputs: &[Option<&FlashOutput>]) -> Result<Columns, RefpropError> {
+    if outputs
+        .iter()
+        .flatten()
+        .any(|o| !matches!(o.basis, Basis::Molar))
+    {
+        return Err(RefpropError::InvalidInput(
+            "Columnar output requires flash results on a molar basis.".to_string(),
+        ));
+    }
+    let names: Vec<String> = RefpropFunctionLibrary::loaded_fluids()?
+        .components
+        .into_iter()
+        .map(|component| match component.path.file_stem() {
+            Some(stem) => stem.to_string_lossy().to_uppercase(),
+            None => component.name,
+        })
+        .collect();
+    if let Some(output) = outputs.iter().flatten().find(|o| o.x.len() != names.len()) {
+        return Err(RefpropError::InvalidInput(format!(
+            "Flash results have {} components, but {} are loaded.",
+            output.x.len(),
+            names.len()
+        )));
+    }
+
+    let mut columns: Columns = SCALAR_COLUMNS
+        .iter()
+        .map(|(name, value)| {
+            let values = outputs.iter().map(|o| o.and_then(value)).collect();
+            (name.to_string(), values)
+        })
+        .collect();
+
+    for (prefix, liquid) in [("x", true), ("y", false)] {
+        for (icomp, name) in names.iter().enumerate() {
+            let values = outputs
+                .iter()
+                .map(|o| o.and_then(|o| if liquid { &o.x } else { &o.y }.get(icomp).copied()))
+                .collect();
+            columns.push((format!("{}_{}", prefix, name), values));
+        }
+    }
+
+    Ok(columns)
+}
+
+#[cfg(feature = "arrow")]
+impl RefpropFunctionLibrary {
+    /// Converts a batch of flash results into an Arrow record batch.
+    ///
+    /// The batch has one row per result and one `Float64` column per property, named with its unit
+    /// (e.g., `T_K`, `h_J_per_mol`), followed by the liquid (`x_<component>`) and vapor
+    /// (`y_<component>`) mole fractions, named after the fluid files currently loaded in REFPROP.
+    /// `Cv` and `Cp` are null in the two-phase region.
+    ///
+    /// # Parameters
+    ///
+    /// - `outputs`: Flash results calculated for the fluid that is currently loaded.
+    ///
+    /// # Returns
+    ///
+    /// - `RecordBatch`: The results in columnar form.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the results are not on a molar basis or do not match the
+    ///   number of loaded components.
+    /// - Returns other `RefpropError` variants if the component names cannot be retrieved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///
+    ///     let _guard = RefpropFunctionLibrary::lock()?;
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///     let outputs = [250.0, 275.0, 300.0]
+    ///         .iter()
+    ///         .map(|&T| RefpropFunctionLibrary::tp_flash(T, 101.325, &z))
+    ///         .collect::<Result<Vec<_>, _>>()?;
+    ///
+    ///     let batch = RefpropFunctionLibrary::flash_record_batch(&outputs)?;
+    ///     assert_eq!(batch.num_rows(), 3);
+    ///     assert!(batch.column_by_name("x_R32").is_some());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn flash_record_batch(
+        outputs: &[FlashOutput],
+    ) -> Result<arrow_array::RecordBatch, RefpropError> {
+        use std::sync::Arc;
+
+        use arrow_array::{ArrayRef, Float64Array, RecordBatch};
+        use arrow_schema::{DataType, Field, Schema};
+
+        // Keep the loaded fluid fixed while the component names are read
+        let _lock = acquire_lock()?;
+
+        let outputs: Vec<_> = outputs.iter().map(Some).collect();
+        let columns = flash_columns(&outputs)?;
+
+        let fields: Vec<Field> = columns
+            .iter()
+            .map(|(name, _)| Field::new(name, DataType::Float64, true))
+            .collect();
+        let arrays: Vec<ArrayRef> = columns
+            .into_iter()
+            .map(|(_, values)| Arc::new(Float64Array::from(values)) as ArrayRef)
+            .collect();
+
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+            .map_err(|e| RefpropError::UnknownError(format!("Invalid record batch: {}", e)))
+    }
+}
+
+#[cfg(feature = "polars")]
+impl RefpropFunctionLibrary {
+    /// Applies a flash calculation to every row of a Polars DataFrame of inputs.
+    ///
+    /// The two independent properties are read from the columns `a` and `b` (cast to `Float64`), in
+    /// the default molar units of `InputPair`. The result holds the input columns followed by the
+    /// columns of `flash_record_batch`; rows whose inputs are null or where REFPROP reports an error are null.
+    ///
+    /// The global REFPROP lock is held for the whole frame, so the fluid must be loaded beforehand by
+    /// the same thread or within the same `RefpropFunctionLibrary::lock` scope.
+    ///
+    /// # Parameters
+    ///
+    /// - `inputs`: DataFrame holding the independent properties.
+    /// - `pair`: Pair of independent properties held by the columns `a` and `b`.
+    /// - `a`: Name of the column holding the first property of the pair.
+    /// - `b`: Name of the column holding the second property of the pair.
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `DataFrame`: The inputs with the calculated properties appended.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if a column is missing or not numeric, the composition is
+    ///   invalid, or a result column clashes with an input column.
+    /// - Returns other `RefpropError` variants if an error other than a calculation error occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use polars::prelude::*;
+    /// use refprop_sys::{InputPair, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///
+    ///     let inputs = DataFrame::new(vec![
+    ///         Column::new("T".into(), [250.0, 275.0, 300.0]),
+    ///         Column::new("P".into(), [101.325, 101.325, 101.325]),
+    ///     ])
+    ///     .unwrap();
+    ///
+    ///     let _guard = RefpropFunctionLibrary::lock()?;
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///     let states = RefpropFunctionLibrary::flash_dataframe(&inputs, InputPair::TP, "T", "P", &z)?;
+    ///     println!("{}", states);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn flash_dataframe(
+        inputs: &polars::prelude::DataFrame,
+        pair: InputPair,
+        a: &str,
+        b: &str,
+        z: impl Into<CompositionInput<'_>>,
+    ) -> Result<polars::prelude::DataFrame, RefpropError> {
+        use polars::prelude::{Column, DataType, PolarsError};
+
+        let invalid = |e: PolarsError| RefpropError::InvalidInput(e.to_string());
+
+        let read = |name: &str| -> Result<Vec<Option<f64>>, RefpropError> {
+            let column = inputs
+                .column(name)
+                .and_then(|column| column.cast(&DataType::Float64))
+                .map_err(invalid)?;
+            Ok(column.f64().map_err(invalid)?.into_iter().collect())
+        };
+        let (a, b) = (read(a)?, read(b)?);
+
+        let _lock = acquire_lock()?;
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        let outputs = a
+            .iter()
+            .zip(&b)
+            .map(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => match Self::flash(pair, *a, *b, &*z) {
+                    Ok(output) => Ok(Some(output)),
+                    // A point that REFPROP cannot calculate is a null row
+                    Err(RefpropError::CalculationError(_)) => Ok(None),
+                    Err(error) => Err(error),
+                },
+                _ => Ok(None),
+            })
+            .collect::<Result<Vec<Option<FlashOutput>>, RefpropError>>()?;
+        let outputs: Vec<_> = outputs.iter().map(Option::as_ref).collect();
+
+        let columns: Vec<Column> = flash_columns(&outputs)?
+            .into_iter()
+            .map(|(name, values)| Column::new(name.into(), values))
+            .collect();
+        inputs.hstack(&columns).map_err(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputPair;
+
+    #[test]
+    fn test_flash_columns() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+
+        let vapor = RefpropFunctionLibrary::flash(InputPair::TP, 300.0, 101.325, &z)?;
+        let two_phase = RefpropFunctionLibrary::flash(InputPair::TQ, 250.0, 0.5, &z)?;
+        let columns = flash_columns(&[Some(&vapor), Some(&two_phase), None])?;
+
+        let column = |name: &str| {
+            columns
+                .iter()
+                .find(|(column, _)| column == name)
+                .map(|(_, values)| values.clone())
+                .unwrap_or_else(|| panic!("Missing column {}", name))
+        };
+        for name in ["x_R32", "x_R1234YF", "y_R32", "y_R1234YF"] {
+            assert_eq!(column(name).len(), 3);
+        }
+        assert_eq!(column("T_K"), vec![Some(300.0), Some(two_phase.T), None]);
+        assert_eq!(column("y_R32")[1], two_phase.y.first().copied());
+
+        // Heat capacities are undefined in the two-phase region
+        assert!(column("Cv_J_per_mol_K")[0].is_some());
+        assert_eq!(column("Cv_J_per_mol_K")[1], None);
+        assert_eq!(column("Cp_J_per_mol_K")[1], None);
+        Ok(())
+    }
+
+    #[cfg(feature = "polars")]
+    #[test]
+    fn test_flash_dataframe() -> Result<(), RefpropError> {
+        use polars::prelude::{Column, DataFrame};
+
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+
+        // The second row cannot be calculated and the third has a null input
+        let inputs = DataFrame::new(vec![
+            Column::new("T".into(), [Some(300.0), Some(300.0), None]),
+            Column::new("P".into(), [Some(101.325), Some(-1.0), Some(101.325)]),
+        ])
+        .unwrap();
+        let states = RefpropFunctionLibrary::flash_dataframe(&inputs, InputPair::TP, "T", "P", &z)?;
+
+        assert_eq!(states.height(), 3);
+        for name in ["T_K", "Cp_J_per_mol_K", "x_R32", "y_R1234YF"] {
+            let values = states.column(name).unwrap().f64().unwrap();
+            assert!(values.get(0).is_some());
+            assert_eq!(values.get(1), None);
+            assert_eq!(values.get(2), None);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,593 @@
+use std::{
+    borrow::Cow,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{
+    utils::{acquire_lock, fluids_generation, validate_fractions},
+    Basis, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Component name lists found to match the loaded fluids, with the fluids generation they were
+/// checked at plus one (0 if never).
+static VERIFIED_COMPONENTS: Mutex<(u64, Vec<Vec<String>>)> = Mutex::new((0, Vec::new()));
+
+/// Represents the basis on which the fractions of a `Composition` are expressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompositionBasis {
+    /// Mole fractions [mol/mol].
+    Mole,
+    /// Mass fractions [kg/kg].
+    Mass,
+}
+
+/// Represents the composition of a fluid, tracking whether it is expressed in mole or mass fractions
+/// and, optionally, which components the fractions refer to.
+///
+/// All flash routines accept a `Composition` through `CompositionInput`. Raw slices and vectors of
+/// `f64` are taken as mole fractions, so `&z` can still be passed where `z: Vec<f64>` holds mole
+/// fractions; such raw compositions are borrowed without copying and validated when they are used.
+///
+/// **Note:** Converting between bases uses the molar masses of the fluids currently loaded in
+/// REFPROP, so the components must be loaded (in the same order) before a mass-based composition is
+/// converted or passed to a flash routine. A composition with component names is checked against
+/// the loaded fluids whenever its fractions are used; each name must be the short name, full name,
+/// CAS number, or fluid file name of the loaded component at the same position.
+///
+/// # Examples
+///
+/// ```rust
+/// use refprop_sys::{Composition, RefpropFunctionLibrary, RefpropError};
+///
+/// fn main() -> Result<(), RefpropError> {
+///     let _ = RefpropFunctionLibrary::set_path(None);
+///     RefpropFunctionLibrary::set_fluids("R32;R1234YF")?;
+///
+///     // 68.9 / 31.1 % by mass, as specified for R454B
+///     let z = Composition::mass(vec![0.689, 0.311])?.with_components(vec!["R32", "R1234YF"])?;
+///     let output = RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z)?;
+///
+///     println!("Mole fractions: {:?}", z.mole_fractions()?);
+///     println!("Density: {} mol/L", output.D);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "CompositionData")
+)]
+pub struct Composition {
+    fractions: Vec<f64>,
+    basis: CompositionBasis,
+    components: Option<Vec<String>>,
+}
+
+/// Unvalidated fields of a deserialized `Composition`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CompositionData {
+    fractions: Vec<f64>,
+    basis: CompositionBasis,
+    components: Option<Vec<String>>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CompositionData> for Composition {
+    type Error = RefpropError;
+
+    fn try_from(data: CompositionData) -> Result<Self, Self::Error> {
+        let composition = Composition::new(data.fractions, data.basis)?;
+        match data.components {
+            Some(components) => composition.with_components(components),
+            None => Ok(composition),
+        }
+    }
+}
+
+impl Composition {
+    /// Creates a composition from fractions on the given basis.
+    ///
+    /// # Parameters
+    ///
+    /// - `fractions`: The mole or mass fractions of the components. Maximum of 20 components.
+    /// - `basis`: The basis on which `fractions` are expressed.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if:
+    ///     - `fractions` is empty or contains more than 20 elements.
+    ///     - Any fraction is negative or not finite.
+    ///     - The sum of the fractions does not equal 1 within a specified tolerance.
+    pub fn new(fractions: Vec<f64>, basis: CompositionBasis) -> Result<Self, RefpropError> {
+        check_fractions(&fractions)?;
+        validate_fractions(&fractions)?;
+
+        Ok(Composition {
+            fractions,
+            basis,
+            components: None,
+        })
+    }
+
+    /// Creates a composition from mole fractions.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` under the same conditions as `Composition::new`.
+    pub fn mole(fractions: Vec<f64>) -> Result<Self, RefpropError> {
+        Self::new(fractions, CompositionBasis::Mole)
+    }
+
+    /// Creates a composition from mass fractions.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` under the same conditions as `Composition::new`.
+    pub fn mass(fractions: Vec<f64>) -> Result<Self, RefpropError> {
+        Self::new(fractions, CompositionBasis::Mass)
+    }
+
+    /// Creates a composition by normalising the given amounts so that they sum to 1.
+    ///
+    /// # Parameters
+    ///
+    /// - `amounts`: Relative amounts (e.g., moles, masses, or percentages) of the components.
+    /// - `basis`: The basis on which `amounts` are expressed.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if:
+    ///     - `amounts` is empty or contains more than 20 elements.
+    ///     - Any amount is negative or not finite, or all amounts are zero.
+    pub fn normalized(amounts: Vec<f64>, basis: CompositionBasis) -> Result<Self, RefpropError> {
+        check_fractions(&amounts)?;
+        let total: f64 = amounts.iter().sum();
+        if total <= 0.0 {
+            return Err(RefpropError::InvalidInput(
+                "Cannot normalise a composition whose amounts sum to zero.".to_string(),
+            ));
+        }
+
+        Self::new(amounts.iter().map(|&a| a / total).collect(), basis)
+    }
+
+    /// Creates the composition of a pure fluid (a single mole fraction of 1).
+    pub fn pure() -> Self {
+        Composition {
+            fractions: vec![1.0],
+            basis: CompositionBasis::Mole,
+            components: None,
+        }
+    }
+
+    /// Creates a composition for the fluids currently loaded in REFPROP, naming its components after them.
+    ///
+    /// # Parameters
+    ///
+    /// - `fractions`: The mole or mass fractions of the loaded components, in the order they were loaded.
+    /// - `basis`: The basis on which `fractions` are expressed.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the fractions are invalid or their number differs
+    ///   from the number of loaded components.
+    /// - Returns `RefpropError::Utf8Error` if the component names cannot be converted to UTF-8.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn from_loaded(fractions: Vec<f64>, basis: CompositionBasis) -> Result<Self, RefpropError> {
+        let names = RefpropFunctionLibrary::loaded_component_names()?;
+        Self::new(fractions, basis)?.with_components(names)
+    }
+
+    /// Attaches the names of the components the fractions refer to.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the number of names differs from the number of fractions.
+    pub fn with_components<S: Into<String>>(
+        mut self,
+        components: Vec<S>,
+    ) -> Result<Self, RefpropError> {
+        if components.len() != self.fractions.len() {
+            return Err(RefpropError::InvalidInput(format!(
+                "Composition has {} fractions but {} component names were given.",
+                self.fractions.len(),
+                components.len()
+            )));
+        }
+        self.components = Some(components.into_iter().map(Into::into).collect());
+
+        Ok(self)
+    }
+
+    /// Returns the fractions on the basis of the composition.
+    pub fn fractions(&self) -> &[f64] {
+        &self.fractions
+    }
+
+    /// Returns the basis on which the fractions are expressed.
+    pub fn basis(&self) -> CompositionBasis {
+        self.basis
+    }
+
+    /// Returns the names of the components, if known.
+    pub fn components(&self) -> Option<&[String]> {
+        self.components.as_deref()
+    }
+
+    /// Returns the number of components.
+    pub fn len(&self) -> usize {
+        self.fractions.len()
+    }
+
+    /// Returns `true` if the composition has no components.
+    pub fn is_empty(&self) -> bool {
+        self.fractions.is_empty()
+    }
+
+    /// Returns the mole fractions, converting from mass fractions with `convert_to_mole_fractions` if needed.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the fractions are invalid or the component names do
+    ///   not match the loaded fluids.
+    /// - Returns `RefpropError::CalculationError` if the conversion fails.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn mole_fractions(&self) -> Result<Vec<f64>, RefpropError> {
+        // Keep the loaded fluids fixed between the check and the conversion
+        let _lock = acquire_lock()?;
+        self.check_loaded_components()?;
+        match self.basis {
+            CompositionBasis::Mole => Ok(self.fractions.clone()),
+            CompositionBasis::Mass => {
+                let (mole_fractions, _) =
+                    RefpropFunctionLibrary::convert_to_mole_fractions(&self.fractions)?;
+                Ok(mole_fractions)
+            }
+        }
+    }
+
+    /// Returns the mass fractions, converting from mole fractions with `convert_to_mass_fractions` if needed.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the fractions are invalid or the component names do
+    ///   not match the loaded fluids.
+    /// - Returns `RefpropError::CalculationError` if the conversion fails.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn mass_fractions(&self) -> Result<Vec<f64>, RefpropError> {
+        // Keep the loaded fluids fixed between the check and the conversion
+        let _lock = acquire_lock()?;
+        self.check_loaded_components()?;
+        match self.basis {
+            CompositionBasis::Mass => Ok(self.fractions.clone()),
+            CompositionBasis::Mole => {
+                let (mass_fractions, _) =
+                    RefpropFunctionLibrary::convert_to_mass_fractions(&self.fractions)?;
+                Ok(mass_fractions)
+            }
+        }
+    }
+
+    /// Converts the composition to a mole basis, keeping its component names.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `Composition::mole_fractions`.
+    pub fn to_mole_basis(&self) -> Result<Composition, RefpropError> {
+        Ok(Composition {
+            fractions: self.mole_fractions()?,
+            basis: CompositionBasis::Mole,
+            components: self.components.clone(),
+        })
+    }
+
+    /// Converts the composition to a mass basis, keeping its component names.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `Composition::mass_fractions`.
+    pub fn to_mass_basis(&self) -> Result<Composition, RefpropError> {
+        Ok(Composition {
+            fractions: self.mass_fractions()?,
+            basis: CompositionBasis::Mass,
+            components: self.components.clone(),
+        })
+    }
+
+    /// Checks the component names, if any, against the fluids currently loaded in REFPROP. Must be
+    /// called while holding the lock.
+    fn check_loaded_components(&self) -> Result<(), RefpropError> {
+        let Some(components) = &self.components else {
+            return Ok(());
+        };
+
+        // The check is repeated only after the loaded fluids have changed
+        let generation = fluids_generation() + 1;
+        let mut verified = VERIFIED_COMPONENTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if verified.0 != generation {
+            *verified = (generation, Vec::new());
+        }
+        if verified.1.contains(components) {
+            return Ok(());
+        }
+
+        let loaded = RefpropFunctionLibrary::loaded_fluids()?;
+        let matches = components.len() == loaded.ncomp()
+            && components
+                .iter()
+                .zip(&loaded.components)
+                .all(|(name, component)| component.matches(name));
+        if !matches {
+            return Err(RefpropError::InvalidInput(format!(
+                "Composition refers to {} but {} is loaded.",
+                components.join(", "),
+                loaded.names().join(", ")
+            )));
+        }
+        verified.1.push(components.clone());
+
+        Ok(())
+    }
+
+    /// Returns the fractions expected by a REFPROP routine called with the given `imass` flag.
+    ///
+    /// REFPROP expects mass fractions when all inputs are on a mass basis and mole fractions otherwise.
+    pub(crate) fn fractions_for(&self, basis: Basis) -> Result<Vec<f64>, RefpropError> {
+        match basis {
+            Basis::Mass => self.mass_fractions(),
+            Basis::Molar | Basis::MassExceptComposition => self.mole_fractions(),
+        }
+    }
+}
+
+impl From<Vec<f64>> for Composition {
+    fn from(fractions: Vec<f64>) -> Self {
+        Composition {
+            fractions,
+            basis: CompositionBasis::Mole,
+            components: None,
+        }
+    }
+}
+
+impl From<&Vec<f64>> for Composition {
+    fn from(fractions: &Vec<f64>) -> Self {
+        Composition::from(fractions.clone())
+    }
+}
+
+impl From<&[f64]> for Composition {
+    fn from(fractions: &[f64]) -> Self {
+        Composition::from(fractions.to_vec())
+    }
+}
+
+impl<const N: usize> From<[f64; N]> for Composition {
+    fn from(fractions: [f64; N]) -> Self {
+        Composition::from(fractions.to_vec())
+    }
+}
+
+impl<const N: usize> From<&[f64; N]> for Composition {
+    fn from(fractions: &[f64; N]) -> Self {
+        Composition::from(fractions.to_vec())
+    }
+}
+
+impl From<&Composition> for Composition {
+    fn from(composition: &Composition) -> Self {
+        composition.clone()
+    }
+}
+
+/// Composition argument of the flash routines.
+///
+/// Raw mole fractions and references to a `Composition` are borrowed rather than copied, so that a
+/// flash call with `&z` does not allocate. It is created implicitly from the same types as a
+/// `Composition`.
+#[derive(Debug, Clone)]
+pub enum CompositionInput<'a> {
+    /// Mole fractions without component names, validated when they are used.
+    Fractions(Cow<'a, [f64]>),
+    /// A composition, possibly on a mass basis or with component names.
+    Composition(Cow<'a, Composition>),
+}
+
+impl CompositionInput<'_> {
+    /// Returns the fractions expected by a REFPROP routine called with the given `imass` flag,
+    /// borrowing them when no conversion or check against the loaded fluids is needed.
+    ///
+    /// Raw fractions are mole fractions, and are converted to mass fractions for `Basis::Mass`. Must be
+    /// called while holding the lock.
+    pub(crate) fn fractions_for(&self, basis: Basis) -> Result<Cow<'_, [f64]>, RefpropError> {
+        let mass = matches!(basis, Basis::Mass);
+        match self {
+            CompositionInput::Fractions(fractions) if !mass => Ok(Cow::Borrowed(fractions)),
+            CompositionInput::Fractions(fractions) => {
+                let (mass_fractions, _) =
+                    RefpropFunctionLibrary::convert_to_mass_fractions(fractions)?;
+                Ok(Cow::Owned(mass_fractions))
+            }
+            CompositionInput::Composition(composition) => {
+                let on_basis = match composition.basis {
+                    CompositionBasis::Mole => !mass,
+                    CompositionBasis::Mass => mass,
+                };
+                if on_basis && composition.components.is_none() {
+                    Ok(Cow::Borrowed(&composition.fractions))
+                } else {
+                    composition.fractions_for(basis).map(Cow::Owned)
+                }
+            }
+        }
+    }
+
+    /// Returns the mole fractions, borrowing them when possible. Must be called while holding the lock.
+    pub(crate) fn mole_fractions(&self) -> Result<Cow<'_, [f64]>, RefpropError> {
+        self.fractions_for(Basis::Molar)
+    }
+}
+
+impl From<Vec<f64>> for CompositionInput<'_> {
+    fn from(fractions: Vec<f64>) -> Self {
+        CompositionInput::Fractions(Cow::Owned(fractions))
+    }
+}
+
+impl<'a> From<&'a Vec<f64>> for CompositionInput<'a> {
+    fn from(fractions: &'a Vec<f64>) -> Self {
+        CompositionInput::Fractions(Cow::Borrowed(fractions))
+    }
+}
+
+impl<'a> From<&'a [f64]> for CompositionInput<'a> {
+    fn from(fractions: &'a [f64]) -> Self {
+        CompositionInput::Fractions(Cow::Borrowed(fractions))
+    }
+}
+
+impl<const N: usize> From<[f64; N]> for CompositionInput<'_> {
+    fn from(fractions: [f64; N]) -> Self {
+        CompositionInput::Fractions(Cow::Owned(fractions.to_vec()))
+    }
+}
+
+impl<'a, const N: usize> From<&'a [f64; N]> for CompositionInput<'a> {
+    fn from(fractions: &'a [f64; N]) -> Self {
+        CompositionInput::Fractions(Cow::Borrowed(fractions))
+    }
+}
+
+impl From<Composition> for CompositionInput<'_> {
+    fn from(composition: Composition) -> Self {
+        CompositionInput::Composition(Cow::Owned(composition))
+    }
+}
+
+impl<'a> From<&'a Composition> for CompositionInput<'a> {
+    fn from(composition: &'a Composition) -> Self {
+        CompositionInput::Composition(Cow::Borrowed(composition))
+    }
+}
+
+/// Checks that the fractions are non-empty, non-negative, and finite.
+fn check_fractions(fractions: &[f64]) -> Result<(), RefpropError> {
+    if fractions.is_empty() {
+        return Err(RefpropError::InvalidInput(
+            "Composition must contain at least one component.".to_string(),
+        ));
+    }
+    if let Some(value) = fractions.iter().find(|v| !v.is_finite() || **v < 0.0) {
+        return Err(RefpropError::InvalidInput(format!(
+            "Composition fractions must be finite and non-negative. Found: {}",
+            value
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composition_validation() {
+        assert!(Composition::mole(vec![0.5, 0.5]).is_ok());
+        assert!(Composition::mole(vec![0.5, 0.6]).is_err());
+        assert!(Composition::mole(vec![1.2, -0.2]).is_err());
+        assert!(Composition::mole(vec![]).is_err());
+        assert!(Composition::mass(vec![1.0 / 21.0; 21]).is_err());
+        assert!(Composition::mole(vec![0.5, 0.5])
+            .unwrap()
+            .with_components(vec!["R32"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_composition_normalized() -> Result<(), RefpropError> {
+        let z = Composition::normalized(vec![68.9, 31.1], CompositionBasis::Mass)?
+            .with_components(vec!["R32", "R1234YF"])?;
+
+        assert_eq!(z.basis(), CompositionBasis::Mass);
+        assert!((z.fractions()[0] - 0.689).abs() < 1e-12);
+        assert_eq!(z.components().unwrap()[1], "R1234YF");
+        assert!(Composition::normalized(vec![0.0, 0.0], CompositionBasis::Mole).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_composition_components_checked() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        RefpropFunctionLibrary::set_fluids("R32;R1234YF")?;
+
+        let z = Composition::mole(vec![0.7, 0.3])?;
+        assert!(z
+            .clone()
+            .with_components(vec!["R32", "R1234YF"])?
+            .mole_fractions()
+            .is_ok());
+        assert!(z
+            .clone()
+            .with_components(vec!["75-10-5", "r1234yf"])?
+            .mole_fractions()
+            .is_ok());
+        assert!(matches!(
+            z.clone()
+                .with_components(vec!["R32", "R125"])?
+                .mole_fractions(),
+            Err(RefpropError::InvalidInput(_))
+        ));
+
+        // Names verified earlier are checked again once other fluids are loaded
+        let named = z.with_components(vec!["R32", "R1234YF"])?;
+        RefpropFunctionLibrary::set_fluids("R32;R125")?;
+        assert!(matches!(
+            named.mole_fractions(),
+            Err(RefpropError::InvalidInput(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_composition_input_borrows() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        RefpropFunctionLibrary::set_fluids("R32;R1234YF")?;
+
+        // Raw mole fractions and unnamed mole-based compositions are not copied
+        let z = vec![0.7, 0.3];
+        let input = CompositionInput::from(&z);
+        assert!(matches!(input.mole_fractions()?, Cow::Borrowed(_)));
+        let composition = Composition::mole(z.clone())?;
+        let input = CompositionInput::from(&composition);
+        assert!(matches!(input.mole_fractions()?, Cow::Borrowed(_)));
+
+        // Raw fractions are converted to mass fractions like a mole-based composition
+        let input = CompositionInput::from(&z);
+        let mass_fractions = input.fractions_for(Basis::Mass)?;
+        assert_eq!(mass_fractions.into_owned(), composition.mass_fractions()?);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_composition_deserialize_validates() {
+        let valid = r#"{"fractions": [0.5, 0.5], "basis": "Mole", "components": null}"#;
+        assert!(serde_json::from_str::<Composition>(valid).is_ok());
+
+        let invalid = r#"{"fractions": [0.5, 0.6], "basis": "Mole", "components": null}"#;
+        assert!(serde_json::from_str::<Composition>(invalid).is_err());
+
+        let mismatched = r#"{"fractions": [1.0], "basis": "Mass", "components": ["R32", "R125"]}"#;
+        assert!(serde_json::from_str::<Composition>(mismatched).is_err());
+    }
+}
@@ -28,3 +28,29 @@ pub enum RefpropError {
     #[error("Unknown error: {0}")]
     UnknownError(String),
 }
+
+impl RefpropError {
+    /// Returns the name of the error variant (e.g., `"InvalidInput"`), for structured error reporting.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RefpropError::InitializationError(_) => "InitializationError",
+            RefpropError::CalculationError(_) => "CalculationError",
+            RefpropError::InvalidInput(_) => "InvalidInput",
+            RefpropError::Utf8Error(_) => "Utf8Error",
+            RefpropError::MutexPoisoned => "MutexPoisoned",
+            RefpropError::UnknownError(_) => "UnknownError",
+        }
+    }
+
+    /// Returns the error message without the description of the error variant.
+    pub fn message(&self) -> String {
+        match self {
+            RefpropError::InitializationError(message)
+            | RefpropError::CalculationError(message)
+            | RefpropError::InvalidInput(message)
+            | RefpropError::UnknownError(message) => message.clone(),
+            RefpropError::Utf8Error(error) => error.to_string(),
+            RefpropError::MutexPoisoned => "Mutex was poisoned".to_string(),
+        }
+    }
+}
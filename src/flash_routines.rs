@@ -2,7 +2,9 @@ mod ab_flash;
 mod de_flash;
 mod dh_flash;
 mod ds_flash;
+mod flash_property;
 mod hs_flash;
+mod input_pair;
 mod pd_flash;
 mod pe_flash;
 mod ph_flash;
@@ -14,9 +16,24 @@ mod th_flash;
 mod tp_flash;
 mod tq_flash;
 mod ts_flash;
+mod warm_start;
 
-// Represents the output properties from the flash routines.
+pub use flash_property::FlashProperty;
+pub use input_pair::InputPair;
+
+use crate::Basis;
+
+/// Represents the output properties from the flash routines.
+///
+/// With the `serde` feature, the properties are serialized under names that carry their units, which
+/// depend on `basis` (e.g. `D_mol_per_L` on a molar basis and `D_kg_per_m3` on a mass basis). The
+/// format is described by the JSON schema in `schema/flash_output.schema.json`.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "FlashRecord", from = "FlashRecord")
+)]
 pub struct FlashOutput {
     /// Temperature [K]
     pub T: f64,
@@ -24,9 +41,9 @@ pub struct FlashOutput {
     pub P: f64,
     /// Density [mol/L or kg/m³]
     pub D: f64,
-    /// Molar density of the liquid phase [mol/L or kg/m³]
+    /// Density of the liquid phase [mol/L or kg/m³]
     pub Dl: f64,
-    /// Molar density of the vapor phase [mol/L or kg/m³]
+    /// Density of the vapor phase [mol/L or kg/m³]
     pub Dv: f64,
     /// Composition of the liquid phase (mole or mass fractions)
     pub x: Vec<f64>,
@@ -46,4 +63,263 @@ pub struct FlashOutput {
     pub Cp: Option<f64>, // Not defined for 2-phase states
     /// Speed of sound [m/s]
     pub w: f64,
+    /// Basis of the densities, the energies, the heat capacities and the compositions
+    pub basis: Basis,
+}
+
+/// Serialized form of `FlashOutput`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct FlashRecord {
+    T_K: f64,
+    P_kPa: f64,
+    #[serde(flatten)]
+    properties: BasisProperties,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    q_mol_per_mol: f64,
+    // Undefined in the two-phase region, where it is NaN and JSON has no NaN
+    w_m_per_s: Option<f64>,
+}
+
+/// Properties of a `FlashRecord` whose units depend on the basis, tagged with the basis.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "basis", rename_all = "snake_case")]
+enum BasisProperties {
+    Molar(MolarProperties),
+    Mass(MassProperties),
+    MassExceptComposition(MassProperties),
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MolarProperties {
+    D_mol_per_L: f64,
+    Dl_mol_per_L: f64,
+    Dv_mol_per_L: f64,
+    e_J_per_mol: f64,
+    h_J_per_mol: f64,
+    s_J_per_mol_K: f64,
+    Cv_J_per_mol_K: Option<f64>,
+    Cp_J_per_mol_K: Option<f64>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MassProperties {
+    D_kg_per_m3: f64,
+    Dl_kg_per_m3: f64,
+    Dv_kg_per_m3: f64,
+    e_kJ_per_kg: f64,
+    h_kJ_per_kg: f64,
+    s_kJ_per_kg_K: f64,
+    Cv_kJ_per_kg_K: Option<f64>,
+    Cp_kJ_per_kg_K: Option<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl From<FlashOutput> for FlashRecord {
+    fn from(output: FlashOutput) -> Self {
+        let mass = || MassProperties {
+            D_kg_per_m3: output.D,
+            Dl_kg_per_m3: output.Dl,
+            Dv_kg_per_m3: output.Dv,
+            e_kJ_per_kg: output.e,
+            h_kJ_per_kg: output.h,
+            s_kJ_per_kg_K: output.s,
+            Cv_kJ_per_kg_K: output.Cv,
+            Cp_kJ_per_kg_K: output.Cp,
+        };
+        let properties = match output.basis {
+            Basis::Molar => BasisProperties::Molar(MolarProperties {
+                D_mol_per_L: output.D,
+                Dl_mol_per_L: output.Dl,
+                Dv_mol_per_L: output.Dv,
+                e_J_per_mol: output.e,
+                h_J_per_mol: output.h,
+                s_J_per_mol_K: output.s,
+                Cv_J_per_mol_K: output.Cv,
+                Cp_J_per_mol_K: output.Cp,
+            }),
+            Basis::Mass => BasisProperties::Mass(mass()),
+            Basis::MassExceptComposition => BasisProperties::MassExceptComposition(mass()),
+        };
+
+        FlashRecord {
+            T_K: output.T,
+            P_kPa: output.P,
+            properties,
+            x: output.x,
+            y: output.y,
+            q_mol_per_mol: output.q,
+            w_m_per_s: Some(output.w).filter(|w| w.is_finite()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<FlashRecord> for FlashOutput {
+    fn from(record: FlashRecord) -> Self {
+        let (basis, p) = match record.properties {
+            BasisProperties::Molar(p) => {
+                return FlashOutput {
+                    T: record.T_K,
+                    P: record.P_kPa,
+                    D: p.D_mol_per_L,
+                    Dl: p.Dl_mol_per_L,
+                    Dv: p.Dv_mol_per_L,
+                    x: record.x,
+                    y: record.y,
+                    q: record.q_mol_per_mol,
+                    e: p.e_J_per_mol,
+                    h: p.h_J_per_mol,
+                    s: p.s_J_per_mol_K,
+                    Cv: p.Cv_J_per_mol_K,
+                    Cp: p.Cp_J_per_mol_K,
+                    w: record.w_m_per_s.unwrap_or(f64::NAN),
+                    basis: Basis::Molar,
+                }
+            }
+            BasisProperties::Mass(p) => (Basis::Mass, p),
+            BasisProperties::MassExceptComposition(p) => (Basis::MassExceptComposition, p),
+        };
+
+        FlashOutput {
+            T: record.T_K,
+            P: record.P_kPa,
+            D: p.D_kg_per_m3,
+            Dl: p.Dl_kg_per_m3,
+            Dv: p.Dv_kg_per_m3,
+            x: record.x,
+            y: record.y,
+            q: record.q_mol_per_mol,
+            e: p.e_kJ_per_kg,
+            h: p.h_kJ_per_kg,
+            s: p.s_kJ_per_kg_K,
+            Cv: p.Cv_kJ_per_kg_K,
+            Cp: p.Cp_kJ_per_kg_K,
+            w: record.w_m_per_s.unwrap_or(f64::NAN),
+            basis,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn output(basis: Basis, w: f64) -> FlashOutput {
+        FlashOutput {
+            T: 300.0,
+            P: 101.325,
+            D: 0.0407,
+            Dl: 0.0407,
+            Dv: 0.0407,
+            x: vec![1.0],
+            y: vec![1.0],
+            q: 998.0,
+            e: 6200.0,
+            h: 8690.0,
+            s: 191.5,
+            Cv: Some(20.8),
+            Cp: None,
+            w,
+            basis,
+        }
+    }
+
+    #[test]
+    fn test_flash_output_serde() {
+        let output = output(Basis::Molar, 353.0);
+
+        let json = serde_json::to_value(&output).unwrap();
+        for key in [
+            "T_K",
+            "P_kPa",
+            "D_mol_per_L",
+            "q_mol_per_mol",
+            "h_J_per_mol",
+            "s_J_per_mol_K",
+            "Cp_J_per_mol_K",
+            "w_m_per_s",
+        ] {
+            assert!(json.get(key).is_some(), "missing field {}", key);
+        }
+        assert_eq!(json["basis"], "molar");
+
+        let round_trip: FlashOutput = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.h, output.h);
+        assert_eq!(round_trip.Cv, output.Cv);
+        assert!(matches!(round_trip.basis, Basis::Molar));
+    }
+
+    #[test]
+    fn test_flash_output_serde_mass_basis() {
+        let json = serde_json::to_value(output(Basis::MassExceptComposition, f64::NAN)).unwrap();
+        assert_eq!(json["basis"], "mass_except_composition");
+        assert_eq!(json["h_kJ_per_kg"], 8690.0);
+        assert!(json.get("h_J_per_mol").is_none());
+        assert!(json["w_m_per_s"].is_null());
+
+        let round_trip: FlashOutput = serde_json::from_value(json).unwrap();
+        assert!(matches!(round_trip.basis, Basis::MassExceptComposition));
+        assert!(round_trip.w.is_nan());
+    }
+
+    /// Checks the serialized outputs against `schema/flash_output.schema.json`.
+    #[test]
+    fn test_flash_output_schema() {
+        let schema: Value =
+            serde_json::from_str(include_str!("../schema/flash_output.schema.json")).unwrap();
+        let keys =
+            |value: &Value| -> Vec<String> { value.as_object().unwrap().keys().cloned().collect() };
+        let allows = |definition: &Value, value: &Value| match &definition["type"] {
+            Value::String(kind) => kind == "number" && value.is_number() || kind == "array",
+            Value::Array(kinds) => kinds.iter().any(|kind| {
+                kind == "number" && value.is_number() || kind == "null" && value.is_null()
+            }),
+            _ => true,
+        };
+
+        for basis in [Basis::Molar, Basis::Mass, Basis::MassExceptComposition] {
+            let json = serde_json::to_value(output(basis, f64::NAN)).unwrap();
+            let branches: Vec<&Value> = schema["oneOf"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|branch| {
+                    let tag = &branch["properties"]["basis"];
+                    tag["const"] == json["basis"]
+                        || tag["enum"]
+                            .as_array()
+                            .is_some_and(|e| e.contains(&json["basis"]))
+                })
+                .collect();
+            assert_eq!(branches.len(), 1, "basis {}", json["basis"]);
+
+            let properties = [&schema, branches[0]].map(|s| &s["properties"]);
+            for key in keys(&json) {
+                let definition = properties
+                    .iter()
+                    .find_map(|p| p.get(&key))
+                    .unwrap_or_else(|| panic!("field {} is not in the schema", key));
+                assert!(
+                    allows(definition, &json[&key]),
+                    "field {} has the wrong type",
+                    key
+                );
+            }
+            for required in [&schema, branches[0]].map(|s| s["required"].as_array().unwrap()) {
+                for key in required {
+                    assert!(
+                        json.get(key.as_str().unwrap()).is_some(),
+                        "missing field {}",
+                        key
+                    );
+                }
+            }
+        }
+    }
 }
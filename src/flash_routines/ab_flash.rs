@@ -2,7 +2,8 @@ use std::ffi::CString;
 
 use crate::{
     bindings,
-    utils::{acquire_lock, check_refprop_error, validate_composition},
+    composition::CompositionInput,
+    utils::{acquire_lock, check_refprop_error, validate_composition_unlocked},
     Basis, KrKqFlag, Phase, RefpropError, RefpropFunctionLibrary, CP_UNDEFINED, CV_UNDEFINED,
 };
 
@@ -30,7 +31,9 @@ impl RefpropFunctionLibrary {
     ///
     /// - `a`: The value of the first property specified in `ab`.
     /// - `b`: The value of the second property specified in `ab`.
-    /// - `z`: A slice containing the overall composition (mole fractions). Maximum of 20 components.
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    ///         With `Basis::Mass`, a slice is read as mole fractions and converted to mass fractions (earlier
+    ///         versions sent it to REFPROP unchanged); use `Composition::mass` to pass mass fractions.
     ///         For saturation properties (`"TQ"` or `"PQ"`), send `b = -99` for melting line states and `b = -98` for sublimation line states.
     /// - `flags`: An `AbfleshFlags` struct specifying the combined flags for the calculation.
     ///
@@ -89,7 +92,7 @@ impl RefpropFunctionLibrary {
         ab: &str,
         a: f64,
         b: f64,
-        z: &[f64],
+        z: impl Into<CompositionInput<'_>>,
         imass_flag: Basis,
         kph_flag: Phase,
         krkq_flag: KrKqFlag,
@@ -113,12 +116,14 @@ impl RefpropFunctionLibrary {
             }
         }
 
-        // Validate composition slice length
-        validate_composition(z)?;
-
         // Acquire the mutex lock to ensure exclusive access
         let lock = acquire_lock()?;
 
+        // Resolve the composition on the expected basis and validate it
+        let input = z.into();
+        let z = input.fractions_for(imass_flag)?;
+        validate_composition_unlocked(&lock, &z)?;
+
         // Convert 'ab' to CString, ensuring no null bytes
         let c_ab = CString::new(ab_upper.as_str())
             .map_err(|e| RefpropError::InvalidInput(format!("ab contains null byte: {}", e)))?;
@@ -243,6 +248,7 @@ impl RefpropFunctionLibrary {
             Cv,
             Cp,
             w,
+            basis: imass_flag,
         };
 
         Ok(output)
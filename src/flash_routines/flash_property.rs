@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use crate::{flash_routines::FlashOutput, units::Quantity, RefpropError};
+
+/// Represents a scalar property of a `FlashOutput`, in the default molar units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlashProperty {
+    /// Temperature [K] (`T`).
+    Temperature,
+    /// Pressure [kPa] (`P`).
+    Pressure,
+    /// Bulk density [mol/L] (`D`).
+    Density,
+    /// Density of the liquid phase [mol/L] (`Dl`).
+    LiquidDensity,
+    /// Density of the vapor phase [mol/L] (`Dv`).
+    VaporDensity,
+    /// Vapor quality on a molar basis [mol/mol] (`q`).
+    Quality,
+    /// Internal energy [J/mol] (`e`).
+    InternalEnergy,
+    /// Enthalpy [J/mol] (`h`).
+    Enthalpy,
+    /// Entropy [J/mol-K] (`s`).
+    Entropy,
+    /// Isochoric heat capacity [J/mol-K] (`Cv`).
+    Cv,
+    /// Isobaric heat capacity [J/mol-K] (`Cp`).
+    Cp,
+    /// Speed of sound [m/s] (`w`).
+    SpeedOfSound,
+}
+
+impl FlashProperty {
+    /// All properties, in the order of the fields of `FlashOutput`.
+    pub const ALL: [FlashProperty; 12] = [
+        FlashProperty::Temperature,
+        FlashProperty::Pressure,
+        FlashProperty::Density,
+        FlashProperty::LiquidDensity,
+        FlashProperty::VaporDensity,
+        FlashProperty::Quality,
+        FlashProperty::InternalEnergy,
+        FlashProperty::Enthalpy,
+        FlashProperty::Entropy,
+        FlashProperty::Cv,
+        FlashProperty::Cp,
+        FlashProperty::SpeedOfSound,
+    ];
+
+    /// Returns the name of the corresponding `FlashOutput` field (e.g., `"Dl"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlashProperty::Temperature => "T",
+            FlashProperty::Pressure => "P",
+            FlashProperty::Density => "D",
+            FlashProperty::LiquidDensity => "Dl",
+            FlashProperty::VaporDensity => "Dv",
+            FlashProperty::Quality => "q",
+            FlashProperty::InternalEnergy => "e",
+            FlashProperty::Enthalpy => "h",
+            FlashProperty::Entropy => "s",
+            FlashProperty::Cv => "Cv",
+            FlashProperty::Cp => "Cp",
+            FlashProperty::SpeedOfSound => "w",
+        }
+    }
+
+    /// Returns the physical quantity of the property.
+    pub fn quantity(&self) -> Quantity {
+        match self {
+            FlashProperty::Temperature => Quantity::Temperature,
+            FlashProperty::Pressure => Quantity::Pressure,
+            FlashProperty::Density | FlashProperty::LiquidDensity | FlashProperty::VaporDensity => {
+                Quantity::Density
+            }
+            FlashProperty::Quality => Quantity::Quality,
+            FlashProperty::InternalEnergy | FlashProperty::Enthalpy => Quantity::Energy,
+            FlashProperty::Entropy | FlashProperty::Cv | FlashProperty::Cp => Quantity::Entropy,
+            FlashProperty::SpeedOfSound => Quantity::Speed,
+        }
+    }
+
+    /// Returns the value of the property in `output`, or NaN if it is undefined (`Cv` and `Cp` of two-phase states).
+    pub fn value(&self, output: &FlashOutput) -> f64 {
+        match self {
+            FlashProperty::Temperature => output.T,
+            FlashProperty::Pressure => output.P,
+            FlashProperty::Density => output.D,
+            FlashProperty::LiquidDensity => output.Dl,
+            FlashProperty::VaporDensity => output.Dv,
+            FlashProperty::Quality => output.q,
+            FlashProperty::InternalEnergy => output.e,
+            FlashProperty::Enthalpy => output.h,
+            FlashProperty::Entropy => output.s,
+            FlashProperty::Cv => output.Cv.unwrap_or(f64::NAN),
+            FlashProperty::Cp => output.Cp.unwrap_or(f64::NAN),
+            FlashProperty::SpeedOfSound => output.w,
+        }
+    }
+}
+
+impl FromStr for FlashProperty {
+    type Err = RefpropError;
+
+    /// Parses a property from the name of its `FlashOutput` field, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        FlashProperty::ALL
+            .into_iter()
+            .find(|property| property.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| RefpropError::InvalidInput(format!("Unknown flash property: {}", s)))
+    }
+}
@@ -1,8 +1,9 @@
 use crate::{
     bindings,
+    composition::CompositionInput,
     flash_routines::FlashOutput,
-    utils::{acquire_lock, check_refprop_error, validate_composition},
-    RefpropError, RefpropFunctionLibrary, CP_UNDEFINED, CV_UNDEFINED,
+    utils::{acquire_lock, check_refprop_error, validate_composition_unlocked},
+    Basis, RefpropError, RefpropFunctionLibrary, CP_UNDEFINED, CV_UNDEFINED,
 };
 
 impl RefpropFunctionLibrary {
@@ -16,7 +17,7 @@ impl RefpropFunctionLibrary {
     ///
     /// - `h`: Enthalpy [J/mol]
     /// - `s`: Entropy [J/(mol-K)]
-    /// - `z`: Overall Composition (slice of mole fractions). Maximum of 20 components.
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
     ///
     /// # Returns
     ///
@@ -34,13 +35,19 @@ impl RefpropFunctionLibrary {
     /// # References
     ///
     /// - [REFPROP Documentation - HSFLSHdll](https://pages.nist.gov/RefProp/documentation.html)
-    pub fn hs_flash(h: f64, s: f64, z: &[f64]) -> Result<FlashOutput, RefpropError> {
-        // Validate composition slice length
-        validate_composition(z)?;
-
+    pub fn hs_flash(
+        h: f64,
+        s: f64,
+        z: impl Into<CompositionInput<'_>>,
+    ) -> Result<FlashOutput, RefpropError> {
         // Acquire the mutex lock to ensure exclusive access
         let _lock = acquire_lock()?;
 
+        // Resolve the composition on the expected basis and validate it
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        validate_composition_unlocked(&_lock, &z)?;
+
         // Convert 'z' slice to a fixed-size array with padding
         let mut z_buffer = [0.0f64; 20];
         for (i, &val) in z.iter().enumerate() {
@@ -137,6 +144,7 @@ impl RefpropFunctionLibrary {
             Cv,
             Cp,
             w,
+            basis: Basis::Molar,
         };
 
         Ok(output)
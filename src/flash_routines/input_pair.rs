@@ -0,0 +1,177 @@
+use std::str::FromStr;
+
+use crate::{
+    composition::CompositionInput, flash_routines::FlashOutput, units::Quantity, Basis, KrKqFlag,
+    Phase, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Represents the pair of independent properties given to a flash calculation.
+///
+/// All properties are on a molar basis in the default units: temperature [K], pressure [kPa],
+/// density [mol/L], internal energy and enthalpy [J/mol], entropy [J/mol-K], and molar quality [mol/mol].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputPair {
+    /// Temperature and pressure (`tp_flash`).
+    TP,
+    /// Pressure and enthalpy (`ph_flash`).
+    PH,
+    /// Pressure and entropy (`ps_flash`).
+    PS,
+    /// Pressure and density (`pd_flash`).
+    PD,
+    /// Pressure and internal energy (`pe_flash`).
+    PE,
+    /// Temperature and density (`td_flash`).
+    TD,
+    /// Density and enthalpy (`dh_flash`).
+    DH,
+    /// Density and entropy (`ds_flash`).
+    DS,
+    /// Density and internal energy (`de_flash`).
+    DE,
+    /// Enthalpy and entropy (`hs_flash`).
+    HS,
+    /// Temperature and molar quality (`tq_flash`).
+    TQ,
+    /// Pressure and molar quality (`pq_flash`).
+    PQ,
+}
+
+impl InputPair {
+    /// All input pairs, in declaration order.
+    pub const ALL: [InputPair; 12] = [
+        InputPair::TP,
+        InputPair::PH,
+        InputPair::PS,
+        InputPair::PD,
+        InputPair::PE,
+        InputPair::TD,
+        InputPair::DH,
+        InputPair::DS,
+        InputPair::DE,
+        InputPair::HS,
+        InputPair::TQ,
+        InputPair::PQ,
+    ];
+
+    /// Returns the two-letter identifier of the pair (e.g., `"TP"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InputPair::TP => "TP",
+            InputPair::PH => "PH",
+            InputPair::PS => "PS",
+            InputPair::PD => "PD",
+            InputPair::PE => "PE",
+            InputPair::TD => "TD",
+            InputPair::DH => "DH",
+            InputPair::DS => "DS",
+            InputPair::DE => "DE",
+            InputPair::HS => "HS",
+            InputPair::TQ => "TQ",
+            InputPair::PQ => "PQ",
+        }
+    }
+
+    /// Returns the physical quantities of the first and second inputs.
+    pub fn quantities(&self) -> (Quantity, Quantity) {
+        match self {
+            InputPair::TP => (Quantity::Temperature, Quantity::Pressure),
+            InputPair::PH | InputPair::PE => (Quantity::Pressure, Quantity::Energy),
+            InputPair::PS => (Quantity::Pressure, Quantity::Entropy),
+            InputPair::PD => (Quantity::Pressure, Quantity::Density),
+            InputPair::TD => (Quantity::Temperature, Quantity::Density),
+            InputPair::DH | InputPair::DE => (Quantity::Density, Quantity::Energy),
+            InputPair::DS => (Quantity::Density, Quantity::Entropy),
+            InputPair::HS => (Quantity::Energy, Quantity::Entropy),
+            InputPair::TQ => (Quantity::Temperature, Quantity::Quality),
+            InputPair::PQ => (Quantity::Pressure, Quantity::Quality),
+        }
+    }
+}
+
+impl FromStr for InputPair {
+    type Err = RefpropError;
+
+    /// Parses an input pair from its two-letter identifier, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_uppercase();
+        InputPair::ALL
+            .into_iter()
+            .find(|pair| pair.as_str() == name)
+            .ok_or_else(|| RefpropError::InvalidInput(format!("Unknown input pair: {}", s)))
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Performs a flash calculation for any supported pair of independent properties.
+    ///
+    /// Dispatches to the flash routine corresponding to `pair`. The quality pairs (`TQ` and `PQ`) are
+    /// evaluated on a molar basis with the phase determined by REFPROP.
+    ///
+    /// # Parameters
+    ///
+    /// - `pair`: The pair of independent properties.
+    /// - `a`: The first property of the pair, in the default molar units.
+    /// - `b`: The second property of the pair, in the default molar units.
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `FlashOutput`: A struct containing the calculated properties.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of the underlying flash routine.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{InputPair, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let pair: InputPair = "tp".parse()?;
+    ///     let output = RefpropFunctionLibrary::flash(pair, 300.0, 101.325, &z)?;
+    ///     println!("Enthalpy: {} J/mol", output.h);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn flash(
+        pair: InputPair,
+        a: f64,
+        b: f64,
+        z: impl Into<CompositionInput<'_>>,
+    ) -> Result<FlashOutput, RefpropError> {
+        match pair {
+            InputPair::TP => Self::tp_flash(a, b, z),
+            InputPair::PH => Self::ph_flash(a, b, z),
+            InputPair::PS => Self::ps_flash(a, b, z),
+            InputPair::PD => Self::pd_flash(a, b, z),
+            InputPair::PE => Self::pe_flash(a, b, z),
+            InputPair::TD => Self::td_flash(a, b, z),
+            InputPair::DH => Self::dh_flash(a, b, z),
+            InputPair::DS => Self::ds_flash(a, b, z),
+            InputPair::DE => Self::de_flash(a, b, z),
+            InputPair::HS => Self::hs_flash(a, b, z),
+            InputPair::TQ => Self::tq_flash(
+                a,
+                b,
+                z,
+                Basis::Molar,
+                Phase::Unknown,
+                KrKqFlag::QualityMolar,
+            ),
+            InputPair::PQ => Self::pq_flash(
+                a,
+                b,
+                z,
+                Basis::Molar,
+                Phase::Unknown,
+                KrKqFlag::QualityMolar,
+            ),
+        }
+    }
+}
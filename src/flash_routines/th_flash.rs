@@ -1,8 +1,9 @@
 use crate::{
     bindings,
+    composition::CompositionInput,
     flash_routines::FlashOutput,
-    utils::{acquire_lock, check_refprop_error, validate_composition},
-    RefpropError, RefpropFunctionLibrary, CP_UNDEFINED, CV_UNDEFINED,
+    utils::{acquire_lock, check_refprop_error, validate_composition_unlocked},
+    Basis, RefpropError, RefpropFunctionLibrary, CP_UNDEFINED, CV_UNDEFINED,
 };
 
 impl RefpropFunctionLibrary {
@@ -16,7 +17,7 @@ impl RefpropFunctionLibrary {
     ///
     /// - `T`: Temperature [K]
     /// - `h`: Enthalpy [J/mol]
-    /// - `z`: Bulk Composition (slice of mole fractions). Maximum of 20 components.
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
     /// - `kr`: Integer flag. Typically set to `1`. Set to `2` to obtain a single-phase state if multiple solutions exist.
     ///
     /// # Returns
@@ -35,10 +36,12 @@ impl RefpropFunctionLibrary {
     /// # References
     ///
     /// - [REFPROP Documentation - THFLSHdll](https://pages.nist.gov/RefProp/documentation.html)
-    pub fn th_flash(T: f64, h: f64, z: &[f64], kr: i32) -> Result<FlashOutput, RefpropError> {
-        // Validate composition slice length
-        validate_composition(z)?;
-
+    pub fn th_flash(
+        T: f64,
+        h: f64,
+        z: impl Into<CompositionInput<'_>>,
+        kr: i32,
+    ) -> Result<FlashOutput, RefpropError> {
         // Validate kr (assuming valid values are 1 or 2 based on documentation)
         if kr != 1 && kr != 2 {
             return Err(RefpropError::InvalidInput(
@@ -49,6 +52,11 @@ impl RefpropFunctionLibrary {
         // Acquire the mutex lock to ensure exclusive access
         let _lock = acquire_lock()?;
 
+        // Resolve the composition on the expected basis and validate it
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        validate_composition_unlocked(&_lock, &z)?;
+
         // Convert 'z' slice to a fixed-size array with padding
         let mut z_buffer = [0.0f64; 20];
         for (i, &val) in z.iter().enumerate() {
@@ -147,6 +155,7 @@ impl RefpropFunctionLibrary {
             Cv,
             Cp,
             w,
+            basis: Basis::Molar,
         };
 
         Ok(output)
@@ -1,7 +1,8 @@
 use crate::{
     bindings,
+    composition::CompositionInput,
     flash_routines::FlashOutput,
-    utils::{acquire_lock, check_refprop_error, validate_composition},
+    utils::{acquire_lock, check_refprop_error, validate_composition_unlocked},
     Basis, KrKqFlag, Phase, RefpropError, RefpropFunctionLibrary, CP_UNDEFINED, CV_UNDEFINED,
 };
 
@@ -16,7 +17,9 @@ impl RefpropFunctionLibrary {
     ///
     /// - `T`: Temperature [K].
     /// - `q`: Vapor quality [mol/mol].
-    /// - `z`: A slice containing the bulk composition (mole fractions). Maximum of 20 components.
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    ///   With `Basis::Mass`, a slice is read as mole fractions and converted to mass fractions (earlier
+    ///   versions sent it to REFPROP unchanged); use `Composition::mass` to pass mass fractions.
     /// - `kq`: A `KqFlag` enum specifying the behavior of the flash calculation.
     ///
     /// # Returns
@@ -37,17 +40,19 @@ impl RefpropFunctionLibrary {
     pub fn tq_flash(
         T: f64,
         q: f64,
-        z: &[f64],
+        z: impl Into<CompositionInput<'_>>,
         imass_flag: Basis,
         kph_flag: Phase,
         krkq_flag: KrKqFlag,
     ) -> Result<FlashOutput, RefpropError> {
-        // Validate composition slice length
-        validate_composition(z)?;
-
         // Acquire the mutex lock to ensure exclusive access
         let _lock = acquire_lock()?;
 
+        // Resolve the composition on the expected basis and validate it
+        let input = z.into();
+        let z = input.fractions_for(imass_flag)?;
+        validate_composition_unlocked(&_lock, &z)?;
+
         // Convert 'z' slice to a fixed-size array with padding
         let mut z_buffer = [0.0f64; 20];
         for (i, &val) in z.iter().enumerate() {
@@ -152,6 +157,7 @@ impl RefpropFunctionLibrary {
             Cv,
             Cp,
             w,
+            basis: imass_flag,
             q,
         };
 
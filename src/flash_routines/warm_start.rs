@@ -0,0 +1,881 @@
+use std::ffi::CString;
+
+use crate::{
+    bindings,
+    composition::CompositionInput,
+    flash_routines::FlashOutput,
+    lock::RefpropGuard,
+    utils::{acquire_lock, check_refprop_error, padded_composition},
+    Basis, Phase, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Quality reported by REFPROP for subcooled liquid states.
+const Q_LIQUID: f64 = -998.0;
+/// Quality reported by REFPROP for superheated vapor states.
+const Q_VAPOR: f64 = 998.0;
+
+// Define buffer sizes as per REFPROP's documentation
+const AB_LENGTH: usize = 2;
+const HERR_LENGTH: usize = 255;
+
+/// Returns the quality flag of a single-phase state in the given phase.
+fn single_phase_quality(phase: Phase) -> Result<f64, RefpropError> {
+    match phase {
+        Phase::Liquid => Ok(Q_LIQUID),
+        Phase::Vapor => Ok(Q_VAPOR),
+        _ => Err(RefpropError::InvalidInput(
+            "Single-phase routines require Phase::Liquid or Phase::Vapor".to_string(),
+        )),
+    }
+}
+
+/// Returns the quality flag of a previous single-phase state, or NaN if it is unknown.
+fn previous_quality(previous: Option<&FlashOutput>) -> f64 {
+    match previous {
+        Some(output) if output.q <= 0.0 => Q_LIQUID,
+        Some(output) if output.q >= 1.0 => Q_VAPOR,
+        _ => f64::NAN,
+    }
+}
+
+/// Validates an input pair and converts it to the buffer expected by REFPROP.
+fn ab_buffer(ab: &str) -> Result<CString, RefpropError> {
+    let ab_upper = ab.to_uppercase();
+    if ab_upper.len() != 2 || !ab_upper.chars().all(|ch| "TPDEHS".contains(ch)) {
+        return Err(RefpropError::InvalidInput(format!(
+            "Invalid single- or two-phase input pair '{}'",
+            ab
+        )));
+    }
+    CString::new(ab_upper)
+        .map_err(|e| RefpropError::InvalidInput(format!("ab contains null byte: {}", e)))
+}
+
+/// Thermodynamic properties at a given temperature and density (`THERMdll`).
+struct Therm {
+    P: f64,
+    e: f64,
+    h: f64,
+    s: f64,
+    Cv: f64,
+    Cp: f64,
+    w: f64,
+}
+
+fn therm(_guard: &RefpropGuard, T: f64, D: f64, z: &[f64; 20]) -> Therm {
+    let mut T = T;
+    let mut D = D;
+    let mut z = *z;
+    let mut props = Therm {
+        P: 0.0,
+        e: 0.0,
+        h: 0.0,
+        s: 0.0,
+        Cv: 0.0,
+        Cp: 0.0,
+        w: 0.0,
+    };
+    let mut hjt: f64 = 0.0;
+
+    // Call THERMdll within an unsafe block
+    unsafe {
+        bindings::THERMdll(
+            &mut T as *mut f64,
+            &mut D as *mut f64,
+            z.as_mut_ptr(),
+            &mut props.P as *mut f64,
+            &mut props.e as *mut f64,
+            &mut props.h as *mut f64,
+            &mut props.s as *mut f64,
+            &mut props.Cv as *mut f64,
+            &mut props.Cp as *mut f64,
+            &mut props.w as *mut f64,
+            &mut hjt as *mut f64,
+        );
+    }
+
+    props
+}
+
+/// Completes the output of a single-phase routine from its temperature and density.
+fn single_phase_output(
+    guard: &RefpropGuard,
+    T: f64,
+    D: f64,
+    z: &[f64; 20],
+    ncomp: usize,
+    q: f64,
+) -> FlashOutput {
+    let props = therm(guard, T, D, z);
+    FlashOutput {
+        T,
+        P: props.P,
+        D,
+        Dl: D,
+        Dv: D,
+        x: z[..ncomp].to_vec(),
+        y: z[..ncomp].to_vec(),
+        q,
+        e: props.e,
+        h: props.h,
+        s: props.s,
+        Cv: Some(props.Cv),
+        Cp: Some(props.Cp),
+        w: props.w,
+        basis: Basis::Molar,
+    }
+}
+
+/// Completes the output of a two-phase routine from the states of the saturated phases.
+#[allow(clippy::too_many_arguments)]
+fn two_phase_output(
+    guard: &RefpropGuard,
+    T: f64,
+    P: f64,
+    Dl: f64,
+    Dv: f64,
+    x: &[f64; 20],
+    y: &[f64; 20],
+    ncomp: usize,
+    q: f64,
+) -> FlashOutput {
+    let liquid = therm(guard, T, Dl, x);
+    let vapor = therm(guard, T, Dv, y);
+    let mix = |l: f64, v: f64| (1.0 - q) * l + q * v;
+
+    FlashOutput {
+        T,
+        P,
+        D: 1.0 / ((1.0 - q) / Dl + q / Dv),
+        Dl,
+        Dv,
+        x: x[..ncomp].to_vec(),
+        y: y[..ncomp].to_vec(),
+        q,
+        e: mix(liquid.e, vapor.e),
+        h: mix(liquid.h, vapor.h),
+        s: mix(liquid.s, vapor.s),
+        Cv: None,
+        Cp: None,
+        w: f64::NAN,
+        basis: Basis::Molar,
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Calculates the density of a single phase at a given temperature and pressure using the `TPRHOdll` function.
+    ///
+    /// Unlike `tp_flash`, no phase equilibrium is checked. The density of `previous` is sent to REFPROP
+    /// as an initial guess, which makes repeated calls along a slowly changing path much faster.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `P`: Pressure [kPa].
+    /// - `z`: Composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    /// - `phase`: `Phase::Liquid` or `Phase::Vapor`, the root to return.
+    /// - `previous`: Optional nearby state whose density is used as an initial guess.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Density [mol/L].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition or phase is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not converge.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{Phase, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     RefpropFunctionLibrary::set_fluids("WATER")?;
+    ///
+    ///     let state = RefpropFunctionLibrary::tp_flash(300.0, 101.325, &[1.0])?;
+    ///     let D = RefpropFunctionLibrary::tp_rho(300.1, 101.325, &[1.0], Phase::Liquid, Some(&state))?;
+    ///     println!("Density: {} mol/L", D);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn tp_rho(
+        T: f64,
+        P: f64,
+        z: impl Into<CompositionInput<'_>>,
+        phase: Phase,
+        previous: Option<&FlashOutput>,
+    ) -> Result<f64, RefpropError> {
+        single_phase_quality(phase)?;
+
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        let mut z_buffer = padded_composition(&lock, &z)?;
+
+        let mut T = T;
+        let mut P = P;
+        let mut kph = phase as i32;
+        let mut kguess: i32 = previous.is_some() as i32;
+        let mut D = previous.map_or(0.0, |output| output.D);
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call TPRHOdll within an unsafe block
+        unsafe {
+            bindings::TPRHOdll(
+                &mut T as *mut f64,
+                &mut P as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut kph as *mut i32,
+                &mut kguess as *mut i32,
+                &mut D as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        Ok(D)
+    }
+
+    /// Performs a single-phase flash calculation given pressure and enthalpy using the `PHFL1dll` function.
+    ///
+    /// Unlike `ph_flash`, no phase equilibrium is checked, and the temperature and density of
+    /// `previous` are sent to REFPROP as initial guesses. The remaining properties are evaluated with
+    /// `THERMdll` at the resulting temperature and density.
+    ///
+    /// # Parameters
+    ///
+    /// - `P`: Pressure [kPa].
+    /// - `h`: Enthalpy [J/mol].
+    /// - `z`: Composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    /// - `phase`: `Phase::Liquid` or `Phase::Vapor`.
+    /// - `previous`: Optional nearby state used as an initial guess.
+    ///
+    /// # Returns
+    ///
+    /// - `FlashOutput`: The single-phase state, with `q` set to -998 (liquid) or 998 (vapor).
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition or phase is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not converge.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{Phase, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     RefpropFunctionLibrary::set_fluids("WATER")?;
+    ///
+    ///     // Step the enthalpy of a subcooled liquid in small increments
+    ///     let mut state = RefpropFunctionLibrary::tp_flash(300.0, 500.0, &[1.0])?;
+    ///     for _ in 0..10 {
+    ///         state = RefpropFunctionLibrary::ph_flash1(500.0, state.h + 10.0, &[1.0], Phase::Liquid, Some(&state))?;
+    ///     }
+    ///     println!("Temperature: {} K", state.T);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn ph_flash1(
+        P: f64,
+        h: f64,
+        z: impl Into<CompositionInput<'_>>,
+        phase: Phase,
+        previous: Option<&FlashOutput>,
+    ) -> Result<FlashOutput, RefpropError> {
+        Self::single_phase_flash1(true, P, h, z, phase, previous)
+    }
+
+    /// Performs a single-phase flash calculation given pressure and entropy using the `PSFL1dll` function.
+    ///
+    /// Unlike `ps_flash`, no phase equilibrium is checked, and the temperature and density of
+    /// `previous` are sent to REFPROP as initial guesses. The remaining properties are evaluated with
+    /// `THERMdll` at the resulting temperature and density.
+    ///
+    /// # Parameters
+    ///
+    /// - `P`: Pressure [kPa].
+    /// - `s`: Entropy [J/mol-K].
+    /// - `z`: Composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    /// - `phase`: `Phase::Liquid` or `Phase::Vapor`.
+    /// - `previous`: Optional nearby state used as an initial guess.
+    ///
+    /// # Returns
+    ///
+    /// - `FlashOutput`: The single-phase state, with `q` set to -998 (liquid) or 998 (vapor).
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition or phase is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not converge.
+    pub fn ps_flash1(
+        P: f64,
+        s: f64,
+        z: impl Into<CompositionInput<'_>>,
+        phase: Phase,
+        previous: Option<&FlashOutput>,
+    ) -> Result<FlashOutput, RefpropError> {
+        Self::single_phase_flash1(false, P, s, z, phase, previous)
+    }
+
+    /// Shared implementation of `ph_flash1` (`enthalpy = true`) and `ps_flash1`.
+    fn single_phase_flash1(
+        enthalpy: bool,
+        P: f64,
+        b: f64,
+        z: impl Into<CompositionInput<'_>>,
+        phase: Phase,
+        previous: Option<&FlashOutput>,
+    ) -> Result<FlashOutput, RefpropError> {
+        let q = single_phase_quality(phase)?;
+
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        let ncomp = z.len();
+        let mut z_buffer = padded_composition(&lock, &z)?;
+
+        let mut P = P;
+        let mut b = b;
+        let mut kph = phase as i32;
+        let mut T = previous.map_or(0.0, |output| output.T);
+        let mut D = previous.map_or(0.0, |output| output.D);
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call PHFL1dll or PSFL1dll within an unsafe block
+        let routine = if enthalpy {
+            bindings::PHFL1dll
+        } else {
+            bindings::PSFL1dll
+        };
+        unsafe {
+            routine(
+                &mut P as *mut f64,
+                &mut b as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut kph as *mut i32,
+                &mut T as *mut f64,
+                &mut D as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        Ok(single_phase_output(&lock, T, D, &z_buffer, ncomp, q))
+    }
+
+    /// Performs a single-phase flash calculation given density and enthalpy using the `DHFL1dll` function.
+    ///
+    /// Unlike `dh_flash`, no phase equilibrium is checked, and the temperature of `previous` is sent to
+    /// REFPROP as an initial guess. The remaining properties are evaluated with `THERMdll`.
+    ///
+    /// # Parameters
+    ///
+    /// - `D`: Density [mol/L].
+    /// - `h`: Enthalpy [J/mol].
+    /// - `z`: Composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    /// - `previous`: Optional nearby single-phase state used as an initial guess.
+    ///
+    /// # Returns
+    ///
+    /// - `FlashOutput`: The single-phase state, with `q` taken from the phase of `previous`
+    ///   (-998 for liquid, 998 for vapor), or NaN if unknown.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not converge.
+    pub fn dh_flash1(
+        D: f64,
+        h: f64,
+        z: impl Into<CompositionInput<'_>>,
+        previous: Option<&FlashOutput>,
+    ) -> Result<FlashOutput, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        let ncomp = z.len();
+        let mut z_buffer = padded_composition(&lock, &z)?;
+
+        let mut D = D;
+        let mut h = h;
+        let mut T = previous.map_or(0.0, |output| output.T);
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call DHFL1dll within an unsafe block
+        unsafe {
+            bindings::DHFL1dll(
+                &mut D as *mut f64,
+                &mut h as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut T as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        let q = previous_quality(previous);
+        Ok(single_phase_output(&lock, T, D, &z_buffer, ncomp, q))
+    }
+
+    /// Performs a single-phase flash calculation for a general input pair using the `ABFL1dll` function.
+    ///
+    /// Unlike `ab_flash`, no phase equilibrium is checked, and the temperature, pressure, and density
+    /// of `previous` are sent to REFPROP as initial guesses. The remaining properties are evaluated
+    /// with `THERMdll`.
+    ///
+    /// # Parameters
+    ///
+    /// - `ab`: Two-character input pair made of `T`, `P`, `D`, `E`, `H`, and `S` (e.g., "PH").
+    /// - `a`: The first property of the pair, in the units of `basis`.
+    /// - `b`: The second property of the pair, in the units of `basis`.
+    /// - `z`: Composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    /// - `basis`: The basis of the inputs and outputs, as for `ab_flash`.
+    /// - `previous`: Optional nearby single-phase state used as an initial guess.
+    ///
+    /// # Returns
+    ///
+    /// - `FlashOutput`: The single-phase state on `basis`, with `q` taken from the phase of `previous`
+    ///   (-998 for liquid, 998 for vapor), or NaN if unknown.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if `ab` or the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not converge.
+    pub fn ab_flash1(
+        ab: &str,
+        a: f64,
+        b: f64,
+        z: impl Into<CompositionInput<'_>>,
+        basis: Basis,
+        previous: Option<&FlashOutput>,
+    ) -> Result<FlashOutput, RefpropError> {
+        let c_ab = ab_buffer(ab)?;
+
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+
+        let z = z.into();
+        let mole_fractions = z.mole_fractions()?;
+        let ncomp = mole_fractions.len();
+        let molar_mass = Self::calc_molar_mass(&mole_fractions)?;
+        let mole_buffer = padded_composition(&lock, &mole_fractions)?;
+        let mut z_buffer = padded_composition(&lock, &z.fractions_for(basis)?)?;
+
+        // Densities are sent and returned on the basis of the inputs
+        let scale = match basis {
+            Basis::Molar => 1.0,
+            Basis::Mass | Basis::MassExceptComposition => molar_mass,
+        };
+        let previous_D = previous.map_or(0.0, |output| match output.basis {
+            Basis::Molar => output.D * scale,
+            Basis::Mass | Basis::MassExceptComposition => output.D / molar_mass * scale,
+        });
+
+        let mut a = a;
+        let mut b = b;
+        let mut iflag = basis as i32;
+        let mut T = previous.map_or(0.0, |output| output.T);
+        let mut P = previous.map_or(0.0, |output| output.P);
+        let mut D = previous_D;
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call ABFL1dll within an unsafe block
+        unsafe {
+            bindings::ABFL1dll(
+                c_ab.as_ptr() as *mut libc::c_char,
+                &mut a as *mut f64,
+                &mut b as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut iflag as *mut i32,
+                &mut T as *mut f64,
+                &mut P as *mut f64,
+                &mut D as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                AB_LENGTH as i32,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        let q = previous_quality(previous);
+        let output = single_phase_output(&lock, T, D / scale, &mole_buffer, ncomp, q);
+        Ok(match basis {
+            Basis::Molar => output,
+            Basis::Mass | Basis::MassExceptComposition => {
+                let mass_basis = |value: f64| value / molar_mass;
+                let fractions = if matches!(basis, Basis::Mass) {
+                    z_buffer[..ncomp].to_vec()
+                } else {
+                    output.x.clone()
+                };
+                FlashOutput {
+                    D,
+                    Dl: D,
+                    Dv: D,
+                    x: fractions.clone(),
+                    y: fractions,
+                    e: mass_basis(output.e),
+                    h: mass_basis(output.h),
+                    s: mass_basis(output.s),
+                    Cv: output.Cv.map(mass_basis),
+                    Cp: output.Cp.map(mass_basis),
+                    basis,
+                    ..output
+                }
+            }
+        })
+    }
+
+    /// Performs a two-phase flash calculation for a general input pair using the `ABFL2dll` function.
+    ///
+    /// The state must lie in the two-phase region: the stability analysis of `ab_flash` is skipped.
+    /// If `previous` is a two-phase state of a pure fluid at the same temperature or pressure as the
+    /// inputs, its saturated phases are sent to REFPROP as the bubble and dew point bounds
+    /// (`ksat = 1`), as when stepping along an isobar or isotherm; otherwise REFPROP computes the
+    /// bounds itself. Bulk energy, enthalpy, and entropy are the molar averages of the saturated
+    /// phases evaluated with `THERMdll`; `Cv` and `Cp` are `None` and `w` is NaN.
+    ///
+    /// # Parameters
+    ///
+    /// - `ab`: Two-character input pair made of `T`, `P`, `D`, `E`, `H`, and `S` (e.g., "PH").
+    /// - `a`: The first property of the pair, in molar units.
+    /// - `b`: The second property of the pair, in molar units.
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    /// - `previous`: Optional nearby two-phase state providing the saturation bounds.
+    ///
+    /// # Returns
+    ///
+    /// - `FlashOutput`: The two-phase state.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if `ab` or the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not converge.
+    pub fn ab_flash2(
+        ab: &str,
+        a: f64,
+        b: f64,
+        z: impl Into<CompositionInput<'_>>,
+        previous: Option<&FlashOutput>,
+    ) -> Result<FlashOutput, RefpropError> {
+        let c_ab = ab_buffer(ab)?;
+
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        let ncomp = z.len();
+        let mut z_buffer = padded_composition(&lock, &z)?;
+
+        // Only a pure fluid at the same temperature or pressure shares the saturation state of `previous`
+        let bounds = previous.filter(|output| {
+            ncomp == 1
+                && matches!(output.basis, Basis::Molar)
+                && output.q > 0.0
+                && output.q < 1.0
+                && ab.to_uppercase().chars().zip([a, b]).any(|(ch, value)| {
+                    (ch == 'T' && value == output.T) || (ch == 'P' && value == output.P)
+                })
+        });
+
+        let mut a = a;
+        let mut b = b;
+        let mut kq: i32 = 1;
+        let mut ksat = bounds.is_some() as i32;
+        let (mut Tbub, mut Tdew) = bounds.map_or((0.0, 0.0), |output| (output.T, output.T));
+        let (mut Pbub, mut Pdew) = bounds.map_or((0.0, 0.0), |output| (output.P, output.P));
+        let (mut Dlbub, mut Dvdew) = bounds.map_or((0.0, 0.0), |output| (output.Dl, output.Dv));
+        let mut ybub = [0.0f64; 20];
+        let mut xdew = [0.0f64; 20];
+        if bounds.is_some() {
+            (ybub[0], xdew[0]) = (1.0, 1.0);
+        }
+        let (mut T, mut P, mut Dl, mut Dv, mut q) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        let mut x = [0.0f64; 20];
+        let mut y = [0.0f64; 20];
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call ABFL2dll within an unsafe block
+        unsafe {
+            bindings::ABFL2dll(
+                c_ab.as_ptr() as *mut libc::c_char,
+                &mut a as *mut f64,
+                &mut b as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut kq as *mut i32,
+                &mut ksat as *mut i32,
+                &mut Tbub as *mut f64,
+                &mut Tdew as *mut f64,
+                &mut Pbub as *mut f64,
+                &mut Pdew as *mut f64,
+                &mut Dlbub as *mut f64,
+                &mut Dvdew as *mut f64,
+                ybub.as_mut_ptr(),
+                xdew.as_mut_ptr(),
+                &mut T as *mut f64,
+                &mut P as *mut f64,
+                &mut Dl as *mut f64,
+                &mut Dv as *mut f64,
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+                &mut q as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                AB_LENGTH as i32,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        Ok(two_phase_output(&lock, T, P, Dl, Dv, &x, &y, ncomp, q))
+    }
+
+    /// Performs a two-phase flash calculation given temperature and pressure using the `TPFL2dll` function.
+    ///
+    /// The state must lie in the two-phase region: the stability analysis of `tp_flash` is skipped.
+    /// The phase densities, phase compositions, and quality of a two-phase `previous` are sent to
+    /// REFPROP as initial guesses. Bulk energy, enthalpy, and entropy are the molar averages of the
+    /// saturated phases evaluated with `THERMdll`; `Cv` and `Cp` are `None` and `w` is NaN.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `P`: Pressure [kPa].
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    /// - `previous`: Optional nearby two-phase state used as an initial guess.
+    ///
+    /// # Returns
+    ///
+    /// - `FlashOutput`: The two-phase state.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not converge.
+    pub fn tp_flash2(
+        T: f64,
+        P: f64,
+        z: impl Into<CompositionInput<'_>>,
+        previous: Option<&FlashOutput>,
+    ) -> Result<FlashOutput, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+
+        let input = z.into();
+        let z = input.mole_fractions()?;
+        let ncomp = z.len();
+        let mut z_buffer = padded_composition(&lock, &z)?;
+
+        let guess = previous.filter(|output| {
+            matches!(output.basis, Basis::Molar)
+                && output.q > 0.0
+                && output.q < 1.0
+                && output.x.len() == ncomp
+        });
+
+        let mut T = T;
+        let mut P = P;
+        let (mut Dl, mut Dv, mut q) =
+            guess.map_or((0.0, 0.0, 0.0), |output| (output.Dl, output.Dv, output.q));
+        let mut x = [0.0f64; 20];
+        let mut y = [0.0f64; 20];
+        if let Some(output) = guess {
+            x[..ncomp].copy_from_slice(&output.x);
+            y[..ncomp].copy_from_slice(&output.y);
+        }
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call TPFL2dll within an unsafe block
+        unsafe {
+            bindings::TPFL2dll(
+                &mut T as *mut f64,
+                &mut P as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut Dl as *mut f64,
+                &mut Dv as *mut f64,
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+                &mut q as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        Ok(two_phase_output(&lock, T, P, Dl, Dv, &x, &y, ncomp, q))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputPair, KrKqFlag};
+
+    #[test]
+    fn test_warm_start_flash() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        RefpropFunctionLibrary::set_fluids("WATER")?;
+        let z = [1.0];
+
+        let state = RefpropFunctionLibrary::tp_flash(300.0, 500.0, z)?;
+        let cold = RefpropFunctionLibrary::ph_flash1(500.0, state.h, z, Phase::Liquid, None)?;
+        let warm =
+            RefpropFunctionLibrary::ph_flash1(500.0, state.h, z, Phase::Liquid, Some(&state))?;
+        assert!((cold.T - 300.0).abs() < 1e-6);
+        assert!((warm.T - 300.0).abs() < 1e-6);
+        assert!((warm.D - state.D).abs() < 1e-8);
+
+        let D = RefpropFunctionLibrary::tp_rho(300.0, 500.0, z, Phase::Liquid, Some(&state))?;
+        assert!((D - state.D).abs() < 1e-8);
+
+        Ok(())
+    }
+
+    /// Checks that a warm-start flash found the state of the full flash.
+    fn assert_same_state(warm: &FlashOutput, full: &FlashOutput) {
+        for (value, expected) in [
+            (warm.T, full.T),
+            (warm.P, full.P),
+            (warm.D, full.D),
+            (warm.h, full.h),
+        ] {
+            assert!(
+                (value - expected).abs() <= 1e-6 * expected.abs().max(1.0),
+                "{} != {}",
+                value,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_warm_start_single_phase() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        RefpropFunctionLibrary::set_fluids("WATER")?;
+        let z = [1.0];
+
+        let state = RefpropFunctionLibrary::tp_flash(300.0, 500.0, z)?;
+        let nearby = RefpropFunctionLibrary::tp_flash(301.0, 510.0, z)?;
+        for previous in [None, Some(&nearby)] {
+            let full = RefpropFunctionLibrary::ps_flash(500.0, state.s, z)?;
+            let warm =
+                RefpropFunctionLibrary::ps_flash1(500.0, state.s, z, Phase::Liquid, previous)?;
+            assert_same_state(&warm, &full);
+
+            let full = RefpropFunctionLibrary::dh_flash(state.D, state.h, z)?;
+            let warm = RefpropFunctionLibrary::dh_flash1(state.D, state.h, z, previous)?;
+            assert_same_state(&warm, &full);
+
+            let full = RefpropFunctionLibrary::ph_flash(500.0, state.h, z)?;
+            let warm =
+                RefpropFunctionLibrary::ab_flash1("PH", 500.0, state.h, z, Basis::Molar, previous)?;
+            assert_same_state(&warm, &full);
+        }
+
+        // On a mass basis, inputs and outputs are in kJ/kg and kg/m³
+        let full = RefpropFunctionLibrary::ab_flash(
+            "TP",
+            300.0,
+            500.0,
+            z,
+            Basis::Mass,
+            Phase::Unknown,
+            KrKqFlag::Default,
+        )?;
+        for previous in [None, Some(&nearby)] {
+            let warm =
+                RefpropFunctionLibrary::ab_flash1("PH", 500.0, full.h, z, Basis::Mass, previous)?;
+            assert!(matches!(warm.basis, Basis::Mass));
+            assert_same_state(&warm, &full);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_warm_start_two_phase() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+
+        // A pure fluid along an isobar, where `previous` provides the saturation bounds
+        RefpropFunctionLibrary::set_fluids("WATER")?;
+        let z = [1.0];
+        let previous = RefpropFunctionLibrary::flash(InputPair::PQ, 500.0, 0.5, z)?;
+        let state = RefpropFunctionLibrary::flash(InputPair::PQ, 500.0, 0.3, z)?;
+        let full = RefpropFunctionLibrary::ph_flash(500.0, state.h, z)?;
+        for previous in [None, Some(&previous)] {
+            let warm = RefpropFunctionLibrary::ab_flash2("PH", 500.0, state.h, z, previous)?;
+            assert_same_state(&warm, &full);
+            assert!((warm.q - full.q).abs() < 1e-6);
+        }
+
+        // A mixture inside the dome
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+        let bubble = RefpropFunctionLibrary::flash(InputPair::PQ, 1000.0, 0.0, &z)?;
+        let dew = RefpropFunctionLibrary::flash(InputPair::PQ, 1000.0, 1.0, &z)?;
+        let T = 0.5 * (bubble.T + dew.T);
+        let full = RefpropFunctionLibrary::tp_flash(T, 1000.0, &z)?;
+        let previous = RefpropFunctionLibrary::tp_flash(T - 0.2, 1000.0, &z)?;
+        for previous in [None, Some(&previous)] {
+            let warm = RefpropFunctionLibrary::tp_flash2(T, 1000.0, &z, previous)?;
+            assert_same_state(&warm, &full);
+            assert!((warm.q - full.q).abs() < 1e-6);
+            assert!((warm.x[0] - full.x[0]).abs() < 1e-6);
+
+            let warm = RefpropFunctionLibrary::ab_flash2("TP", T, 1000.0, &z, previous)?;
+            assert_same_state(&warm, &full);
+        }
+
+        Ok(())
+    }
+}
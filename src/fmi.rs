@@ -0,0 +1,935 @@
+//! FMI 2.0 co-simulation interface of a fluid property component, built with the `fmi` feature.
+//!
+//! The component takes pressure and enthalpy as inputs and, at every communication point, computes
+//! temperature, density, quality, and transport properties with `ph_flash` and
+//! `transport_properties` in the default molar units. The fluid is configured with the string
+//! parameters `fluid` (a predefined mixture, a pure fluid, or a list of fluids separated by
+//! semicolons) and `composition` (comma-separated mole fractions, required for a list of fluids).
+//!
+//! The shared library of the component is built with
+//! `cargo rustc --release --lib --features fmi --crate-type cdylib`. The `refprop-fmu` binary
+//! packages it together with the `modelDescription.xml` returned by `model_description` into an
+//! FMU. If the FMU carries the REFPROP fluid files in its `resources` directory, they are used
+//! instead of `RPPREFIX`.
+
+use std::{
+    env,
+    ffi::{c_char, c_int, c_void, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    ptr, slice,
+};
+
+use crate::{
+    catalogue::subdirectory,
+    utils::{fluids_generation, validate_fractions},
+    RefpropError, RefpropFunctionLibrary,
+};
+
+/// Name of the shared library inside the FMU, without platform prefix and extension.
+pub const MODEL_IDENTIFIER: &str = "refprop_sys";
+
+/// GUID identifying the variables of the component in `modelDescription.xml`.
+pub const GUID: &str = "{5d3c9a8e-2f61-4b7a-9e0c-8a41f3b6d2c7}";
+
+type Component = *mut c_void;
+type FmuState = *mut c_void;
+type ValueReference = u32;
+type Boolean = c_int;
+
+/// Status returned by the FMI functions (`fmi2Status`).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0,
+    Warning = 1,
+    Discard = 2,
+    Error = 3,
+    Fatal = 4,
+    Pending = 5,
+}
+
+type Logger =
+    unsafe extern "C" fn(*mut c_void, *const c_char, Status, *const c_char, *const c_char, ...);
+
+/// Callbacks provided by the simulation environment (`fmi2CallbackFunctions`).
+#[repr(C)]
+pub struct CallbackFunctions {
+    logger: Option<Logger>,
+    allocate_memory: Option<unsafe extern "C" fn(usize, usize) -> *mut c_void>,
+    free_memory: Option<unsafe extern "C" fn(*mut c_void)>,
+    step_finished: Option<unsafe extern "C" fn(*mut c_void, Status)>,
+    component_environment: *mut c_void,
+}
+
+/// Real variables of the component, indexed by their value reference.
+const REAL_VARIABLES: [(&str, &str, &str, &str); 7] = [
+    ("P", "input", "kPa", "Pressure"),
+    ("h", "input", "J/mol", "Enthalpy"),
+    ("T", "output", "K", "Temperature"),
+    ("D", "output", "mol/L", "Density"),
+    ("q", "output", "mol/mol", "Vapor quality on a molar basis"),
+    ("eta", "output", "uPa.s", "Dynamic viscosity"),
+    ("tcx", "output", "W/(m.K)", "Thermal conductivity"),
+];
+
+/// Default values of the inputs (P, h).
+const DEFAULT_INPUTS: [f64; 2] = [101.325, 0.0];
+
+/// Value references of the string parameters.
+const FLUID: usize = 0;
+const COMPOSITION: usize = 1;
+
+/// Instance of the fluid property component.
+struct FluidComponent {
+    instance_name: CString,
+    logger: Option<Logger>,
+    environment: *mut c_void,
+    logging_on: bool,
+    reals: [f64; 7],
+    strings: [CString; 2],
+    z: Vec<f64>,
+    /// Fluids generation right after this instance last loaded its fluid
+    loaded: Option<u64>,
+}
+
+impl FluidComponent {
+    fn log(&self, status: Status, category: &CStr, message: &str) {
+        if status == Status::Ok && !self.logging_on {
+            return;
+        }
+        if let Some(logger) = self.logger {
+            let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+            unsafe {
+                logger(
+                    self.environment,
+                    self.instance_name.as_ptr(),
+                    status,
+                    category.as_ptr(),
+                    c"%s".as_ptr(),
+                    message.as_ptr(),
+                );
+            }
+        }
+    }
+
+    /// Loads the configured fluid unless it is still the active one. Must be called while holding the lock.
+    fn activate(&mut self) -> Result<(), RefpropError> {
+        if self.loaded == Some(fluids_generation()) {
+            return Ok(());
+        }
+
+        let fluid = self.strings[FLUID].to_str()?.trim().to_string();
+        let composition = self.strings[COMPOSITION].to_str()?.trim().to_string();
+        self.z = if composition.is_empty() {
+            if fluid.contains(';') {
+                return Err(RefpropError::InvalidInput(
+                    "A list of fluids requires the 'composition' parameter.".to_string(),
+                ));
+            }
+            RefpropFunctionLibrary::set_mixture_or_fluid(&fluid)?
+        } else {
+            let z = parse_composition(&composition)?;
+            validate_fractions(&z)?;
+            RefpropFunctionLibrary::set_fluids(&fluid)?;
+            z
+        };
+
+        self.loaded = Some(fluids_generation());
+        Ok(())
+    }
+
+    /// Computes the outputs from the current inputs.
+    ///
+    /// Calculation failures (e.g. inputs outside the range of the equation of state) set the outputs
+    /// to NaN and return `Status::Warning`; other failures return `Status::Error`.
+    fn evaluate(&mut self) -> Status {
+        let result = RefpropFunctionLibrary::lock().and_then(|_guard| {
+            self.activate()?;
+            let state = RefpropFunctionLibrary::ph_flash(self.reals[0], self.reals[1], &self.z)?;
+
+            // Transport properties are only defined for single-phase states
+            let (eta, tcx) = if state.q > 0.0 && state.q < 1.0 {
+                (f64::NAN, f64::NAN)
+            } else {
+                let transport =
+                    RefpropFunctionLibrary::transport_properties(state.T, state.D, &self.z)?;
+                (transport.eta, transport.tcx)
+            };
+            Ok([state.T, state.D, state.q, eta, tcx])
+        });
+
+        match result {
+            Ok(outputs) => {
+                self.reals[2..].copy_from_slice(&outputs);
+                Status::Ok
+            }
+            Err(error) => {
+                self.reals[2..].fill(f64::NAN);
+                let (status, category) = match error {
+                    RefpropError::CalculationError(_) => (Status::Warning, c"logStatusWarning"),
+                    _ => (Status::Error, c"logStatusError"),
+                };
+                self.log(status, category, &error.to_string());
+                status
+            }
+        }
+    }
+}
+
+/// Parses comma-separated mole fractions.
+fn parse_composition(composition: &str) -> Result<Vec<f64>, RefpropError> {
+    composition
+        .split(',')
+        .map(|fraction| {
+            fraction.trim().parse::<f64>().map_err(|_| {
+                RefpropError::InvalidInput(format!("Invalid mole fraction '{}'.", fraction.trim()))
+            })
+        })
+        .collect()
+}
+
+/// Returns the directory behind a `file:` resource URI if it bundles REFPROP fluid files.
+///
+/// A directory with a `FLUIDS` subdirectory (ignoring case) is returned even if it lacks other files,
+/// so that `set_path` checks it and reports what is missing.
+fn fluid_directory(resource_location: &str) -> Option<PathBuf> {
+    let path = resource_location.strip_prefix("file:")?;
+    let path = path
+        .strip_prefix("//localhost")
+        .or_else(|| path.strip_prefix("//"))
+        .unwrap_or(path);
+
+    // Decode percent-encoded bytes (e.g. spaces)
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    let directory = PathBuf::from(String::from_utf8(decoded).ok()?);
+    subdirectory(&directory, "FLUIDS").map(|_| directory)
+}
+
+/// Escapes a string for use in an XML attribute.
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Returns the `modelDescription.xml` of the component with the given default fluid parameters.
+///
+/// # Parameters
+///
+/// - `fluid`: Default value of the `fluid` parameter (e.g., "R454B" or "R32;R1234YF").
+/// - `composition`: Default value of the `composition` parameter (e.g., "0.5,0.5", or "" for predefined mixtures and pure fluids).
+///
+/// # Returns
+///
+/// - `String`: The model description, in FMI 2.0 co-simulation format.
+pub fn model_description(fluid: &str, composition: &str) -> String {
+    let mut variables = String::new();
+    for (reference, (name, causality, unit, description)) in REAL_VARIABLES.iter().enumerate() {
+        let attributes = match reference {
+            0 | 1 => format!(
+                "causality=\"input\" variability=\"continuous\">\n      <Real start=\"{}\" unit=\"{}\"/>",
+                DEFAULT_INPUTS[reference], unit
+            ),
+            _ => format!(
+                "causality=\"{}\" variability=\"continuous\" initial=\"calculated\">\n      <Real unit=\"{}\"/>",
+                causality, unit
+            ),
+        };
+        variables.push_str(&format!(
+            "    <ScalarVariable name=\"{}\" valueReference=\"{}\" description=\"{}\" {}\n    </ScalarVariable>\n",
+            name, reference, description, attributes
+        ));
+    }
+    for (reference, (name, description, start)) in [
+        (
+            "fluid",
+            "Predefined mixture, pure fluid, or fluids separated by semicolons",
+            fluid,
+        ),
+        (
+            "composition",
+            "Comma-separated mole fractions of a list of fluids",
+            composition,
+        ),
+    ]
+    .iter()
+    .enumerate()
+    {
+        variables.push_str(&format!(
+            "    <ScalarVariable name=\"{}\" valueReference=\"{}\" description=\"{}\" causality=\"parameter\" variability=\"fixed\">\n      <String start=\"{}\"/>\n    </ScalarVariable>\n",
+            name, reference, description, escape_xml(start)
+        ));
+    }
+
+    let mut units: Vec<&str> = REAL_VARIABLES.iter().map(|variable| variable.2).collect();
+    units.dedup();
+    let units: String = units
+        .iter()
+        .map(|unit| format!("    <Unit name=\"{}\"/>\n", unit))
+        .collect();
+
+    let outputs: String = (3..=REAL_VARIABLES.len())
+        .filter(|index| REAL_VARIABLES[index - 1].1 == "output")
+        .map(|index| {
+            format!(
+                "      <Unknown index=\"{}\" dependencies=\"1 2\"/>\n",
+                index
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<fmiModelDescription fmiVersion="2.0" modelName="RefpropFluid" guid="{guid}" description="REFPROP fluid properties from pressure and enthalpy" generationTool="refprop-sys {version}" variableNamingConvention="flat" numberOfEventIndicators="0">
+  <CoSimulation modelIdentifier="{identifier}" canHandleVariableCommunicationStepSize="true" canInterpolateInputs="false" canBeInstantiatedOnlyOncePerProcess="false"/>
+  <UnitDefinitions>
+{units}  </UnitDefinitions>
+  <ModelVariables>
+{variables}  </ModelVariables>
+  <ModelStructure>
+    <Outputs>
+{outputs}    </Outputs>
+    <InitialUnknowns>
+{outputs}    </InitialUnknowns>
+  </ModelStructure>
+</fmiModelDescription>
+"#,
+        guid = GUID,
+        version = env!("CARGO_PKG_VERSION"),
+        identifier = MODEL_IDENTIFIER,
+        units = units,
+        variables = variables,
+        outputs = outputs,
+    )
+}
+
+/// Runs an FMI call on a component, converting panics into `Status::Fatal`.
+unsafe fn with_component(c: Component, call: impl FnOnce(&mut FluidComponent) -> Status) -> Status {
+    let Some(component) = (c as *mut FluidComponent).as_mut() else {
+        return Status::Error;
+    };
+    panic::catch_unwind(AssertUnwindSafe(|| call(component))).unwrap_or(Status::Fatal)
+}
+
+/// Logs and rejects a call that the component does not support.
+unsafe fn unsupported(c: Component, function: &str) -> Status {
+    with_component(c, |component| {
+        component.log(
+            Status::Error,
+            c"logStatusError",
+            &format!("{} is not supported.", function),
+        );
+        Status::Error
+    })
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetTypesPlatform() -> *const c_char {
+    c"default".as_ptr()
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetVersion() -> *const c_char {
+    c"2.0".as_ptr()
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2Instantiate(
+    instance_name: *const c_char,
+    fmu_type: c_int,
+    guid: *const c_char,
+    resource_location: *const c_char,
+    functions: *const CallbackFunctions,
+    _visible: Boolean,
+    logging_on: Boolean,
+) -> Component {
+    let string = |value: *const c_char| {
+        if value.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(value).to_string_lossy().into_owned()
+        }
+    };
+
+    let component = Box::new(FluidComponent {
+        instance_name: CString::new(string(instance_name)).unwrap_or_default(),
+        logger: functions.as_ref().and_then(|functions| functions.logger),
+        environment: functions
+            .as_ref()
+            .map_or(ptr::null_mut(), |functions| functions.component_environment),
+        logging_on: logging_on != 0,
+        reals: [
+            DEFAULT_INPUTS[0],
+            DEFAULT_INPUTS[1],
+            f64::NAN,
+            f64::NAN,
+            f64::NAN,
+            f64::NAN,
+            f64::NAN,
+        ],
+        strings: [c"WATER".into(), CString::default()],
+        z: Vec::new(),
+        loaded: None,
+    });
+
+    // Only co-simulation (fmi2CoSimulation = 1) is supported
+    let error = if fmu_type != 1 {
+        Some("Only co-simulation is supported.".to_string())
+    } else if string(guid) != GUID {
+        Some(format!("GUID {} does not match {}.", string(guid), GUID))
+    } else {
+        let path = fluid_directory(&string(resource_location));
+        let result = panic::catch_unwind(|| match &path {
+            Some(directory) => RefpropFunctionLibrary::set_path(directory.to_str()),
+            None if env::var_os("RPPREFIX").is_some() => RefpropFunctionLibrary::set_path(None),
+            None => Ok(()),
+        });
+        match result {
+            Ok(Ok(())) => None,
+            Ok(Err(error)) => Some(error.to_string()),
+            Err(_) => Some("Panic while setting the REFPROP path.".to_string()),
+        }
+    };
+
+    if let Some(message) = error {
+        component.log(Status::Error, c"logStatusError", &message);
+        return ptr::null_mut();
+    }
+    Box::into_raw(component) as Component
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2FreeInstance(c: Component) {
+    if !c.is_null() {
+        drop(Box::from_raw(c as *mut FluidComponent));
+    }
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetDebugLogging(
+    c: Component,
+    logging_on: Boolean,
+    _n_categories: usize,
+    _categories: *const *const c_char,
+) -> Status {
+    with_component(c, |component| {
+        component.logging_on = logging_on != 0;
+        Status::Ok
+    })
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetupExperiment(
+    c: Component,
+    _tolerance_defined: Boolean,
+    _tolerance: f64,
+    _start_time: f64,
+    _stop_time_defined: Boolean,
+    _stop_time: f64,
+) -> Status {
+    with_component(c, |_| Status::Ok)
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2EnterInitializationMode(c: Component) -> Status {
+    with_component(c, |_| Status::Ok)
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2ExitInitializationMode(c: Component) -> Status {
+    with_component(c, FluidComponent::evaluate)
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2Terminate(c: Component) -> Status {
+    with_component(c, |_| Status::Ok)
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2Reset(c: Component) -> Status {
+    with_component(c, |component| {
+        component.reals[..2].copy_from_slice(&DEFAULT_INPUTS);
+        component.reals[2..].fill(f64::NAN);
+        Status::Ok
+    })
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetReal(
+    c: Component,
+    vr: *const ValueReference,
+    nvr: usize,
+    value: *mut f64,
+) -> Status {
+    with_component(c, |component| {
+        if nvr == 0 {
+            return Status::Ok;
+        }
+        let references = slice::from_raw_parts(vr, nvr);
+        let values = slice::from_raw_parts_mut(value, nvr);
+        for (reference, value) in references.iter().zip(values) {
+            match component.reals.get(*reference as usize) {
+                Some(real) => *value = *real,
+                None => return Status::Error,
+            }
+        }
+        Status::Ok
+    })
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetReal(
+    c: Component,
+    vr: *const ValueReference,
+    nvr: usize,
+    value: *const f64,
+) -> Status {
+    with_component(c, |component| {
+        if nvr == 0 {
+            return Status::Ok;
+        }
+        let references = slice::from_raw_parts(vr, nvr);
+        let values = slice::from_raw_parts(value, nvr);
+        for (reference, value) in references.iter().zip(values) {
+            match *reference {
+                0 | 1 => component.reals[*reference as usize] = *value,
+                _ => {
+                    component.log(
+                        Status::Error,
+                        c"logStatusError",
+                        &format!("Variable {} is not an input.", reference),
+                    );
+                    return Status::Error;
+                }
+            }
+        }
+        Status::Ok
+    })
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetString(
+    c: Component,
+    vr: *const ValueReference,
+    nvr: usize,
+    value: *mut *const c_char,
+) -> Status {
+    with_component(c, |component| {
+        if nvr == 0 {
+            return Status::Ok;
+        }
+        let references = slice::from_raw_parts(vr, nvr);
+        let values = slice::from_raw_parts_mut(value, nvr);
+        for (reference, value) in references.iter().zip(values) {
+            match component.strings.get(*reference as usize) {
+                Some(string) => *value = string.as_ptr(),
+                None => return Status::Error,
+            }
+        }
+        Status::Ok
+    })
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetString(
+    c: Component,
+    vr: *const ValueReference,
+    nvr: usize,
+    value: *const *const c_char,
+) -> Status {
+    with_component(c, |component| {
+        if nvr == 0 {
+            return Status::Ok;
+        }
+        let references = slice::from_raw_parts(vr, nvr);
+        let values = slice::from_raw_parts(value, nvr);
+        for (reference, value) in references.iter().zip(values) {
+            if *reference as usize >= component.strings.len() || value.is_null() {
+                return Status::Error;
+            }
+            component.strings[*reference as usize] = CStr::from_ptr(*value).into();
+            component.loaded = None;
+        }
+        Status::Ok
+    })
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetInteger(
+    c: Component,
+    _vr: *const ValueReference,
+    nvr: usize,
+    _value: *mut c_int,
+) -> Status {
+    if nvr == 0 {
+        return with_component(c, |_| Status::Ok);
+    }
+    unsupported(c, "fmi2GetInteger")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetInteger(
+    c: Component,
+    _vr: *const ValueReference,
+    nvr: usize,
+    _value: *const c_int,
+) -> Status {
+    if nvr == 0 {
+        return with_component(c, |_| Status::Ok);
+    }
+    unsupported(c, "fmi2SetInteger")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetBoolean(
+    c: Component,
+    _vr: *const ValueReference,
+    nvr: usize,
+    _value: *mut Boolean,
+) -> Status {
+    if nvr == 0 {
+        return with_component(c, |_| Status::Ok);
+    }
+    unsupported(c, "fmi2GetBoolean")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetBoolean(
+    c: Component,
+    _vr: *const ValueReference,
+    nvr: usize,
+    _value: *const Boolean,
+) -> Status {
+    if nvr == 0 {
+        return with_component(c, |_| Status::Ok);
+    }
+    unsupported(c, "fmi2SetBoolean")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetFMUstate(c: Component, _state: *mut FmuState) -> Status {
+    unsupported(c, "fmi2GetFMUstate")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetFMUstate(c: Component, _state: FmuState) -> Status {
+    unsupported(c, "fmi2SetFMUstate")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2FreeFMUstate(c: Component, _state: *mut FmuState) -> Status {
+    unsupported(c, "fmi2FreeFMUstate")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SerializedFMUstateSize(
+    c: Component,
+    _state: FmuState,
+    _size: *mut usize,
+) -> Status {
+    unsupported(c, "fmi2SerializedFMUstateSize")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SerializeFMUstate(
+    c: Component,
+    _state: FmuState,
+    _serialized: *mut u8,
+    _size: usize,
+) -> Status {
+    unsupported(c, "fmi2SerializeFMUstate")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2DeSerializeFMUstate(
+    c: Component,
+    _serialized: *const u8,
+    _size: usize,
+    _state: *mut FmuState,
+) -> Status {
+    unsupported(c, "fmi2DeSerializeFMUstate")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetDirectionalDerivative(
+    c: Component,
+    _unknown: *const ValueReference,
+    _n_unknown: usize,
+    _known: *const ValueReference,
+    _n_known: usize,
+    _dv_known: *const f64,
+    _dv_unknown: *mut f64,
+) -> Status {
+    unsupported(c, "fmi2GetDirectionalDerivative")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetRealInputDerivatives(
+    c: Component,
+    _vr: *const ValueReference,
+    _nvr: usize,
+    _order: *const c_int,
+    _value: *const f64,
+) -> Status {
+    unsupported(c, "fmi2SetRealInputDerivatives")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetRealOutputDerivatives(
+    c: Component,
+    _vr: *const ValueReference,
+    _nvr: usize,
+    _order: *const c_int,
+    _value: *mut f64,
+) -> Status {
+    unsupported(c, "fmi2GetRealOutputDerivatives")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2DoStep(
+    c: Component,
+    _current_communication_point: f64,
+    _communication_step_size: f64,
+    _no_set_fmu_state_prior_to_current_point: Boolean,
+) -> Status {
+    with_component(c, FluidComponent::evaluate)
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2CancelStep(c: Component) -> Status {
+    unsupported(c, "fmi2CancelStep")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetStatus(c: Component, _kind: c_int, _value: *mut Status) -> Status {
+    unsupported(c, "fmi2GetStatus")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetRealStatus(c: Component, _kind: c_int, _value: *mut f64) -> Status {
+    unsupported(c, "fmi2GetRealStatus")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetIntegerStatus(
+    c: Component,
+    _kind: c_int,
+    _value: *mut c_int,
+) -> Status {
+    unsupported(c, "fmi2GetIntegerStatus")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetBooleanStatus(
+    c: Component,
+    _kind: c_int,
+    _value: *mut Boolean,
+) -> Status {
+    unsupported(c, "fmi2GetBooleanStatus")
+}
+
+/// # Safety
+///
+/// Called by the simulation environment according to the FMI 2.0 standard.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetStringStatus(
+    c: Component,
+    _kind: c_int,
+    _value: *mut *const c_char,
+) -> Status {
+    unsupported(c, "fmi2GetStringStatus")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmi_configuration() {
+        assert_eq!(fluid_directory("http://example.com/resources"), None);
+        assert_eq!(fluid_directory("file:///nonexistent%20dir/resources"), None);
+
+        // A bundle is recognised ignoring case and left for set_path to check
+        let resources = env::temp_dir().join(format!("refprop fmi {}", std::process::id()));
+        std::fs::create_dir_all(resources.join("fluids")).unwrap();
+        let location = format!("file://{}", resources.display()).replace(' ', "%20");
+        let directory = fluid_directory(&location);
+        std::fs::remove_dir_all(&resources).unwrap();
+        assert_eq!(directory, Some(resources));
+        assert_eq!(parse_composition("0.5, 0.5").unwrap(), vec![0.5, 0.5]);
+        assert!(parse_composition("0.5,x").is_err());
+
+        let xml = model_description("R32;R125", "0.5,0.5");
+        assert!(xml.contains(GUID));
+        assert!(xml.contains("<String start=\"R32;R125\"/>"));
+        assert_eq!(xml.matches("<ScalarVariable").count(), 9);
+        assert!(xml.contains("<Unknown index=\"7\" dependencies=\"1 2\"/>"));
+    }
+
+    #[test]
+    fn test_fmi_co_simulation() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let state = {
+            let _guard = RefpropFunctionLibrary::lock()?;
+            RefpropFunctionLibrary::set_fluids("WATER")?;
+            RefpropFunctionLibrary::tp_flash(300.0, 101.325, &[1.0])?
+        };
+
+        let guid = CString::new(GUID).unwrap();
+        let c = unsafe {
+            fmi2Instantiate(
+                c"water".as_ptr(),
+                1,
+                guid.as_ptr(),
+                ptr::null(),
+                ptr::null(),
+                0,
+                0,
+            )
+        };
+        assert!(!c.is_null());
+
+        let (strings, fluid) = ([FLUID as ValueReference], [c"WATER".as_ptr()]);
+        let (inputs, outputs) = ([0, 1], [2, 3, 4]);
+        let (input_values, mut values) = ([101.325, state.h], [0.0; 3]);
+        let status = unsafe {
+            [
+                fmi2SetString(c, strings.as_ptr(), 1, fluid.as_ptr()),
+                fmi2SetReal(c, inputs.as_ptr(), 2, input_values.as_ptr()),
+                fmi2DoStep(c, 0.0, 1.0, 1),
+                fmi2GetReal(c, outputs.as_ptr(), 3, values.as_mut_ptr()),
+                fmi2SetReal(c, outputs.as_ptr(), 1, input_values.as_ptr()),
+            ]
+        };
+        unsafe { fmi2FreeInstance(c) };
+
+        assert_eq!(status[..4], [Status::Ok; 4]);
+        assert_eq!(status[4], Status::Error);
+        assert!((values[0] - 300.0).abs() < 1e-4);
+        assert!((values[1] - state.D).abs() < 1e-4);
+        assert!(values[2] < 0.0);
+
+        Ok(())
+    }
+}
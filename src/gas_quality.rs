@@ -0,0 +1,277 @@
+use crate::{
+    bindings,
+    utils::{acquire_lock, check_refprop_error, validate_composition},
+    RefpropError, RefpropFunctionLibrary, StateRegion,
+};
+
+/// Molar mass of standard dry air [g/mol], following ISO 6976:2016.
+const M_AIR: f64 = 28.96546;
+
+/// Pressure of one pound-force per square inch [kPa].
+const PSI: f64 = 6.894757293168361;
+
+/// Reference conditions at which the gas quality is reported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasReferenceConditions {
+    /// Temperature of the metering and combustion reference [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_K"))]
+    pub T: f64,
+    /// Pressure of the metering reference [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "P_kPa"))]
+    pub P: f64,
+    /// Compressibility factor of dry air at `T` and `P` [-], used for the real-gas relative density
+    pub Z_air: f64,
+}
+
+impl GasReferenceConditions {
+    /// ISO standard reference conditions: 15 °C and 101.325 kPa.
+    pub const ISO: GasReferenceConditions = GasReferenceConditions {
+        T: 288.15,
+        P: 101.325,
+        Z_air: 0.999595,
+    };
+
+    /// US customary reference conditions: 60 °F and 14.696 psia.
+    pub const US: GasReferenceConditions = GasReferenceConditions {
+        T: 288.705_555_555_555_6,
+        P: 14.696 * PSI,
+        Z_air: 0.999601,
+    };
+}
+
+/// Represents the gas quality calculated by the `gas_quality` method.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasQualityReport {
+    /// Reference temperature [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_K"))]
+    pub T: f64,
+    /// Reference pressure [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "P_kPa"))]
+    pub P: f64,
+    /// Molar mass [g/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "M_g_per_mol"))]
+    pub M: f64,
+    /// Molar density at the reference conditions [mol/L]
+    #[cfg_attr(feature = "serde", serde(rename = "D_mol_per_L"))]
+    pub D: f64,
+    /// Compressibility factor at the reference conditions [-]
+    pub Z: f64,
+    /// Gross (superior) molar heating value [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "hg_J_per_mol"))]
+    pub hg: f64,
+    /// Net (inferior) molar heating value [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "hn_J_per_mol"))]
+    pub hn: f64,
+    /// Gross mass-based heating value [kJ/kg]
+    #[cfg_attr(feature = "serde", serde(rename = "hg_mass_kJ_per_kg"))]
+    pub hg_mass: f64,
+    /// Net mass-based heating value [kJ/kg]
+    #[cfg_attr(feature = "serde", serde(rename = "hn_mass_kJ_per_kg"))]
+    pub hn_mass: f64,
+    /// Gross volumetric heating value of the real gas at the reference conditions [kJ/m³]
+    #[cfg_attr(feature = "serde", serde(rename = "Hs_kJ_per_m3"))]
+    pub Hs: f64,
+    /// Net volumetric heating value of the real gas at the reference conditions [kJ/m³]
+    #[cfg_attr(feature = "serde", serde(rename = "Hi_kJ_per_m3"))]
+    pub Hi: f64,
+    /// Real-gas relative density (ratio of the gas density to the density of dry air) [-]
+    pub relative_density: f64,
+    /// Gross (superior) Wobbe index [kJ/m³]
+    #[cfg_attr(feature = "serde", serde(rename = "Ws_kJ_per_m3"))]
+    pub Ws: f64,
+    /// Net (inferior) Wobbe index [kJ/m³]
+    #[cfg_attr(feature = "serde", serde(rename = "Wi_kJ_per_m3"))]
+    pub Wi: f64,
+}
+
+impl RefpropFunctionLibrary {
+    /// Calculates the ideal-gas gross and net heating values of a fuel using the `HEATdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Combustion reference temperature [K]
+    /// - `D`: Molar density [mol/L]; not used by current versions of REFPROP
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `(f64, f64)`: The gross and net heating values [J/mol].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP has no heating values for a loaded component.
+    ///
+    /// # References
+    ///
+    /// - [HEATdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/HEATdll)
+    pub fn heating_value(T: f64, D: f64, z: &[f64]) -> Result<(f64, f64), RefpropError> {
+        validate_composition(z)?;
+
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+
+        // Define buffer sizes as per REFPROP's documentation
+        const HERR_LENGTH: usize = 255;
+
+        let mut z_buffer = [0.0f64; 20];
+        z_buffer[..z.len()].copy_from_slice(z);
+
+        let mut T = T;
+        let mut D = D;
+        let mut hg: f64 = 0.0;
+        let mut hn: f64 = 0.0;
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call HEATdll within an unsafe block
+        unsafe {
+            bindings::HEATdll(
+                &mut T as *mut f64,
+                &mut D as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut hg as *mut f64,
+                &mut hn as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        Ok((hg, hn))
+    }
+
+    /// Calculates the heating values, Wobbe index, relative density, and compressibility of a fuel gas.
+    ///
+    /// The heating values are the ideal-gas values of `heating_value` at the reference temperature.
+    /// The volumetric heating values and Wobbe indices refer to the real gas at the reference
+    /// conditions, with its density from `tp_flash`. The relative density is the ratio of the real-gas
+    /// densities of the fuel and of dry air at the reference conditions.
+    ///
+    /// # Parameters
+    ///
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    /// - `reference`: The reference conditions, e.g. `GasReferenceConditions::ISO`.
+    ///
+    /// # Returns
+    ///
+    /// - `GasQualityReport`: A struct containing the gas quality.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation or
+    ///   the gas is not a superheated vapor or supercritical at the reference conditions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{GasReferenceConditions, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     RefpropFunctionLibrary::set_fluids("METHANE;ETHANE;NITROGEN;CO2")?;
+    ///     let z = [0.92, 0.04, 0.025, 0.015];
+    ///
+    ///     let report = RefpropFunctionLibrary::gas_quality(&z, GasReferenceConditions::ISO)?;
+    ///     println!("Wobbe index: {} MJ/m³", report.Ws / 1000.0);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn gas_quality(
+        z: &[f64],
+        reference: GasReferenceConditions,
+    ) -> Result<GasQualityReport, RefpropError> {
+        let GasReferenceConditions { T, P, Z_air } = reference;
+
+        // Keep the loaded fluids fixed across the calls below
+        let _lock = acquire_lock()?;
+
+        let state = Self::tp_flash(T, P, z)?;
+        let region = StateRegion::from_quality(state.q);
+        if !matches!(
+            region,
+            StateRegion::SuperheatedVapor | StateRegion::Supercritical
+        ) {
+            return Err(RefpropError::CalculationError(format!(
+                "Gas is not a vapor at the reference conditions but {} (q = {})",
+                region.as_str(),
+                state.q
+            )));
+        }
+        let (hg, hn) = Self::heating_value(T, state.D, z)?;
+        let M = Self::calc_molar_mass(z)?;
+
+        let mut z_buffer = [0.0f64; 20];
+        z_buffer[..z.len()].copy_from_slice(z);
+        let mut R: f64 = 0.0;
+        unsafe {
+            bindings::RMIX2dll(z_buffer.as_mut_ptr(), &mut R as *mut f64);
+        }
+        let Z = P / (state.D * R * T);
+
+        let relative_density = M / M_AIR * Z_air / Z;
+        let Hs = hg * state.D;
+        let Hi = hn * state.D;
+
+        Ok(GasQualityReport {
+            T,
+            P,
+            M,
+            D: state.D,
+            Z,
+            hg,
+            hn,
+            hg_mass: hg / M,
+            hn_mass: hn / M,
+            Hs,
+            Hi,
+            relative_density,
+            Ws: Hs / relative_density.sqrt(),
+            Wi: Hi / relative_density.sqrt(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_quality() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        RefpropFunctionLibrary::set_fluids("METHANE")?;
+
+        // Methane: about 891 kJ/mol, 37.7 MJ/m³, and a Wobbe index of about 50.7 MJ/m³ at 15 °C
+        let report = RefpropFunctionLibrary::gas_quality(&[1.0], GasReferenceConditions::ISO)?;
+        assert!((report.hg - 891.0e3).abs() < 2.0e3);
+        assert!(report.hn < report.hg);
+        assert!(report.Z < 1.0 && report.Z > 0.99);
+        assert!((report.relative_density - 0.555).abs() < 0.005);
+        assert!((report.Ws - 50.7e3).abs() < 0.5e3);
+
+        let us = RefpropFunctionLibrary::gas_quality(&[1.0], GasReferenceConditions::US)?;
+        assert!(us.Hs < report.Hs);
+
+        // Butane is a subcooled liquid at 15 °C and 1 MPa
+        RefpropFunctionLibrary::set_fluids("BUTANE")?;
+        let liquid = GasReferenceConditions {
+            P: 1000.0,
+            ..GasReferenceConditions::ISO
+        };
+        assert!(matches!(
+            RefpropFunctionLibrary::gas_quality(&[1.0], liquid),
+            Err(RefpropError::CalculationError(_))
+        ));
+
+        Ok(())
+    }
+}
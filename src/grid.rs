@@ -0,0 +1,127 @@
+use ndarray::{Array2, ArrayView1, AsArray};
+
+use crate::{
+    utils::{acquire_lock, fluids_generation},
+    CompositionInput, FlashProperty, InputPair, RefpropError, RefpropFunctionLibrary,
+};
+
+impl RefpropFunctionLibrary {
+    /// Evaluates flash calculations over the grid spanned by two axes of independent properties.
+    ///
+    /// Every point `(a[i], b[j])` is flashed with `pair`, and one array of shape `(a.len(), b.len())`
+    /// is returned per requested property, in the order of `properties`. Points whose calculation
+    /// fails, and properties that are undefined at a point (`Cv` and `Cp` in the two-phase region),
+    /// are NaN.
+    ///
+    /// The global REFPROP lock is acquired for each row and released between rows, so that other
+    /// threads are not starved during large grids. The fluid must be loaded beforehand; if another
+    /// thread loads different fluids while the grid is evaluated, the evaluation is aborted. Holding
+    /// `RefpropFunctionLibrary::lock` around the call keeps the lock for the whole grid instead.
+    ///
+    /// # Parameters
+    ///
+    /// - `pair`: The pair of independent properties, in the default molar units.
+    /// - `a`: Axis of the first property of the pair (rows of the result).
+    /// - `b`: Axis of the second property of the pair (columns of the result).
+    /// - `z`: Overall composition, as a `Composition` or a slice of mole fractions. Maximum of 20 components.
+    /// - `properties`: The properties to return.
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<Array2<f64>>`: One array per requested property.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if the loaded fluids change during the evaluation.
+    /// - Returns `RefpropError::MutexPoisoned` if the lock cannot be acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndarray::Array1;
+    /// use refprop_sys::{FlashProperty, InputPair, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let T = Array1::linspace(250.0, 350.0, 11);
+    ///     let P = Array1::linspace(100.0, 1000.0, 10);
+    ///     let maps = RefpropFunctionLibrary::flash_grid(
+    ///         InputPair::TP,
+    ///         &T,
+    ///         &P,
+    ///         &z,
+    ///         &[FlashProperty::Enthalpy, FlashProperty::Density],
+    ///     )?;
+    ///
+    ///     assert_eq!(maps[0].dim(), (11, 10));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn flash_grid<'a>(
+        pair: InputPair,
+        a: impl AsArray<'a, f64>,
+        b: impl AsArray<'a, f64>,
+        z: impl Into<CompositionInput<'_>>,
+        properties: &[FlashProperty],
+    ) -> Result<Vec<Array2<f64>>, RefpropError> {
+        let (a, b): (ArrayView1<f64>, ArrayView1<f64>) = (a.into(), b.into());
+        let input = z.into();
+        let (z, generation) = {
+            let _lock = acquire_lock()?;
+            (input.mole_fractions()?, fluids_generation())
+        };
+
+        let mut maps = vec![Array2::from_elem((a.len(), b.len()), f64::NAN); properties.len()];
+        for (i, a) in a.iter().enumerate() {
+            let _lock = acquire_lock()?;
+            if fluids_generation() != generation {
+                return Err(RefpropError::CalculationError(
+                    "The loaded fluids changed during the grid evaluation.".to_string(),
+                ));
+            }
+
+            for (j, b) in b.iter().enumerate() {
+                if let Ok(output) = Self::flash(pair, *a, *b, &*z) {
+                    for (map, property) in maps.iter_mut().zip(properties) {
+                        map[[i, j]] = property.value(&output);
+                    }
+                }
+            }
+        }
+
+        Ok(maps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_flash_grid() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+
+        let T = array![250.0, 300.0];
+        let P = array![101.325, 500.0, -1.0];
+        let maps = RefpropFunctionLibrary::flash_grid(
+            InputPair::TP,
+            &T,
+            &P,
+            &z,
+            &[FlashProperty::Temperature, FlashProperty::Density],
+        )?;
+
+        assert_eq!(maps.len(), 2);
+        assert_eq!(maps[0].dim(), (2, 3));
+        assert!((maps[0][[1, 0]] - 300.0).abs() < 1e-6);
+        assert!(maps[1][[0, 1]] > 0.0);
+        assert!(maps[1][[0, 2]].is_nan());
+
+        Ok(())
+    }
+}
@@ -1,26 +1,68 @@
 #![allow(non_snake_case)]
+mod binary_parameters;
 pub(crate) mod bindings;
+mod cache;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod catalogue;
+mod charts;
+#[cfg(any(feature = "arrow", feature = "polars"))]
+mod columnar;
+mod composition;
 pub mod errors;
 mod flash_routines;
+#[cfg(feature = "fmi")]
+pub mod fmi;
+mod gas_quality;
+#[cfg(feature = "ndarray")]
+mod grid;
+mod lock;
 mod misc;
+mod mixture_definition;
+mod psychrometrics;
+#[cfg(feature = "python")]
+mod python;
 mod setup;
+mod tables;
+mod units;
 pub(crate) mod utils;
 
-use std::sync::{Mutex, OnceLock};
-
+pub use binary_parameters::{
+    BinaryCoverage, BinaryEntry, BinaryPairCoverage, BinaryParameterFile, BinaryParameterSource,
+};
+pub use cache::{CacheStats, FlashCache};
+pub use catalogue::{CatalogueMatch, FluidCatalogue, FluidEntry, FluidFileKind, MixtureEntry};
+pub use charts::{ChartKind, ChartLine, ChartSpec, LineKind, PropertyChart, StatePoint};
+pub use composition::{Composition, CompositionBasis, CompositionInput};
 pub use errors::RefpropError;
-pub use flash_routines::FlashOutput;
+pub use flash_routines::{FlashOutput, FlashProperty, InputPair};
+pub use gas_quality::{GasQualityReport, GasReferenceConditions};
+pub use lock::RefpropGuard;
 pub use misc::get_enum::GetEnumFlag;
-pub use misc::TransportOutput;
-pub use setup::CriticalParameters;
+pub use misc::{
+    CompositionDerivatives, ExcessOutput, HelmholtzDerivatives, LoadedComponent, LoadedFluids,
+    NameOutput, QualityOutput, ResidualProperties, TransportOutput, VirialOutput,
+};
+pub use mixture_definition::MixtureDefinition;
+pub use psychrometrics::MoistAirState;
+pub use setup::{
+    CriticalParameters, EquationOfState, EquationOfStateGuard, FlagGuard, PureComponentGuard,
+    ReferenceState, RefpropFlag,
+};
+pub use tables::{
+    SaturatedState, SaturationAxis, SaturationRow, SaturationStatus, SaturationTable, StateRegion,
+    SuperheatedRow, SuperheatedStatus, SuperheatedTable,
+};
+pub use units::Quantity;
 
-pub(crate) static REFPROP_MUTEX: OnceLock<Mutex<()>> = OnceLock::new();
+pub(crate) static REFPROP_LOCK: lock::ReentrantLock = lock::ReentrantLock::new();
 
 pub(crate) const CV_UNDEFINED: f64 = -9999990.0;
 pub(crate) const CP_UNDEFINED: f64 = -9999980.0;
 
 /// Represents the unit systems for temperature and density inputs.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Units {
     /// Default units: Temperature in Kelvin (K) and Density in mol/dm³.
     Default,
@@ -87,6 +129,7 @@ impl Units {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Basis {
     /// All inputs and outputs are given on a mole basis.
     Molar = 0,
@@ -97,6 +140,7 @@ pub enum Basis {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Phase {
     /// Unknown phase; saturation routines will determine the phase.
     Unknown = 0,
@@ -110,6 +154,7 @@ pub enum Phase {
 
 /// Represents the kr/kq flags for the `ab_fls_h` method.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KrKqFlag {
     /// Default flag.
     Default = 0,
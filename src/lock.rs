@@ -0,0 +1,204 @@
+use std::{
+    marker::PhantomData,
+    sync::{Condvar, Mutex, PoisonError},
+    thread::{self, ThreadId},
+};
+
+use crate::{
+    utils::{acquire_lock, fluids_changed},
+    RefpropError, RefpropFunctionLibrary, REFPROP_LOCK,
+};
+
+/// Reentrant lock serialising access to REFPROP, which keeps its state in global variables.
+///
+/// The thread holding the lock may acquire it again, so that a sequence of calls (e.g., loading a
+/// fluid and flashing it) can be made atomic with `RefpropFunctionLibrary::lock` while every method
+/// still locks internally. A panic while the lock is held poisons it, as with `std::sync::Mutex`.
+pub(crate) struct ReentrantLock {
+    state: Mutex<LockState>,
+    released: Condvar,
+}
+
+struct LockState {
+    owner: Option<ThreadId>,
+    depth: usize,
+    poisoned: bool,
+}
+
+impl ReentrantLock {
+    pub(crate) const fn new() -> Self {
+        ReentrantLock {
+            state: Mutex::new(LockState {
+                owner: None,
+                depth: 0,
+                poisoned: false,
+            }),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Blocks until the lock is free or already held by the current thread.
+    pub(crate) fn acquire(&'static self) -> Result<RefpropGuard, RefpropError> {
+        let current = thread::current().id();
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if state.poisoned {
+                return Err(RefpropError::MutexPoisoned);
+            }
+            match state.owner {
+                None => {
+                    state.owner = Some(current);
+                    state.depth = 1;
+                    break;
+                }
+                Some(owner) if owner == current => {
+                    state.depth += 1;
+                    break;
+                }
+                Some(_) => {
+                    state = self
+                        .released
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+            }
+        }
+
+        Ok(RefpropGuard {
+            lock: self,
+            _not_send: PhantomData,
+        })
+    }
+
+    /// Clears the poisoning left by a thread that panicked while holding the lock.
+    pub(crate) fn clear_poison(&self) {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .poisoned = false;
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if thread::panicking() {
+            state.poisoned = true;
+        }
+        state.depth -= 1;
+        if state.depth == 0 {
+            state.owner = None;
+            self.released.notify_all();
+        }
+    }
+}
+
+/// Guard holding the global REFPROP lock, which is released when the guard is dropped.
+///
+/// The guard is returned by `RefpropFunctionLibrary::lock` and is not `Send`: the lock belongs to the
+/// thread that acquired it.
+pub struct RefpropGuard {
+    lock: &'static ReentrantLock,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for RefpropGuard {
+    fn drop(&mut self) {
+        self.lock.release();
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Acquires the global REFPROP lock for the current thread until the returned guard is dropped.
+    ///
+    /// REFPROP keeps the loaded fluids, reference states, and flags in global state, so a sequence of
+    /// calls that depends on that state (e.g., `set_mixture` followed by flash calculations) is only
+    /// consistent if no other thread calls REFPROP in between. Holding the guard blocks all other
+    /// threads, while the current thread can keep calling any method of the library.
+    ///
+    /// # Returns
+    ///
+    /// - `RefpropGuard`: A guard releasing the lock when dropped.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::MutexPoisoned` if a thread panicked while holding the lock, until
+    ///   `clear_lock_poison` is called.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///
+    ///     // No other thread can load different fluids between these two calls
+    ///     let guard = RefpropFunctionLibrary::lock()?;
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///     let output = RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z)?;
+    ///     drop(guard);
+    ///
+    ///     println!("Density: {} mol/L", output.D);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lock() -> Result<RefpropGuard, RefpropError> {
+        acquire_lock()
+    }
+
+    /// Clears the poisoning of the global REFPROP lock after a thread panicked while holding it.
+    ///
+    /// The panic may have interrupted a setup, so the setup is marked as changed: fluids must be
+    /// loaded again before further calculations, and cached results are discarded.
+    pub fn clear_lock_poison() {
+        REFPROP_LOCK.clear_poison();
+        if let Ok(guard) = acquire_lock() {
+            fluids_changed(&guard);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::mpsc, time::Duration};
+
+    static LOCK: ReentrantLock = ReentrantLock::new();
+
+    #[test]
+    fn test_reentrant_lock() {
+        let outer = LOCK.acquire().unwrap();
+        let inner = LOCK.acquire().unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let _guard = LOCK.acquire().unwrap();
+            sender.send(()).unwrap();
+        });
+
+        // The other thread is blocked until both guards are dropped
+        drop(inner);
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+        drop(outer);
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_clear_poison() {
+        static POISONED: ReentrantLock = ReentrantLock::new();
+
+        let result = thread::spawn(|| {
+            let _guard = POISONED.acquire().unwrap();
+            panic!("poison the lock");
+        })
+        .join();
+        assert!(result.is_err());
+        assert!(matches!(
+            POISONED.acquire(),
+            Err(RefpropError::MutexPoisoned)
+        ));
+
+        POISONED.clear_poison();
+        assert!(POISONED.acquire().is_ok());
+    }
+}
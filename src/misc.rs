@@ -4,11 +4,20 @@ mod convert_to_mass_quality;
 mod convert_to_mole_fractions;
 mod convert_to_mole_quality;
 pub(crate) mod get_enum;
+mod helmholtz;
+mod loaded_fluids;
 mod name;
 mod transport;
+mod virial;
+
+pub use helmholtz::{CompositionDerivatives, HelmholtzDerivatives, ResidualProperties};
+pub use loaded_fluids::{LoadedComponent, LoadedFluids};
+pub use name::NameOutput;
+pub use virial::{ExcessOutput, VirialOutput};
 
 /// Represents the output of the `qmole` method.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QualityOutput {
     /// Quality on molar or mass basis (moles/mass of vapor/total moles/mass).
     pub quality: f64,
@@ -17,15 +26,20 @@ pub struct QualityOutput {
     /// Composition of the vapor phase in mole fractions (xmol) [unitless].
     pub vap_composition: Vec<f64>,
     /// Molar mass of the liquid phase [g/mol].
+    #[cfg_attr(feature = "serde", serde(rename = "liq_molar_mass_g_per_mol"))]
     pub liq_molar_mass: f64,
     /// Molar mass of the vapor phase [g/mol].
+    #[cfg_attr(feature = "serde", serde(rename = "vap_molar_mass_g_per_mol"))]
     pub vap_molar_mass: f64,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransportOutput {
     /// Dynamic viscosity [uPa-s].
+    #[cfg_attr(feature = "serde", serde(rename = "eta_uPa_s"))]
     pub eta: f64,
     /// Thermal conductivity [W/(m-K)].
+    #[cfg_attr(feature = "serde", serde(rename = "tcx_W_per_m_K"))]
     pub tcx: f64,
 }
@@ -8,6 +8,7 @@ use crate::{
 
 /// Represents the flags for the `get_enum` method.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GetEnumFlag {
     /// Check all strings possible.
     AllStrings = 0,
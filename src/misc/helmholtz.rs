@@ -0,0 +1,472 @@
+use crate::{
+    bindings,
+    lock::RefpropGuard,
+    utils::{acquire_lock, check_refprop_error, padded_composition},
+    RefpropError, RefpropFunctionLibrary,
+};
+
+/// Highest derivative order with respect to `tau` or `delta` accepted by the Helmholtz routines.
+const MAX_ORDER: i32 = 3;
+
+/// Reduced Helmholtz energy and its derivatives at a state point, returned by `helmholtz_derivatives`.
+///
+/// Entry `[i][j]` of `residual` and `ideal` holds `tau^i * delta^j * d^(i+j)(alpha)/(dtau^i ddelta^j)`
+/// for `i + j <= 3`; entries of higher total order are NaN.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HelmholtzDerivatives {
+    /// Reducing temperature of the mixture [K]
+    #[cfg_attr(feature = "serde", serde(rename = "Tred_K"))]
+    pub Tred: f64,
+    /// Reducing density of the mixture [mol/L]
+    #[cfg_attr(feature = "serde", serde(rename = "Dred_mol_per_L"))]
+    pub Dred: f64,
+    /// Inverse reduced temperature, `Tred / T`
+    pub tau: f64,
+    /// Reduced density, `D / Dred`
+    pub delta: f64,
+    /// Derivatives of the residual part of the reduced Helmholtz energy
+    pub residual: [[f64; 4]; 4],
+    /// Derivatives of the ideal-gas part of the reduced Helmholtz energy
+    pub ideal: [[f64; 4]; 4],
+}
+
+/// Composition derivatives of the residual Helmholtz energy, returned by `phi_derv`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompositionDerivatives {
+    /// `n * d(alphar)/d(n_i)` at constant T, V, and n_j, per component
+    pub dadn: Vec<f64>,
+    /// `d(n * alphar)/d(n_i)` at constant T, V, and n_j, per component
+    pub dnadn: Vec<f64>,
+}
+
+/// Residual properties at a state point, returned by `residual_properties`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResidualProperties {
+    /// Residual pressure [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "Pr_kPa"))]
+    pub Pr: f64,
+    /// Residual internal energy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "er_J_per_mol"))]
+    pub er: f64,
+    /// Residual enthalpy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "hr_J_per_mol"))]
+    pub hr: f64,
+    /// Residual entropy [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "sr_J_per_mol_K"))]
+    pub sr: f64,
+    /// Residual isochoric heat capacity [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "Cvr_J_per_mol_K"))]
+    pub Cvr: f64,
+    /// Residual isobaric heat capacity [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "Cpr_J_per_mol_K"))]
+    pub Cpr: f64,
+    /// Residual Helmholtz energy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "Ar_J_per_mol"))]
+    pub Ar: f64,
+    /// Residual Gibbs energy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "Gr_J_per_mol"))]
+    pub Gr: f64,
+}
+
+/// Checks the derivative orders of the Helmholtz routines.
+fn check_orders(itau: i32, idel: i32) -> Result<(), RefpropError> {
+    if !(0..=MAX_ORDER).contains(&itau) || !(0..=MAX_ORDER).contains(&idel) {
+        return Err(RefpropError::InvalidInput(format!(
+            "Derivative orders must be between 0 and {}, got itau = {} and idel = {}",
+            MAX_ORDER, itau, idel
+        )));
+    }
+    Ok(())
+}
+
+fn phix_unlocked(
+    _guard: &RefpropGuard,
+    itau: i32,
+    idel: i32,
+    tau: f64,
+    delta: f64,
+    z: &[f64; 20],
+) -> f64 {
+    let mut itau = itau;
+    let mut idel = idel;
+    let mut tau = tau;
+    let mut delta = delta;
+    let mut z = *z;
+    let mut phixx: f64 = 0.0;
+
+    // Call PHIXdll within an unsafe block
+    unsafe {
+        bindings::PHIXdll(
+            &mut itau as *mut i32,
+            &mut idel as *mut i32,
+            &mut tau as *mut f64,
+            &mut delta as *mut f64,
+            z.as_mut_ptr(),
+            &mut phixx as *mut f64,
+        );
+    }
+
+    phixx
+}
+
+fn phi0_unlocked(
+    _guard: &RefpropGuard,
+    itau: i32,
+    idel: i32,
+    T: f64,
+    D: f64,
+    z: &[f64; 20],
+) -> f64 {
+    let mut itau = itau;
+    let mut idel = idel;
+    let mut T = T;
+    let mut D = D;
+    let mut z = *z;
+    let mut phi00: f64 = 0.0;
+
+    // Call PHI0dll within an unsafe block
+    unsafe {
+        bindings::PHI0dll(
+            &mut itau as *mut i32,
+            &mut idel as *mut i32,
+            &mut T as *mut f64,
+            &mut D as *mut f64,
+            z.as_mut_ptr(),
+            &mut phi00 as *mut f64,
+        );
+    }
+
+    phi00
+}
+
+fn reducing_parameters_unlocked(_guard: &RefpropGuard, z: &[f64; 20]) -> (f64, f64) {
+    let mut z = *z;
+    let mut Tred: f64 = 0.0;
+    let mut Dred: f64 = 0.0;
+
+    // Call REDXdll within an unsafe block
+    unsafe {
+        bindings::REDXdll(z.as_mut_ptr(), &mut Tred as *mut f64, &mut Dred as *mut f64);
+    }
+
+    (Tred, Dred)
+}
+
+impl RefpropFunctionLibrary {
+    /// Computes a derivative of the residual reduced Helmholtz energy using the `PHIXdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `itau`: Order of the derivative with respect to `tau` (0 to 3).
+    /// - `idel`: Order of the derivative with respect to `delta` (0 to 3).
+    /// - `tau`: Inverse reduced temperature, `Tred / T`.
+    /// - `delta`: Reduced density, `D / Dred`.
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: `tau^itau * delta^idel * d^(itau+idel)(alphar)/(dtau^itau ddelta^idel)`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the derivative orders or the composition are invalid.
+    ///
+    /// # References
+    ///
+    /// - [PHIXdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/PHIXdll)
+    pub fn phix(
+        itau: i32,
+        idel: i32,
+        tau: f64,
+        delta: f64,
+        z: &[f64],
+    ) -> Result<f64, RefpropError> {
+        check_orders(itau, idel)?;
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(phix_unlocked(&lock, itau, idel, tau, delta, &z_buffer))
+    }
+
+    /// Computes a derivative of the ideal-gas reduced Helmholtz energy using the `PHI0dll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `itau`: Order of the derivative with respect to `tau` (0 to 3).
+    /// - `idel`: Order of the derivative with respect to `delta` (0 to 3).
+    /// - `T`: Temperature [K].
+    /// - `D`: Molar density [mol/L].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: `tau^itau * delta^idel * d^(itau+idel)(alpha0)/(dtau^itau ddelta^idel)`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the derivative orders or the composition are invalid.
+    ///
+    /// # References
+    ///
+    /// - [PHI0dll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/PHI0dll)
+    pub fn phi0(itau: i32, idel: i32, T: f64, D: f64, z: &[f64]) -> Result<f64, RefpropError> {
+        check_orders(itau, idel)?;
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(phi0_unlocked(&lock, itau, idel, T, D, &z_buffer))
+    }
+
+    /// Computes the composition derivatives of the residual Helmholtz energy using the `PHIDERVdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `iderv`: Set to 1 for the first derivatives only, or 2 to also update REFPROP's internal second derivatives.
+    /// - `T`: Temperature [K].
+    /// - `D`: Molar density [mol/L].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `CompositionDerivatives`: The derivatives for each component of `z`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if `iderv` or the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation.
+    ///
+    /// # References
+    ///
+    /// - [PHIDERVdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/PHIDERVdll)
+    pub fn phi_derv(
+        iderv: i32,
+        T: f64,
+        D: f64,
+        z: &[f64],
+    ) -> Result<CompositionDerivatives, RefpropError> {
+        if !(1..=2).contains(&iderv) {
+            return Err(RefpropError::InvalidInput(format!(
+                "iderv must be 1 or 2, got {}",
+                iderv
+            )));
+        }
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let mut z_buffer = padded_composition(&lock, z)?;
+
+        const HERR_LENGTH: usize = 255;
+        let mut iderv = iderv;
+        let mut T = T;
+        let mut D = D;
+        let mut dadn = [0.0f64; 20];
+        let mut dnadn = [0.0f64; 20];
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call PHIDERVdll within an unsafe block
+        unsafe {
+            bindings::PHIDERVdll(
+                &mut iderv as *mut i32,
+                &mut T as *mut f64,
+                &mut D as *mut f64,
+                z_buffer.as_mut_ptr(),
+                dadn.as_mut_ptr(),
+                dnadn.as_mut_ptr(),
+                &mut ierr as *mut i32,
+                herr_ptr,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        Ok(CompositionDerivatives {
+            dadn: dadn[..z.len()].to_vec(),
+            dnadn: dnadn[..z.len()].to_vec(),
+        })
+    }
+
+    /// Computes the residual properties at a given temperature and density using the `RESIDUALdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `D`: Molar density [mol/L].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `ResidualProperties`: The residual properties.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    ///
+    /// # References
+    ///
+    /// - [RESIDUALdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/RESIDUALdll)
+    pub fn residual_properties(
+        T: f64,
+        D: f64,
+        z: &[f64],
+    ) -> Result<ResidualProperties, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let mut z_buffer = padded_composition(&lock, z)?;
+
+        let mut T = T;
+        let mut D = D;
+        let mut output = ResidualProperties {
+            Pr: 0.0,
+            er: 0.0,
+            hr: 0.0,
+            sr: 0.0,
+            Cvr: 0.0,
+            Cpr: 0.0,
+            Ar: 0.0,
+            Gr: 0.0,
+        };
+
+        // Call RESIDUALdll within an unsafe block
+        unsafe {
+            bindings::RESIDUALdll(
+                &mut T as *mut f64,
+                &mut D as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut output.Pr as *mut f64,
+                &mut output.er as *mut f64,
+                &mut output.hr as *mut f64,
+                &mut output.sr as *mut f64,
+                &mut output.Cvr as *mut f64,
+                &mut output.Cpr as *mut f64,
+                &mut output.Ar as *mut f64,
+                &mut output.Gr as *mut f64,
+            );
+        }
+
+        Ok(output)
+    }
+
+    /// Computes the reducing temperature and density of a mixture using the `REDXdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `(f64, f64)`: The reducing temperature [K] and reducing density [mol/L].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    pub fn reducing_parameters(z: &[f64]) -> Result<(f64, f64), RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(reducing_parameters_unlocked(&lock, &z_buffer))
+    }
+
+    /// Computes the residual and ideal-gas reduced Helmholtz energy and all their derivatives up to third order.
+    ///
+    /// The state is reduced with the mixture's reducing parameters (`REDXdll`), and each entry is
+    /// evaluated with `PHIXdll` (residual part) or `PHI0dll` (ideal-gas part) under a single lock.
+    /// Derived properties follow from the usual relations, e.g. the compressibility factor
+    /// `Z = 1 + residual[0][1]` or the residual isochoric heat capacity `Cvr / R = -residual[2][0]`.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `D`: Molar density [mol/L].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `HelmholtzDerivatives`: The reducing parameters and the derivative matrices.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let derivatives = RefpropFunctionLibrary::helmholtz_derivatives(300.0, 0.05, &z)?;
+    ///     println!("Compressibility factor: {}", 1.0 + derivatives.residual[0][1]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn helmholtz_derivatives(
+        T: f64,
+        D: f64,
+        z: &[f64],
+    ) -> Result<HelmholtzDerivatives, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        let (Tred, Dred) = reducing_parameters_unlocked(&lock, &z_buffer);
+        let tau = Tred / T;
+        let delta = D / Dred;
+
+        let mut residual = [[f64::NAN; 4]; 4];
+        let mut ideal = [[f64::NAN; 4]; 4];
+        for itau in 0..=MAX_ORDER {
+            for idel in 0..=(MAX_ORDER - itau) {
+                let (i, j) = (itau as usize, idel as usize);
+                residual[i][j] = phix_unlocked(&lock, itau, idel, tau, delta, &z_buffer);
+                ideal[i][j] = phi0_unlocked(&lock, itau, idel, T, D, &z_buffer);
+            }
+        }
+
+        Ok(HelmholtzDerivatives {
+            Tred,
+            Dred,
+            tau,
+            delta,
+            residual,
+            ideal,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helmholtz_derivatives() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        RefpropFunctionLibrary::set_fluids("NITROGEN")?;
+        let z = [1.0];
+
+        // The residual part reproduces the pressure of a TD flash
+        let (T, D) = (300.0, 1.0);
+        let state = RefpropFunctionLibrary::td_flash(T, D, z)?;
+        let derivatives = RefpropFunctionLibrary::helmholtz_derivatives(T, D, &z)?;
+        let R = 8.314462618;
+        let P = D * R * T * (1.0 + derivatives.residual[0][1]);
+        assert!((P - state.P).abs() / state.P < 1e-4);
+        assert!(derivatives.residual[3][1].is_nan());
+
+        let phixx = RefpropFunctionLibrary::phix(0, 1, derivatives.tau, derivatives.delta, &z)?;
+        assert_eq!(phixx, derivatives.residual[0][1]);
+
+        Ok(())
+    }
+}
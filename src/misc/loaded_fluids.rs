@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+
+use crate::{
+    setup::set_mixture::mixture_composition, utils::acquire_lock, RefpropError,
+    RefpropFunctionLibrary,
+};
+
+/// Component loaded in REFPROP, part of `LoadedFluids`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadedComponent {
+    /// Short name of the component (e.g., "R32")
+    pub name: String,
+    /// Full name of the component (e.g., "difluoromethane")
+    pub full_name: String,
+    /// Chemical Abstracts Service number
+    pub cas: String,
+    /// Path of the fluid file the component was loaded from
+    pub path: PathBuf,
+}
+
+/// Fluids currently loaded in REFPROP, returned by `loaded_fluids`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadedFluids {
+    /// Loaded components, in the order they were loaded
+    pub components: Vec<LoadedComponent>,
+    /// Mole fractions set by the mixture file, or `None` if the fluids were loaded with `set_fluids`
+    pub composition: Option<Vec<f64>>,
+}
+
+impl LoadedComponent {
+    /// Returns whether `fluid` is the short name, full name, CAS number, or fluid file name of the
+    /// component, ignoring case.
+    pub fn matches(&self, fluid: &str) -> bool {
+        let file_name = self.path.file_stem().and_then(|stem| stem.to_str());
+        self.name.eq_ignore_ascii_case(fluid)
+            || self.full_name.eq_ignore_ascii_case(fluid)
+            || self.cas == fluid
+            || file_name.is_some_and(|stem| stem.eq_ignore_ascii_case(fluid))
+    }
+}
+
+impl LoadedFluids {
+    /// Returns the number of loaded components.
+    pub fn ncomp(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns the short names of the loaded components.
+    pub fn names(&self) -> Vec<&str> {
+        self.components
+            .iter()
+            .map(|component| component.name.as_str())
+            .collect()
+    }
+
+    /// Returns the index (0-based) of the first component matching `fluid` (see `LoadedComponent::matches`).
+    pub fn position(&self, fluid: &str) -> Option<usize> {
+        self.components
+            .iter()
+            .position(|component| component.matches(fluid))
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Returns the components currently loaded in REFPROP, using the `NAMEdll` subroutine.
+    ///
+    /// The names, CAS numbers, and fluid file paths are read while holding the REFPROP lock, so they
+    /// describe a single setup even if other threads load fluids concurrently.
+    ///
+    /// # Returns
+    ///
+    /// - `LoadedFluids`: The loaded components and, if a mixture file was loaded with `set_mixture`,
+    ///   its composition.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::Utf8Error` if the names cannot be converted to UTF-8.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    ///
+    /// # References
+    ///
+    /// - [REFPROP Documentation - NAMEdll](https://pages.nist.gov/RefProp/documentation.html)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let loaded = RefpropFunctionLibrary::loaded_fluids()?;
+    ///     assert_eq!(loaded.names(), ["R32", "R1234yf"]);
+    ///     assert_eq!(loaded.position("R1234YF"), Some(1));
+    ///     println!("Composition: {:?}", loaded.composition);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn loaded_fluids() -> Result<LoadedFluids, RefpropError> {
+        // Acquire the mutex lock so that the components and composition belong to the same setup
+        let _lock = acquire_lock()?;
+
+        let mut components = Vec::new();
+        for icomp in 1..=20 {
+            let output = Self::name(icomp)?;
+            if output.hnam.is_empty() {
+                break;
+            }
+            components.push(LoadedComponent {
+                name: output.hnam,
+                full_name: output.hn80,
+                cas: output.hcasn,
+                path: PathBuf::from(Self::get_filename(icomp as usize)?),
+            });
+        }
+
+        Ok(LoadedFluids {
+            components,
+            composition: mixture_composition(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loaded_fluids() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+        let loaded = RefpropFunctionLibrary::loaded_fluids()?;
+        assert_eq!(loaded.ncomp(), 2);
+        assert_eq!(loaded.names(), ["R32", "R1234yf"]);
+        assert_eq!(loaded.position("75-10-5"), Some(0));
+        assert_eq!(loaded.position("r1234yf"), Some(1));
+        assert_eq!(loaded.composition, Some(z));
+        assert!(!loaded.components[0].path.as_os_str().is_empty());
+
+        RefpropFunctionLibrary::set_fluids("R32")?;
+        let loaded = RefpropFunctionLibrary::loaded_fluids()?;
+        assert_eq!(loaded.names(), ["R32"]);
+        assert_eq!(loaded.composition, None);
+
+        Ok(())
+    }
+}
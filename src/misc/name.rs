@@ -92,11 +92,27 @@ impl RefpropFunctionLibrary {
 
         return Ok(output.hn80);
     }
+
+    /// Returns the short names of the components currently loaded in REFPROP, in the order they were loaded.
+    pub(crate) fn loaded_component_names() -> Result<Vec<String>, RefpropError> {
+        let mut names = Vec::new();
+        for icomp in 1..=20 {
+            let output = Self::name(icomp)?;
+            if output.hnam.is_empty() {
+                break;
+            }
+            names.push(output.hnam);
+        }
+
+        Ok(names)
+    }
 }
 
 /// Struct representing the output of the `name` function.
 ///
 /// Contains the component name (`hnam`), long form name (`hn80`), and Chemical Abstracts Service number (`hcasn`).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NameOutput {
     /// Component name (character*12)
     pub hnam: String,
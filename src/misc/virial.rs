@@ -0,0 +1,442 @@
+use crate::{
+    bindings,
+    lock::RefpropGuard,
+    utils::{acquire_lock, check_refprop_error, padded_composition},
+    Phase, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Maximum number of iterations of the virial density estimate.
+const MAX_ITERATIONS: usize = 50;
+
+/// Virial coefficients at a given temperature, returned by `virial_coefficients`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VirialOutput {
+    /// Second virial coefficient [L/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "B_L_per_mol"))]
+    pub B: f64,
+    /// Third virial coefficient [(L/mol)^2]
+    #[cfg_attr(feature = "serde", serde(rename = "C_L2_per_mol2"))]
+    pub C: f64,
+    /// Temperature derivative of the second virial coefficient [L/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "dBdT_L_per_mol_K"))]
+    pub dBdT: f64,
+    /// Temperature derivative of the third virial coefficient [(L/mol)^2/K]
+    #[cfg_attr(feature = "serde", serde(rename = "dCdT_L2_per_mol2_K"))]
+    pub dCdT: f64,
+}
+
+/// Excess properties of a mixture, returned by `excess_properties`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExcessOutput {
+    /// Molar density of the mixture [mol/L]
+    #[cfg_attr(feature = "serde", serde(rename = "D_mol_per_L"))]
+    pub D: f64,
+    /// Excess volume [L/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "vE_L_per_mol"))]
+    pub vE: f64,
+    /// Excess internal energy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "eE_J_per_mol"))]
+    pub eE: f64,
+    /// Excess enthalpy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "hE_J_per_mol"))]
+    pub hE: f64,
+    /// Excess entropy [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "sE_J_per_mol_K"))]
+    pub sE: f64,
+    /// Excess Helmholtz energy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "aE_J_per_mol"))]
+    pub aE: f64,
+    /// Excess Gibbs energy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "gE_J_per_mol"))]
+    pub gE: f64,
+}
+
+/// Signature shared by `VIRBdll`, `VIRCdll`, `DBDTdll`, `DCDTdll`, and `B12dll`.
+type VirialRoutine = unsafe extern "C" fn(*mut f64, *mut f64, *mut f64);
+
+/// Evaluates one of the virial routines at temperature `T`.
+fn virial_unlocked(_guard: &RefpropGuard, routine: VirialRoutine, T: f64, z: &[f64; 20]) -> f64 {
+    let mut T = T;
+    let mut z = *z;
+    let mut value: f64 = 0.0;
+
+    // Call the routine within an unsafe block
+    unsafe {
+        routine(&mut T as *mut f64, z.as_mut_ptr(), &mut value as *mut f64);
+    }
+
+    value
+}
+
+impl RefpropFunctionLibrary {
+    /// Computes the second virial coefficient using the `VIRBdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Second virial coefficient [L/mol].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    ///
+    /// # References
+    ///
+    /// - [VIRBdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/VIRBdll)
+    pub fn virial_b(T: f64, z: &[f64]) -> Result<f64, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(virial_unlocked(&lock, bindings::VIRBdll, T, &z_buffer))
+    }
+
+    /// Computes the third virial coefficient using the `VIRCdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Third virial coefficient [(L/mol)^2].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    ///
+    /// # References
+    ///
+    /// - [VIRCdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/VIRCdll)
+    pub fn virial_c(T: f64, z: &[f64]) -> Result<f64, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(virial_unlocked(&lock, bindings::VIRCdll, T, &z_buffer))
+    }
+
+    /// Computes the temperature derivative of the second virial coefficient using the `DBDTdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: dB/dT [L/mol-K].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    ///
+    /// # References
+    ///
+    /// - [DBDTdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/DBDTdll)
+    pub fn dbdt(T: f64, z: &[f64]) -> Result<f64, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(virial_unlocked(&lock, bindings::DBDTdll, T, &z_buffer))
+    }
+
+    /// Computes the temperature derivative of the third virial coefficient using the `DCDTdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: dC/dT [(L/mol)^2/K].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    ///
+    /// # References
+    ///
+    /// - [DCDTdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/DCDTdll)
+    pub fn dcdt(T: f64, z: &[f64]) -> Result<f64, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(virial_unlocked(&lock, bindings::DCDTdll, T, &z_buffer))
+    }
+
+    /// Computes the cross second virial coefficient of a binary mixture using the `B12dll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `z`: Composition array (slice of mole fractions) of the two loaded components.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Cross second virial coefficient B12 [L/mol].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid or does not have two components.
+    ///
+    /// # References
+    ///
+    /// - [B12dll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/B12dll)
+    pub fn b12(T: f64, z: &[f64]) -> Result<f64, RefpropError> {
+        if z.len() != 2 {
+            return Err(RefpropError::InvalidInput(format!(
+                "B12 requires a binary mixture, got {} components",
+                z.len()
+            )));
+        }
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(virial_unlocked(&lock, bindings::B12dll, T, &z_buffer))
+    }
+
+    /// Computes the second and third virial coefficients and their temperature derivatives at a given temperature.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `VirialOutput`: A struct containing B, C, dB/dT, and dC/dT.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let virial = RefpropFunctionLibrary::virial_coefficients(300.0, &z)?;
+    ///     println!("B = {} L/mol, C = {} (L/mol)^2", virial.B, virial.C);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn virial_coefficients(T: f64, z: &[f64]) -> Result<VirialOutput, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let z_buffer = padded_composition(&lock, z)?;
+
+        Ok(VirialOutput {
+            B: virial_unlocked(&lock, bindings::VIRBdll, T, &z_buffer),
+            C: virial_unlocked(&lock, bindings::VIRCdll, T, &z_buffer),
+            dBdT: virial_unlocked(&lock, bindings::DBDTdll, T, &z_buffer),
+            dCdT: virial_unlocked(&lock, bindings::DCDTdll, T, &z_buffer),
+        })
+    }
+
+    /// Estimates the gas density from the virial equation truncated after the third coefficient.
+    ///
+    /// Solves `P = D*R*T*(1 + B*D + C*D^2)` for the gas root by successive substitution, starting
+    /// from the ideal-gas density. The estimate is only meaningful at low to moderate densities.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `P`: Pressure [kPa].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Molar density [mol/L].
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid or `T` or `P` is not positive.
+    /// - Returns `RefpropError::CalculationError` if the iteration does not converge, e.g. above the
+    ///   range of validity of the truncated virial equation.
+    pub fn virial_density(T: f64, P: f64, z: &[f64]) -> Result<f64, RefpropError> {
+        if !(T > 0.0 && P > 0.0) {
+            return Err(RefpropError::InvalidInput(
+                "Temperature and pressure must be positive".to_string(),
+            ));
+        }
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let mut z_buffer = padded_composition(&lock, z)?;
+
+        let B = virial_unlocked(&lock, bindings::VIRBdll, T, &z_buffer);
+        let C = virial_unlocked(&lock, bindings::VIRCdll, T, &z_buffer);
+
+        // Gas constant of the mixture [J/mol-K]
+        let mut R: f64 = 0.0;
+        unsafe {
+            bindings::RMIX2dll(z_buffer.as_mut_ptr(), &mut R as *mut f64);
+        }
+
+        let ideal = P / (R * T);
+        let mut D = ideal;
+        for _ in 0..MAX_ITERATIONS {
+            let next = ideal / (1.0 + B * D + C * D * D);
+            if !next.is_finite() || next <= 0.0 {
+                break;
+            }
+            if (next - D).abs() <= 1e-12 * next {
+                return Ok(next);
+            }
+            D = next;
+        }
+
+        Err(RefpropError::CalculationError(format!(
+            "Virial density estimate did not converge at T = {} K and P = {} kPa",
+            T, P
+        )))
+    }
+
+    /// Computes the excess properties of a mixture at a given temperature and pressure using the `EXCESSdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K].
+    /// - `P`: Pressure [kPa].
+    /// - `z`: Composition array (slice of mole fractions). Maximum of 20 components.
+    /// - `phase`: `Phase::Liquid` or `Phase::Vapor` to select a root, or `Phase::Unknown` for the stable phase.
+    ///
+    /// # Returns
+    ///
+    /// - `ExcessOutput`: A struct containing the density and excess properties.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid or `phase` is `Phase::TwoPhase`.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation.
+    ///
+    /// # References
+    ///
+    /// - [EXCESSdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/EXCESSdll)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{Phase, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let excess = RefpropFunctionLibrary::excess_properties(250.0, 1000.0, &z, Phase::Liquid)?;
+    ///     println!("Excess enthalpy: {} J/mol", excess.hE);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn excess_properties(
+        T: f64,
+        P: f64,
+        z: &[f64],
+        phase: Phase,
+    ) -> Result<ExcessOutput, RefpropError> {
+        if matches!(phase, Phase::TwoPhase) {
+            return Err(RefpropError::InvalidInput(
+                "Excess properties require a single phase".to_string(),
+            ));
+        }
+        // Acquire the mutex lock to ensure exclusive access
+        let lock = acquire_lock()?;
+        let mut z_buffer = padded_composition(&lock, z)?;
+
+        // Define buffer sizes as per REFPROP's documentation
+        const HERR_LENGTH: usize = 255;
+
+        let mut T = T;
+        let mut P = P;
+        let mut kph = phase as i32;
+        let mut output = ExcessOutput {
+            D: 0.0,
+            vE: 0.0,
+            eE: 0.0,
+            hE: 0.0,
+            sE: 0.0,
+            aE: 0.0,
+            gE: 0.0,
+        };
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call EXCESSdll within an unsafe block
+        unsafe {
+            bindings::EXCESSdll(
+                &mut T as *mut f64,
+                &mut P as *mut f64,
+                z_buffer.as_mut_ptr(),
+                &mut kph as *mut i32,
+                &mut output.D as *mut f64,
+                &mut output.vE as *mut f64,
+                &mut output.eE as *mut f64,
+                &mut output.hE as *mut f64,
+                &mut output.sE as *mut f64,
+                &mut output.aE as *mut f64,
+                &mut output.gE as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                herr_length,
+            );
+        }
+
+        // Check ierr for errors
+        check_refprop_error(&lock, ierr, herr_ptr, herr_length)?;
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_virial_coefficients() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+
+        let virial = RefpropFunctionLibrary::virial_coefficients(300.0, &z)?;
+        assert!(virial.B < 0.0);
+        assert!(virial.dBdT > 0.0);
+        assert_eq!(virial.B, RefpropFunctionLibrary::virial_b(300.0, &z)?);
+
+        // The derivative of C agrees with a central difference
+        let dCdT = (RefpropFunctionLibrary::virial_c(300.01, &z)?
+            - RefpropFunctionLibrary::virial_c(299.99, &z)?)
+            / 0.02;
+        assert!((virial.dCdT - dCdT).abs() <= 1e-4 * dCdT.abs().max(1e-12));
+        assert_eq!(virial.dCdT, RefpropFunctionLibrary::dcdt(300.0, &z)?);
+
+        // The virial estimate is close to the full equation of state in the dilute gas
+        let estimate = RefpropFunctionLibrary::virial_density(300.0, 101.325, &z)?;
+        let state = RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z)?;
+        assert!((estimate - state.D).abs() / state.D < 1e-3);
+
+        assert!(RefpropFunctionLibrary::b12(300.0, &z)? < 0.0);
+        assert!(
+            RefpropFunctionLibrary::excess_properties(250.0, 1000.0, &z, Phase::TwoPhase).is_err()
+        );
+
+        Ok(())
+    }
+}
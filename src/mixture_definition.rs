@@ -0,0 +1,307 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    binary_parameters::{active_root, hmx_path},
+    utils::acquire_lock,
+    BinaryParameterFile, BinaryParameterSource, Composition, CompositionBasis, FluidCatalogue,
+    FluidFileKind, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Definition of a custom mixture by its components and their fractions.
+///
+/// The definition can be loaded into REFPROP directly with `load`, or written to a `.MIX` file that
+/// `set_mixture` accepts and `FluidCatalogue` lists. Components may be given by any name, synonym, or
+/// CAS number known to the catalogue of the REFPROP path set with `set_path`; both `load` and
+/// `write_mix_file` check that every binary pair has mixing parameters in `HMX.BNC`, so that no
+/// interaction parameters are estimated.
+///
+/// # Examples
+///
+/// ```rust
+/// use refprop_sys::{CompositionBasis, MixtureDefinition, RefpropFunctionLibrary, RefpropError};
+///
+/// fn main() -> Result<(), RefpropError> {
+///     let _ = RefpropFunctionLibrary::set_path(None);
+///
+///     // 68.9 / 31.1 % by mass, as specified for R454B
+///     let blend = MixtureDefinition::new("BLEND1")
+///         .component("R32", 68.9)
+///         .component("R1234YF", 31.1)
+///         .basis(CompositionBasis::Mass);
+///
+///     let z = blend.load()?;
+///     let output = RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z)?;
+///     println!("Density: {} mol/L", output.D);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MixtureDefinition {
+    name: String,
+    components: Vec<String>,
+    amounts: Vec<f64>,
+    basis: CompositionBasis,
+}
+
+/// Formats the contents of a `.MIX` file.
+fn mix_file_contents(
+    description: &str,
+    molar_mass: f64,
+    critical: [f64; 3],
+    components: &[String],
+    mole_fractions: &[f64],
+) -> String {
+    let mut contents = format!(
+        "{}\n{:.4}  {:.3}  {:.3}  {:.5}\n{}\n",
+        description,
+        molar_mass,
+        critical[0],
+        critical[1],
+        critical[2],
+        components.len()
+    );
+    for component in components {
+        contents.push_str(&format!("{}.FLD\n", component));
+    }
+    let fractions: Vec<String> = mole_fractions.iter().map(f64::to_string).collect();
+    contents.push_str(&fractions.join("  "));
+    contents.push_str("\n0\n");
+
+    contents
+}
+
+impl MixtureDefinition {
+    /// Creates an empty mixture definition with mole-based amounts.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the mixture, used as the name of its `.MIX` file.
+    pub fn new(name: impl Into<String>) -> Self {
+        MixtureDefinition {
+            name: name.into(),
+            components: Vec::new(),
+            amounts: Vec::new(),
+            basis: CompositionBasis::Mole,
+        }
+    }
+
+    /// Adds a component with its amount.
+    ///
+    /// The amounts are normalised to fractions, so they may be given as fractions or percentages.
+    ///
+    /// # Parameters
+    ///
+    /// - `fluid`: Name, synonym, or CAS number of the fluid (e.g., "R32", "HFC-32", or "75-10-5").
+    /// - `amount`: Amount of the component on the basis of the definition.
+    pub fn component(mut self, fluid: impl Into<String>, amount: f64) -> Self {
+        self.components.push(fluid.into());
+        self.amounts.push(amount);
+        self
+    }
+
+    /// Sets the basis on which the amounts are expressed (mole-based by default).
+    pub fn basis(mut self, basis: CompositionBasis) -> Self {
+        self.basis = basis;
+        self
+    }
+
+    /// Returns the name of the mixture.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the components as given to `component`.
+    pub fn components(&self) -> &[String] {
+        &self.components
+    }
+
+    /// Returns the composition of the mixture, normalised and named after the fluid files of its components.
+    ///
+    /// The components are resolved with `validate`, so a component given by a synonym or CAS number is
+    /// named after its fluid file and the composition can be used once the mixture is loaded.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `validate`.
+    pub fn composition(&self) -> Result<Composition, RefpropError> {
+        let fluids = self.validate()?;
+        Composition::normalized(self.amounts.clone(), self.basis)?.with_components(fluids)
+    }
+
+    /// Resolves the components to fluid files of the catalogue and checks their binary pairs.
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<String>`: The fluid files of the components, without extension.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InitializationError` if `set_path` has not been called.
+    /// - Returns `RefpropError::InvalidInput` if:
+    ///     - The name is empty or contains whitespace or path separators.
+    ///     - The amounts are invalid.
+    ///     - A component is not a fluid file of the catalogue.
+    ///     - A binary pair has no mixing parameters in `HMX.BNC`.
+    pub fn validate(&self) -> Result<Vec<String>, RefpropError> {
+        if self.name.is_empty()
+            || self
+                .name
+                .chars()
+                .any(|c| c.is_whitespace() || matches!(c, '/' | '\\' | '.'))
+        {
+            return Err(RefpropError::InvalidInput(format!(
+                "Invalid mixture name '{}'",
+                self.name
+            )));
+        }
+        Composition::normalized(self.amounts.clone(), self.basis)?;
+
+        let root = active_root()?;
+        let catalogue = FluidCatalogue::scan(&root)?;
+
+        let mut fluids = Vec::with_capacity(self.components.len());
+        for component in &self.components {
+            let fluid = catalogue
+                .find_fluid(component)
+                .filter(|fluid| fluid.kind == FluidFileKind::Fluid)
+                .ok_or_else(|| {
+                    RefpropError::InvalidInput(format!("Unknown fluid '{}'", component))
+                })?;
+            fluids.push(fluid);
+        }
+
+        if fluids.len() > 1 {
+            let coverage = BinaryParameterFile::read(hmx_path(&root)?)?.coverage(&fluids);
+            let missing: Vec<String> = coverage
+                .with_source(BinaryParameterSource::Missing)
+                .map(|pair| format!("{}/{}", pair.first, pair.second))
+                .collect();
+            if !missing.is_empty() {
+                return Err(RefpropError::InvalidInput(format!(
+                    "No binary mixing parameters in HMX.BNC for {}",
+                    missing.join(", ")
+                )));
+            }
+        }
+
+        Ok(fluids
+            .into_iter()
+            .map(|fluid| fluid.file_name.clone())
+            .collect())
+    }
+
+    /// Loads the mixture into REFPROP with `set_fluids` and returns its mole fractions.
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<f64>`: The mole fractions of the components.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `validate` and `set_fluids`, and of the conversion of mass fractions.
+    pub fn load(&self) -> Result<Vec<f64>, RefpropError> {
+        let fluids = self.validate()?;
+        self.load_fluids(&fluids)
+    }
+
+    /// Loads the validated fluid files of the components and returns the mole fractions.
+    fn load_fluids(&self, fluids: &[String]) -> Result<Vec<f64>, RefpropError> {
+        // Keep the loaded fluids fixed while converting the composition
+        let _lock = acquire_lock()?;
+
+        RefpropFunctionLibrary::set_fluids(&fluids.join(";"))?;
+        Composition::normalized(self.amounts.clone(), self.basis)?
+            .with_components(fluids.to_vec())?
+            .mole_fractions()
+    }
+
+    /// Loads the mixture and returns the contents of its `.MIX` file.
+    ///
+    /// The molar mass and the estimated critical point written to the file are calculated by REFPROP.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `load` and `critical_parameters`.
+    pub fn to_mix_string(&self) -> Result<String, RefpropError> {
+        let fluids = self.validate()?;
+
+        // Keep the loaded fluids fixed while evaluating the mixture
+        let _lock = acquire_lock()?;
+
+        let z = self.load_fluids(&fluids)?;
+        let molar_mass = RefpropFunctionLibrary::calc_molar_mass(&z)?;
+        let critical = RefpropFunctionLibrary::critical_parameters(&z)?;
+        let description = format!("{}  [{}]", self.name, fluids.join("/"));
+
+        Ok(mix_file_contents(
+            &description,
+            molar_mass,
+            [critical.Tc, critical.Pc, critical.Dc],
+            &fluids,
+            &z,
+        ))
+    }
+
+    /// Writes the `.MIX` file of the mixture into a directory.
+    ///
+    /// Writing into the `MIXTURES` directory of the REFPROP path makes the mixture available to
+    /// `set_mixture` by name; otherwise `set_mixture` accepts the full path of the file.
+    ///
+    /// # Parameters
+    ///
+    /// - `directory`: The directory to write `<name>.MIX` into.
+    ///
+    /// # Returns
+    ///
+    /// - `PathBuf`: The path of the written file.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `to_mix_string`.
+    /// - Returns `RefpropError::InvalidInput` if the file cannot be written.
+    pub fn write_mix_file(&self, directory: impl AsRef<Path>) -> Result<PathBuf, RefpropError> {
+        let contents = self.to_mix_string()?;
+        let path = directory
+            .as_ref()
+            .join(format!("{}.MIX", self.name.to_uppercase()));
+        fs::write(&path, contents).map_err(|e| {
+            RefpropError::InvalidInput(format!("Cannot write {}: {}", path.display(), e))
+        })?;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MixtureEntry;
+
+    #[test]
+    fn test_mix_file_round_trip() {
+        let components = vec!["R32".to_string(), "R1234YF".to_string()];
+        let z = [0.8295243781, 0.1704756219];
+        let contents = mix_file_contents(
+            "BLEND1  [R32/R1234YF]",
+            62.6144,
+            [352.95, 5271.3, 6.885],
+            &components,
+            &z,
+        );
+
+        let entry = MixtureEntry::parse(&contents, "BLEND1", PathBuf::from("BLEND1.MIX")).unwrap();
+        assert!(entry.matches("blend1"));
+        assert_eq!(entry.components, components);
+        assert_eq!(entry.composition, z);
+        assert_eq!(entry.molar_mass, 62.6144);
+
+        assert!(MixtureDefinition::new("BAD NAME")
+            .component("R32", 1.0)
+            .validate()
+            .is_err());
+    }
+}
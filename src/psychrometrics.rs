@@ -0,0 +1,741 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    lock::RefpropGuard,
+    utils::{acquire_lock, fluids_generation},
+    Basis, KrKqFlag, Phase, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Fluids loaded by `set_moist_air`, with water as the last component.
+const MOIST_AIR_FLUIDS: &str = "NITROGEN;ARGON;OXYGEN;WATER";
+
+/// Fluids generation at which the moist air mixture was last found loaded, plus one (0 if never).
+static VERIFIED_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Composition of dry air (mole fractions of nitrogen, argon, and oxygen), following Lemmon et al. (2000).
+const DRY_AIR: [f64; 3] = [0.7812, 0.0092, 0.2096];
+
+/// Triple point temperature of water [K].
+const T_TRIPLE_WATER: f64 = 273.16;
+
+/// Temperature of the dry air reference state (zero enthalpy) [K].
+const T_REFERENCE_AIR: f64 = 273.15;
+
+/// Relative tolerance of the iterative solvers.
+const TOLERANCE: f64 = 1e-9;
+
+/// Maximum number of iterations of the iterative solvers.
+const MAX_ITERATIONS: usize = 100;
+
+/// Represents the state of moist air calculated by the `moist_air_state` method.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoistAirState {
+    /// Dry-bulb temperature [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_K"))]
+    pub T: f64,
+    /// Pressure [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "P_kPa"))]
+    pub P: f64,
+    /// Humidity ratio [kg water/kg dry air]
+    #[cfg_attr(feature = "serde", serde(rename = "W_kg_per_kg_dry_air"))]
+    pub W: f64,
+    /// Mole fraction of water [mol/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "x_w_mol_per_mol"))]
+    pub x_w: f64,
+    /// Relative humidity (ratio of the water mole fraction to its value at saturation) [-]
+    pub phi: f64,
+    /// Dew point (frost point below the triple point of water) temperature [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_dp_K"))]
+    pub T_dp: f64,
+    /// Thermodynamic wet-bulb temperature [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_wb_K"))]
+    pub T_wb: f64,
+    /// Enthalpy [J/kg dry air]
+    #[cfg_attr(feature = "serde", serde(rename = "h_J_per_kg_dry_air"))]
+    pub h: f64,
+    /// Specific volume [m³/kg dry air]
+    #[cfg_attr(feature = "serde", serde(rename = "v_m3_per_kg_dry_air"))]
+    pub v: f64,
+}
+
+impl RefpropFunctionLibrary {
+    /// Loads the moist air mixture (nitrogen, argon, oxygen, and water) used by the psychrometric methods.
+    ///
+    /// Dry air is represented by its three main constituents so that the air–water interaction
+    /// parameters of REFPROP are used; water is the last component of the mixture. The psychrometric
+    /// methods check that this mixture is loaded and return an error otherwise.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::CalculationError` if REFPROP fails to load the fluids.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn set_moist_air() -> Result<(), RefpropError> {
+        Self::set_fluids(MOIST_AIR_FLUIDS)
+    }
+
+    /// Calculates the saturation pressure of pure water over liquid water, or over ice below the triple point.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Saturation (or sublimation) pressure of water [kPa]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn water_saturation_pressure(T: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        let z = moist_air_composition(1.0);
+        let output = if T >= T_TRIPLE_WATER {
+            Self::tq_flash(
+                T,
+                0.0,
+                &z,
+                Basis::Molar,
+                Phase::Unknown,
+                KrKqFlag::QualityMolar,
+            )?
+        } else {
+            // b = -98 selects the sublimation line
+            Self::ab_flash(
+                "TQ",
+                T,
+                -98.0,
+                &z,
+                Basis::Molar,
+                Phase::Unknown,
+                KrKqFlag::Default,
+            )?
+        };
+
+        Ok(output.P)
+    }
+
+    /// Calculates the enhancement factor of water vapor in air.
+    ///
+    /// The enhancement factor is the ratio of the saturated water mole fraction of moist air to its
+    /// ideal value `p_ws(T) / P`. It is obtained from the dew point of the air–water mixture, so it
+    /// accounts for the non-ideality of the vapor phase and the solubility of air in liquid water.
+    ///
+    /// **Note:** REFPROP does not model the equilibrium of moist air with ice. Below the triple point
+    /// of water, the enhancement factor at the triple point is used.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K]
+    /// - `P`: Pressure [kPa]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Enhancement factor [-]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error or the iteration does not converge.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn enhancement_factor(T: f64, P: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        let T = T.max(T_TRIPLE_WATER);
+        let p_ws = Self::water_saturation_pressure(T)?;
+        let x_ws = Self::saturated_dew_mole_fraction(T, P, p_ws)?;
+
+        Ok(x_ws * P / p_ws)
+    }
+
+    /// Calculates the humidity ratio of saturated moist air.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Temperature [K]
+    /// - `P`: Pressure [kPa]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Humidity ratio at saturation [kg water/kg dry air]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if the saturated water mole fraction is not below 1 (i.e., `T` is above the boiling point at `P`).
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn saturation_humidity_ratio(T: f64, P: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        let x_ws = Self::saturated_water_mole_fraction(T, P)?;
+        Self::humidity_ratio_from_mole_fraction(x_ws)
+    }
+
+    /// Calculates the humidity ratio from the relative humidity.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Dry-bulb temperature [K]
+    /// - `P`: Pressure [kPa]
+    /// - `phi`: Relative humidity [-], between 0 and 1
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if `phi` is outside `[0, 1]`.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn humidity_ratio_from_relative_humidity(
+        T: f64,
+        P: f64,
+        phi: f64,
+    ) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        if !(0.0..=1.0).contains(&phi) {
+            return Err(RefpropError::InvalidInput(format!(
+                "Relative humidity must be between 0 and 1. Provided: {}",
+                phi
+            )));
+        }
+
+        let x_ws = Self::saturated_water_mole_fraction(T, P)?;
+        Self::humidity_ratio_from_mole_fraction(phi * x_ws)
+    }
+
+    /// Calculates the relative humidity from the humidity ratio.
+    ///
+    /// The relative humidity is defined as the ratio of the water mole fraction to the water mole
+    /// fraction of saturated moist air at the same temperature and pressure.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Dry-bulb temperature [K]
+    /// - `P`: Pressure [kPa]
+    /// - `W`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Relative humidity [-]; values above 1 indicate supersaturated air
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if `W` is negative.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn relative_humidity(T: f64, P: f64, W: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        let x_w = Self::water_mole_fraction(W)?;
+        let x_ws = Self::saturated_water_mole_fraction(T, P)?;
+
+        Ok(x_w / x_ws)
+    }
+
+    /// Calculates the dew point temperature of moist air.
+    ///
+    /// Above the triple point of water, the dew point is the dew temperature of the air–water mixture
+    /// from `pq_flash`. Below it, the frost point over ice is returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `P`: Pressure [kPa]
+    /// - `W`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Dew (or frost) point temperature [K]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if `W` is not positive.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error or the iteration does not converge.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn dew_point(P: f64, W: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        if W <= 0.0 {
+            return Err(RefpropError::InvalidInput(format!(
+                "Humidity ratio must be positive to define a dew point. Provided: {}",
+                W
+            )));
+        }
+        let x_w = Self::water_mole_fraction(W)?;
+
+        // Dew point over liquid water from the mixture dew line
+        let dew = Self::pq_flash(
+            P,
+            1.0,
+            moist_air_composition(x_w),
+            Basis::Molar,
+            Phase::Unknown,
+            KrKqFlag::QualityMolar,
+        );
+        if let Ok(dew) = dew {
+            if dew.T >= T_TRIPLE_WATER {
+                return Ok(dew.T);
+            }
+        }
+
+        // Frost point over ice: x_ws(T) increases monotonically with T
+        let f = Self::enhancement_factor(T_TRIPLE_WATER, P)?;
+        solve(
+            |T| Ok((f * Self::water_saturation_pressure(T)? / P).ln() - x_w.ln()),
+            150.0,
+            T_TRIPLE_WATER,
+        )
+    }
+
+    /// Calculates the humidity ratio of moist air with the given dew point.
+    ///
+    /// # Parameters
+    ///
+    /// - `T_dp`: Dew (or frost) point temperature [K]
+    /// - `P`: Pressure [kPa]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn humidity_ratio_from_dew_point(T_dp: f64, P: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        Self::saturation_humidity_ratio(T_dp, P)
+    }
+
+    /// Calculates the enthalpy of moist air per unit mass of dry air.
+    ///
+    /// The enthalpy is referenced to dry air at 0 °C and liquid water at the triple point, as is
+    /// customary for psychrometric charts, rather than to the reference states of the fluid files.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Dry-bulb temperature [K]
+    /// - `P`: Pressure [kPa]
+    /// - `W`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Enthalpy [J/kg dry air]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if `W` is negative.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error during calculation.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn moist_air_enthalpy(T: f64, P: f64, W: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        let x_w = Self::water_mole_fraction(W)?;
+        let mixture = Self::tp_flash(T, P, moist_air_composition(x_w))?;
+        let (h_air, h_water) = Self::psychrometric_reference_enthalpies(P)?;
+        let M_air = Self::calc_molar_mass(&moist_air_composition(0.0))?;
+
+        // Per mole of mixture: subtract the reference enthalpies and divide by the mass of dry air [kg]
+        let h_mole = mixture.h - (1.0 - x_w) * h_air - x_w * h_water;
+        Ok(h_mole / ((1.0 - x_w) * M_air / 1000.0))
+    }
+
+    /// Calculates the dry-bulb temperature of moist air from its enthalpy and humidity ratio.
+    ///
+    /// # Parameters
+    ///
+    /// - `P`: Pressure [kPa]
+    /// - `h`: Enthalpy [J/kg dry air], with the reference of `moist_air_enthalpy`
+    /// - `W`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Dry-bulb temperature [K]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if `W` is negative.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error or no temperature between 150 K and 500 K matches `h`.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn moist_air_temperature(P: f64, h: f64, W: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        solve(|T| Ok(Self::moist_air_enthalpy(T, P, W)? - h), 150.0, 500.0)
+    }
+
+    /// Calculates the thermodynamic wet-bulb (adiabatic saturation) temperature of moist air.
+    ///
+    /// The wet-bulb temperature `T_wb` satisfies the adiabatic saturation energy balance
+    /// `h(T, W) + (W_s(T_wb) - W) h_w(T_wb) = h(T_wb, W_s(T_wb))`, where `h_w` is the enthalpy of
+    /// liquid water (supercooled below the triple point).
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Dry-bulb temperature [K]
+    /// - `P`: Pressure [kPa]
+    /// - `W`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Wet-bulb temperature [K]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if `W` is negative or the air is supersaturated.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error or the iteration does not converge.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn wet_bulb(T: f64, P: f64, W: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        if W > Self::saturation_humidity_ratio(T, P)? {
+            return Err(RefpropError::InvalidInput(format!(
+                "Moist air with humidity ratio {} is supersaturated at {} K.",
+                W, T
+            )));
+        }
+
+        let h = Self::moist_air_enthalpy(T, P, W)?;
+        let T_low = if W > 0.0 {
+            Self::dew_point(P, W)?
+        } else {
+            150.0
+        };
+
+        solve(
+            |T_wb| {
+                let W_s = Self::saturation_humidity_ratio(T_wb, P)?;
+                Ok(Self::moist_air_enthalpy(T_wb, P, W_s)?
+                    - h
+                    - (W_s - W) * Self::liquid_water_enthalpy(T_wb, P)?)
+            },
+            T_low,
+            T,
+        )
+    }
+
+    /// Calculates the humidity ratio of moist air from its dry-bulb and wet-bulb temperatures.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Dry-bulb temperature [K]
+    /// - `P`: Pressure [kPa]
+    /// - `T_wb`: Thermodynamic wet-bulb temperature [K]
+    ///
+    /// # Returns
+    ///
+    /// - `f64`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if `T_wb` is above `T`.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error or the iteration does not converge.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn humidity_ratio_from_wet_bulb(T: f64, P: f64, T_wb: f64) -> Result<f64, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        if T_wb > T {
+            return Err(RefpropError::InvalidInput(format!(
+                "Wet-bulb temperature {} K is above the dry-bulb temperature {} K.",
+                T_wb, T
+            )));
+        }
+
+        let W_s = Self::saturation_humidity_ratio(T_wb, P)?;
+        let h_s = Self::moist_air_enthalpy(T_wb, P, W_s)?;
+        let h_w = Self::liquid_water_enthalpy(T_wb, P)?;
+
+        // The residual of the energy balance increases monotonically with W
+        solve(
+            |W| Ok(Self::moist_air_enthalpy(T, P, W)? + (W_s - W) * h_w - h_s),
+            0.0,
+            W_s,
+        )
+    }
+
+    /// Calculates the complete psychrometric state of moist air.
+    ///
+    /// # Parameters
+    ///
+    /// - `T`: Dry-bulb temperature [K]
+    /// - `P`: Pressure [kPa]
+    /// - `W`: Humidity ratio [kg water/kg dry air]
+    ///
+    /// # Returns
+    ///
+    /// - `MoistAirState`: A struct containing the humidity variables, enthalpy, and specific volume.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the moist air mixture of `set_moist_air` is not loaded.
+    /// - Returns `RefpropError::InvalidInput` if `W` is negative or the air is supersaturated.
+    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error or an iteration does not converge.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     RefpropFunctionLibrary::set_moist_air()?;
+    ///
+    ///     // 25 °C, 50 % relative humidity at standard pressure
+    ///     let W = RefpropFunctionLibrary::humidity_ratio_from_relative_humidity(298.15, 101.325, 0.5)?;
+    ///     let state = RefpropFunctionLibrary::moist_air_state(298.15, 101.325, W)?;
+    ///     println!("Dew point: {} K, wet bulb: {} K", state.T_dp, state.T_wb);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn moist_air_state(T: f64, P: f64, W: f64) -> Result<MoistAirState, RefpropError> {
+        let _lock = Self::moist_air_lock()?;
+        let x_w = Self::water_mole_fraction(W)?;
+        let mixture = Self::tp_flash(T, P, moist_air_composition(x_w))?;
+        let M_air = Self::calc_molar_mass(&moist_air_composition(0.0))?;
+
+        let T_wb = Self::wet_bulb(T, P, W)?;
+        let T_dp = if W > 0.0 {
+            Self::dew_point(P, W)?
+        } else {
+            f64::NEG_INFINITY
+        };
+
+        Ok(MoistAirState {
+            T,
+            P,
+            W,
+            x_w,
+            phi: Self::relative_humidity(T, P, W)?,
+            T_dp,
+            T_wb,
+            h: Self::moist_air_enthalpy(T, P, W)?,
+            // D [mol/L] = D [kmol/m³]; dry air per kmol of mixture is (1 - x_w) * M_air [kg]
+            v: 1.0 / (mixture.D * (1.0 - x_w) * M_air),
+        })
+    }
+
+    /// Acquires the REFPROP lock after checking that the moist air mixture of `set_moist_air` is loaded.
+    fn moist_air_lock() -> Result<RefpropGuard, RefpropError> {
+        let lock = acquire_lock()?;
+
+        // The check is repeated only after the loaded fluids have changed
+        let generation = fluids_generation();
+        if VERIFIED_GENERATION.load(Ordering::SeqCst) != generation + 1 {
+            let names = Self::loaded_component_names()?;
+            let expected = MOIST_AIR_FLUIDS.split(';');
+            let is_moist_air = names.len() == expected.clone().count()
+                && names
+                    .iter()
+                    .zip(expected)
+                    .all(|(name, fluid)| name.eq_ignore_ascii_case(fluid));
+            if !is_moist_air || Self::pure_component()?.is_some() {
+                return Err(RefpropError::InvalidInput(format!(
+                    "The psychrometric functions require the mixture loaded by set_moist_air; loaded: {}",
+                    names.join(", ")
+                )));
+            }
+            VERIFIED_GENERATION.store(generation + 1, Ordering::SeqCst);
+        }
+
+        Ok(lock)
+    }
+
+    /// Calculates the water mole fraction of saturated moist air.
+    fn saturated_water_mole_fraction(T: f64, P: f64) -> Result<f64, RefpropError> {
+        let p_ws = Self::water_saturation_pressure(T)?;
+        let x_ws = if T >= T_TRIPLE_WATER {
+            Self::saturated_dew_mole_fraction(T, P, p_ws)?
+        } else {
+            Self::enhancement_factor(T_TRIPLE_WATER, P)? * p_ws / P
+        };
+
+        if x_ws >= 1.0 {
+            return Err(RefpropError::InvalidInput(format!(
+                "Water boils at {} K and {} kPa; moist air cannot be saturated.",
+                T, P
+            )));
+        }
+
+        Ok(x_ws)
+    }
+
+    /// Finds the water mole fraction whose mixture dew temperature at `P` equals `T`.
+    fn saturated_dew_mole_fraction(T: f64, P: f64, p_ws: f64) -> Result<f64, RefpropError> {
+        // The ideal value is within a fraction of a percent of the solution at moderate pressures
+        let x_ideal = (p_ws / P).min(0.999);
+        solve(
+            |ln_x| {
+                let dew = Self::pq_flash(
+                    P,
+                    1.0,
+                    moist_air_composition(ln_x.exp()),
+                    Basis::Molar,
+                    Phase::Unknown,
+                    KrKqFlag::QualityMolar,
+                )?;
+                Ok(dew.T - T)
+            },
+            (0.9 * x_ideal).ln(),
+            (1.1 * x_ideal).min(0.999).ln(),
+        )
+        .map(f64::exp)
+    }
+
+    /// Returns the reference enthalpies [J/mol] of dry air at 0 °C and liquid water at the triple point.
+    fn psychrometric_reference_enthalpies(P: f64) -> Result<(f64, f64), RefpropError> {
+        let air = Self::tp_flash(T_REFERENCE_AIR, P, moist_air_composition(0.0))?;
+        let water = Self::tq_flash(
+            T_TRIPLE_WATER,
+            0.0,
+            moist_air_composition(1.0),
+            Basis::Molar,
+            Phase::Unknown,
+            KrKqFlag::QualityMolar,
+        )?;
+
+        Ok((air.h, water.h))
+    }
+
+    /// Calculates the enthalpy of liquid water [J/kg] with the psychrometric reference.
+    fn liquid_water_enthalpy(T: f64, P: f64) -> Result<f64, RefpropError> {
+        let z = moist_air_composition(1.0);
+        let water = Self::tp_flash(T, P, &z)?;
+        let (_, h_water) = Self::psychrometric_reference_enthalpies(P)?;
+        let M_water = Self::calc_molar_mass(&z)?;
+
+        Ok((water.h - h_water) / (M_water / 1000.0))
+    }
+
+    /// Converts a humidity ratio into a water mole fraction.
+    fn water_mole_fraction(W: f64) -> Result<f64, RefpropError> {
+        if W < 0.0 {
+            return Err(RefpropError::InvalidInput(format!(
+                "Humidity ratio must not be negative. Provided: {}",
+                W
+            )));
+        }
+        let epsilon = Self::molar_mass_ratio()?;
+
+        Ok(W / (W + epsilon))
+    }
+
+    /// Converts a water mole fraction into a humidity ratio.
+    fn humidity_ratio_from_mole_fraction(x_w: f64) -> Result<f64, RefpropError> {
+        let epsilon = Self::molar_mass_ratio()?;
+
+        Ok(epsilon * x_w / (1.0 - x_w))
+    }
+
+    /// Returns the ratio of the molar masses of water and dry air.
+    fn molar_mass_ratio() -> Result<f64, RefpropError> {
+        let M_water = Self::calc_molar_mass(&moist_air_composition(1.0))?;
+        let M_air = Self::calc_molar_mass(&moist_air_composition(0.0))?;
+
+        Ok(M_water / M_air)
+    }
+}
+
+/// Builds the composition of the moist air mixture for a given water mole fraction.
+fn moist_air_composition(x_w: f64) -> Vec<f64> {
+    let mut z = DRY_AIR
+        .iter()
+        .map(|&zi| zi * (1.0 - x_w))
+        .collect::<Vec<f64>>();
+    z.push(x_w);
+    z
+}
+
+/// Finds a root of `f` in `[a, b]` with the Illinois variant of the regula falsi method.
+///
+/// If `f(a)` and `f(b)` have the same sign, the secant method is used from the two end points instead.
+fn solve<F>(mut f: F, a: f64, b: f64) -> Result<f64, RefpropError>
+where
+    F: FnMut(f64) -> Result<f64, RefpropError>,
+{
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a)?, f(b)?);
+    let bracketed = fa * fb <= 0.0;
+
+    for _ in 0..MAX_ITERATIONS {
+        if fa == 0.0 {
+            return Ok(a);
+        }
+        if fb == 0.0 {
+            return Ok(b);
+        }
+
+        let c = b - fb * (b - a) / (fb - fa);
+        if !c.is_finite() {
+            break;
+        }
+        let fc = f(c)?;
+        if (c - b).abs() <= TOLERANCE * c.abs().max(1.0) || fc == 0.0 {
+            return Ok(c);
+        }
+
+        if !bracketed || fc * fb < 0.0 {
+            (a, fa) = (b, fb);
+        } else {
+            // Halve the weight of the retained end point to avoid stagnation
+            fa /= 2.0;
+        }
+        (b, fb) = (c, fc);
+    }
+
+    Err(RefpropError::CalculationError(
+        "Psychrometric iteration did not converge.".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moist_air_state() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        RefpropFunctionLibrary::set_moist_air()?;
+
+        let (T, P) = (298.15, 101.325);
+        let W = RefpropFunctionLibrary::humidity_ratio_from_relative_humidity(T, P, 0.5)?;
+        let state = RefpropFunctionLibrary::moist_air_state(T, P, W)?;
+
+        assert!((state.phi - 0.5).abs() < 1e-6);
+        assert!(state.T_dp < state.T_wb && state.T_wb < T);
+
+        let W_dp = RefpropFunctionLibrary::humidity_ratio_from_dew_point(state.T_dp, P)?;
+        let W_wb = RefpropFunctionLibrary::humidity_ratio_from_wet_bulb(T, P, state.T_wb)?;
+        let T_h = RefpropFunctionLibrary::moist_air_temperature(P, state.h, W)?;
+        assert!((W_dp - W).abs() < 1e-6 * W);
+        assert!((W_wb - W).abs() < 1e-5 * W);
+        assert!((T_h - T).abs() < 1e-6);
+
+        println!("{:?}", state);
+
+        // Another fluid invalidates the moist air mixture
+        RefpropFunctionLibrary::set_fluids("R32")?;
+        assert!(matches!(
+            RefpropFunctionLibrary::moist_air_state(T, P, W),
+            Err(RefpropError::InvalidInput(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> Result<(), RefpropError> {
+        let root = solve(|x| Ok(x * x - 2.0), 0.0, 2.0)?;
+        assert!((root - 2f64.sqrt()).abs() < 1e-9);
+
+        // Unbracketed start falls back to the secant method
+        let root = solve(|x| Ok(x.exp() - 3.0), 2.0, 3.0)?;
+        assert!((root - 3f64.ln()).abs() < 1e-9);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,470 @@
+//! Python bindings of the safe wrapper layer, built with the `python` feature.
+//!
+//! The module exposes the flash routines, fluid setup, transport and critical properties, and
+//! batch versions of the flash and transport calculations returning NumPy arrays. Errors are raised
+//! as subclasses of `refprop_sys.RefpropError` mirroring the variants of the Rust `RefpropError`.
+//!
+//! The extension module is built as a shared library by maturin, which enables the feature as
+//! configured in `pyproject.toml`:
+//!
+//! ```text
+//! maturin build --release
+//! ```
+
+use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::{create_exception, exceptions::PyException, prelude::*, types::PyDict};
+
+use crate::{errors, FlashOutput, InputPair, RefpropFunctionLibrary};
+
+create_exception!(
+    refprop_sys,
+    RefpropError,
+    PyException,
+    "Base class of the errors raised by REFPROP calls."
+);
+create_exception!(
+    refprop_sys,
+    InitializationError,
+    RefpropError,
+    "REFPROP could not be initialized."
+);
+create_exception!(
+    refprop_sys,
+    CalculationError,
+    RefpropError,
+    "REFPROP reported an error during a calculation."
+);
+create_exception!(
+    refprop_sys,
+    InvalidInput,
+    RefpropError,
+    "The inputs of a call are invalid."
+);
+create_exception!(
+    refprop_sys,
+    Utf8Error,
+    RefpropError,
+    "A string returned by REFPROP is not valid UTF-8."
+);
+create_exception!(
+    refprop_sys,
+    MutexPoisoned,
+    RefpropError,
+    "A thread panicked while holding the REFPROP lock."
+);
+create_exception!(
+    refprop_sys,
+    UnknownError,
+    RefpropError,
+    "An unexpected error occurred."
+);
+
+impl From<errors::RefpropError> for PyErr {
+    fn from(error: errors::RefpropError) -> Self {
+        let message = error.message();
+        match error {
+            errors::RefpropError::InitializationError(_) => InitializationError::new_err(message),
+            errors::RefpropError::CalculationError(_) => CalculationError::new_err(message),
+            errors::RefpropError::InvalidInput(_) => InvalidInput::new_err(message),
+            errors::RefpropError::Utf8Error(_) => Utf8Error::new_err(message),
+            errors::RefpropError::MutexPoisoned => MutexPoisoned::new_err(message),
+            errors::RefpropError::UnknownError(_) => UnknownError::new_err(message),
+        }
+    }
+}
+
+/// Names of the scalar properties of a `FlashOutput`, in the order of `flash_values`.
+const FLASH_PROPERTIES: [&str; 12] = [
+    "T", "P", "D", "Dl", "Dv", "q", "e", "h", "s", "Cv", "Cp", "w",
+];
+
+/// Returns the scalar properties of a flash result, with `NaN` for undefined heat capacities.
+fn flash_values(output: &FlashOutput) -> [f64; 12] {
+    [
+        output.T,
+        output.P,
+        output.D,
+        output.Dl,
+        output.Dv,
+        output.q,
+        output.e,
+        output.h,
+        output.s,
+        output.Cv.unwrap_or(f64::NAN),
+        output.Cp.unwrap_or(f64::NAN),
+        output.w,
+    ]
+}
+
+fn flash_dict<'py>(py: Python<'py>, output: &FlashOutput) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (name, value) in FLASH_PROPERTIES.iter().zip(flash_values(output)) {
+        dict.set_item(*name, value)?;
+    }
+    dict.set_item("Cv", output.Cv)?;
+    dict.set_item("Cp", output.Cp)?;
+    dict.set_item("x", output.x.clone())?;
+    dict.set_item("y", output.y.clone())?;
+    Ok(dict)
+}
+
+/// Sets the path of the REFPROP installation (defaults to RPPREFIX, then the common installation directories).
+#[pyfunction]
+#[pyo3(signature = (path=None))]
+fn set_path(py: Python<'_>, path: Option<String>) -> PyResult<()> {
+    Ok(py.detach(|| RefpropFunctionLibrary::set_path(path.as_deref()))?)
+}
+
+/// Loads a list of fluids separated by semicolons.
+#[pyfunction]
+fn set_fluids(py: Python<'_>, fluids: String) -> PyResult<()> {
+    Ok(py.detach(|| RefpropFunctionLibrary::set_fluids(&fluids))?)
+}
+
+/// Loads a predefined mixture and returns its composition (mole fractions).
+#[pyfunction]
+fn set_mixture(py: Python<'_>, name: String) -> PyResult<Vec<f64>> {
+    Ok(py.detach(|| RefpropFunctionLibrary::set_mixture(&name))?)
+}
+
+/// Performs a flash calculation for an input pair (e.g. "TP") and returns the properties as a dict.
+#[pyfunction]
+fn flash<'py>(
+    py: Python<'py>,
+    pair: &str,
+    a: f64,
+    b: f64,
+    z: Vec<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let pair: InputPair = pair.parse()?;
+    let output = py.detach(|| RefpropFunctionLibrary::flash(pair, a, b, &z))?;
+    flash_dict(py, &output)
+}
+
+/// Defines a Python function for the flash routine of an input pair.
+macro_rules! flash_function {
+    ($name:ident, $pair:expr, $a:ident, $b:ident, $doc:literal) => {
+        #[doc = $doc]
+        #[pyfunction]
+        fn $name<'py>(
+            py: Python<'py>,
+            $a: f64,
+            $b: f64,
+            z: Vec<f64>,
+        ) -> PyResult<Bound<'py, PyDict>> {
+            let output = py.detach(|| RefpropFunctionLibrary::flash($pair, $a, $b, &z))?;
+            flash_dict(py, &output)
+        }
+    };
+}
+
+flash_function!(
+    tp_flash,
+    InputPair::TP,
+    T,
+    P,
+    "Flash from temperature [K] and pressure [kPa]."
+);
+flash_function!(
+    ph_flash,
+    InputPair::PH,
+    P,
+    h,
+    "Flash from pressure [kPa] and enthalpy [J/mol]."
+);
+flash_function!(
+    ps_flash,
+    InputPair::PS,
+    P,
+    s,
+    "Flash from pressure [kPa] and entropy [J/mol-K]."
+);
+flash_function!(
+    pd_flash,
+    InputPair::PD,
+    P,
+    D,
+    "Flash from pressure [kPa] and density [mol/L]."
+);
+flash_function!(
+    pe_flash,
+    InputPair::PE,
+    P,
+    e,
+    "Flash from pressure [kPa] and internal energy [J/mol]."
+);
+flash_function!(
+    td_flash,
+    InputPair::TD,
+    T,
+    D,
+    "Flash from temperature [K] and density [mol/L]."
+);
+flash_function!(
+    dh_flash,
+    InputPair::DH,
+    D,
+    h,
+    "Flash from density [mol/L] and enthalpy [J/mol]."
+);
+flash_function!(
+    ds_flash,
+    InputPair::DS,
+    D,
+    s,
+    "Flash from density [mol/L] and entropy [J/mol-K]."
+);
+flash_function!(
+    de_flash,
+    InputPair::DE,
+    D,
+    e,
+    "Flash from density [mol/L] and internal energy [J/mol]."
+);
+flash_function!(
+    hs_flash,
+    InputPair::HS,
+    h,
+    s,
+    "Flash from enthalpy [J/mol] and entropy [J/mol-K]."
+);
+flash_function!(
+    tq_flash,
+    InputPair::TQ,
+    T,
+    q,
+    "Flash from temperature [K] and molar quality [mol/mol]."
+);
+flash_function!(
+    pq_flash,
+    InputPair::PQ,
+    P,
+    q,
+    "Flash from pressure [kPa] and molar quality [mol/mol]."
+);
+
+/// Calculates the viscosity [uPa-s] and thermal conductivity [W/(m-K)] at temperature [K] and density [mol/L].
+#[pyfunction]
+fn transport_properties<'py>(
+    py: Python<'py>,
+    T: f64,
+    D: f64,
+    z: Vec<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let output = py.detach(|| RefpropFunctionLibrary::transport_properties(T, D, &z))?;
+    let dict = PyDict::new(py);
+    dict.set_item("eta", output.eta)?;
+    dict.set_item("tcx", output.tcx)?;
+    Ok(dict)
+}
+
+/// Calculates the critical temperature [K], pressure [kPa], and density [mol/L].
+#[pyfunction]
+fn critical_parameters<'py>(py: Python<'py>, z: Vec<f64>) -> PyResult<Bound<'py, PyDict>> {
+    let output = py.detach(|| RefpropFunctionLibrary::critical_parameters(&z))?;
+    let dict = PyDict::new(py);
+    dict.set_item("Tc", output.Tc)?;
+    dict.set_item("Pc", output.Pc)?;
+    dict.set_item("Dc", output.Dc)?;
+    Ok(dict)
+}
+
+/// Copies two input arrays of equal length.
+fn read_pair(
+    a: &PyReadonlyArray1<'_, f64>,
+    b: &PyReadonlyArray1<'_, f64>,
+) -> PyResult<(Vec<f64>, Vec<f64>)> {
+    let a = a.as_array().to_vec();
+    let b = b.as_array().to_vec();
+    if a.len() != b.len() {
+        return Err(InvalidInput::new_err(format!(
+            "Input arrays have different lengths ({} and {}).",
+            a.len(),
+            b.len()
+        )));
+    }
+    Ok((a, b))
+}
+
+/// Evaluates a calculation at every point of a batch while holding the REFPROP lock.
+///
+/// Errors reported by REFPROP at a point are kept as the message of that point; any other error,
+/// such as an invalid composition, applies to the whole batch and is returned.
+fn evaluate_batch<T>(
+    a: &[f64],
+    b: &[f64],
+    calculate: impl Fn(f64, f64) -> Result<T, errors::RefpropError>,
+) -> Result<Vec<Result<T, String>>, errors::RefpropError> {
+    // Keep the loaded fluids fixed for the whole batch
+    let _guard = RefpropFunctionLibrary::lock()?;
+
+    a.iter()
+        .zip(b)
+        .map(|(&a, &b)| match calculate(a, b) {
+            Ok(output) => Ok(Ok(output)),
+            Err(errors::RefpropError::CalculationError(message)) => Ok(Err(message)),
+            Err(error) => Err(error),
+        })
+        .collect()
+}
+
+/// Returns the error message of every point of a batch, `None` where the calculation succeeded.
+fn batch_errors<T>(results: &[Result<T, String>]) -> Vec<Option<String>> {
+    results
+        .iter()
+        .map(|result| result.as_ref().err().cloned())
+        .collect()
+}
+
+/// Performs flash calculations element-wise over two arrays and returns a dict of NumPy arrays.
+///
+/// Points where REFPROP reports an error are set to NaN, and the `"error"` entry lists the message of
+/// each point (`None` where the flash succeeded). Errors that apply to the whole batch, such as an
+/// invalid composition, are raised. The REFPROP lock is held for the whole batch, so the loaded fluids
+/// cannot change part way through.
+#[pyfunction]
+fn flash_batch<'py>(
+    py: Python<'py>,
+    pair: &str,
+    a: PyReadonlyArray1<'py, f64>,
+    b: PyReadonlyArray1<'py, f64>,
+    z: Vec<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let pair: InputPair = pair.parse()?;
+    let (a, b) = read_pair(&a, &b)?;
+
+    let results =
+        py.detach(|| evaluate_batch(&a, &b, |a, b| RefpropFunctionLibrary::flash(pair, a, b, &z)))?;
+
+    let mut columns = vec![Vec::with_capacity(results.len()); FLASH_PROPERTIES.len()];
+    for result in &results {
+        let values = match result {
+            Ok(output) => flash_values(output),
+            Err(_) => [f64::NAN; 12],
+        };
+        for (column, value) in columns.iter_mut().zip(values) {
+            column.push(value);
+        }
+    }
+
+    let dict = PyDict::new(py);
+    for (name, column) in FLASH_PROPERTIES.iter().zip(columns) {
+        dict.set_item(*name, PyArray1::from_vec(py, column))?;
+    }
+    dict.set_item("error", batch_errors(&results))?;
+    Ok(dict)
+}
+
+/// Calculates transport properties element-wise over temperature [K] and density [mol/L] arrays.
+///
+/// Points where REFPROP reports an error are set to NaN, and the `"error"` entry lists the message of
+/// each point (`None` where the calculation succeeded). Errors that apply to the whole batch are raised.
+#[pyfunction]
+fn transport_batch<'py>(
+    py: Python<'py>,
+    T: PyReadonlyArray1<'py, f64>,
+    D: PyReadonlyArray1<'py, f64>,
+    z: Vec<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let (T, D) = read_pair(&T, &D)?;
+
+    let results = py.detach(|| {
+        evaluate_batch(&T, &D, |T, D| {
+            RefpropFunctionLibrary::transport_properties(T, D, &z)
+        })
+    })?;
+
+    let (eta, tcx): (Vec<f64>, Vec<f64>) = results
+        .iter()
+        .map(|result| match result {
+            Ok(output) => (output.eta, output.tcx),
+            Err(_) => (f64::NAN, f64::NAN),
+        })
+        .unzip();
+
+    let dict = PyDict::new(py);
+    dict.set_item("eta", PyArray1::from_vec(py, eta))?;
+    dict.set_item("tcx", PyArray1::from_vec(py, tcx))?;
+    dict.set_item("error", batch_errors(&results))?;
+    Ok(dict)
+}
+
+#[pymodule]
+fn refprop_sys(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("RefpropError", py.get_type::<RefpropError>())?;
+    m.add("InitializationError", py.get_type::<InitializationError>())?;
+    m.add("CalculationError", py.get_type::<CalculationError>())?;
+    m.add("InvalidInput", py.get_type::<InvalidInput>())?;
+    m.add("Utf8Error", py.get_type::<Utf8Error>())?;
+    m.add("MutexPoisoned", py.get_type::<MutexPoisoned>())?;
+    m.add("UnknownError", py.get_type::<UnknownError>())?;
+
+    m.add_function(wrap_pyfunction!(set_path, m)?)?;
+    m.add_function(wrap_pyfunction!(set_fluids, m)?)?;
+    m.add_function(wrap_pyfunction!(set_mixture, m)?)?;
+    m.add_function(wrap_pyfunction!(flash, m)?)?;
+    m.add_function(wrap_pyfunction!(tp_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(ph_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(ps_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(pd_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(pe_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(td_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(dh_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(ds_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(de_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(hs_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(tq_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(pq_flash, m)?)?;
+    m.add_function(wrap_pyfunction!(transport_properties, m)?)?;
+    m.add_function(wrap_pyfunction!(critical_parameters, m)?)?;
+    m.add_function(wrap_pyfunction!(flash_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(transport_batch, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_errors() -> Result<(), errors::RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        RefpropFunctionLibrary::set_fluids("R32")?;
+        let z = [1.0];
+
+        // The second point is far outside the range of the equation of state
+        let T = [300.0, 300.0];
+        let P = [101.325, -1.0];
+        let results = evaluate_batch(&T, &P, |T, P| {
+            RefpropFunctionLibrary::flash(InputPair::TP, T, P, &z)
+        })?;
+        assert!((results[0].as_ref().unwrap().T - 300.0).abs() < 1e-9);
+        let errors = batch_errors(&results);
+        assert!(errors[0].is_none());
+        assert!(errors[1]
+            .as_ref()
+            .is_some_and(|message| !message.is_empty()));
+
+        let D = [results[0].as_ref().unwrap().D, -1.0];
+        let results = evaluate_batch(&T, &D, |T, D| {
+            RefpropFunctionLibrary::transport_properties(T, D, &z)
+        })?;
+        assert!(results[0].as_ref().unwrap().eta > 0.0);
+        assert!(results[1].is_err());
+
+        // An invalid composition applies to every point and is returned
+        let z = [0.5, 0.4];
+        assert!(matches!(
+            evaluate_batch(&T, &P, |T, P| RefpropFunctionLibrary::flash(
+                InputPair::TP,
+                T,
+                P,
+                &z
+            )),
+            Err(errors::RefpropError::InvalidInput(_))
+        ));
+        Ok(())
+    }
+}
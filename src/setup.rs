@@ -1,24 +1,34 @@
-mod purefld;
+mod equation_of_state;
+pub(crate) mod flags;
+pub(crate) mod purefld;
 mod satspln;
 mod set_fluids;
-mod set_mixture;
-mod set_path;
+mod set_mixing_parameters;
+pub(crate) mod set_mixture;
+pub(crate) mod set_path;
+mod set_reference_state;
 
-use std::{
-    ffi::{CStr, CString},
-    sync::Mutex,
-};
+pub use equation_of_state::{EquationOfState, EquationOfStateGuard};
+pub use flags::{FlagGuard, RefpropFlag};
+pub use purefld::PureComponentGuard;
+pub use set_reference_state::ReferenceState;
 
-use crate::{bindings, errors::RefpropError, RefpropFunctionLibrary, Units, REFPROP_MUTEX};
+use std::ffi::{CStr, CString};
+
+use crate::{bindings, errors::RefpropError, utils::acquire_lock, RefpropFunctionLibrary, Units};
 
 /// Represents the critical parameters calculated by the `crit_p` method.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CriticalParameters {
     /// Critical temperature [K]
+    #[cfg_attr(feature = "serde", serde(rename = "Tc_K"))]
     pub Tc: f64,
     /// Critical pressure [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "Pc_kPa"))]
     pub Pc: f64,
     /// Critical density [mol/L]
+    #[cfg_attr(feature = "serde", serde(rename = "Dc_mol_per_L"))]
     pub Dc: f64,
 }
 
@@ -82,10 +92,7 @@ impl RefpropFunctionLibrary {
         }
 
         // Acquire the mutex lock to ensure exclusive access
-        let _lock = REFPROP_MUTEX
-            .get_or_init(|| Mutex::new(()))
-            .lock()
-            .map_err(|_| RefpropError::MutexPoisoned)?;
+        let _lock = acquire_lock()?;
 
         // Convert 'z' slice to a fixed-size array with padding
         let mut z_buffer = [0.0f64; 20];
@@ -233,10 +240,7 @@ impl RefpropFunctionLibrary {
         }
 
         // Acquire the mutex lock to ensure exclusive access
-        let _lock = REFPROP_MUTEX
-            .get_or_init(|| Mutex::new(()))
-            .lock()
-            .map_err(|_| RefpropError::MutexPoisoned)?;
+        let _lock = acquire_lock()?;
 
         // Prepare iIn as i32
         let mut i_in = i_out.len() as i32;
@@ -398,10 +402,7 @@ impl RefpropFunctionLibrary {
         let i_units = units.get_iunits_code()?;
 
         // Acquire the mutex lock to ensure exclusive access
-        let _lock = REFPROP_MUTEX
-            .get_or_init(|| Mutex::new(()))
-            .lock()
-            .map_err(|_| RefpropError::MutexPoisoned)?;
+        let _lock = acquire_lock()?;
 
         // Define buffer sizes as per REFPROP's documentation
         const HOUT_LENGTH: usize = 255;
@@ -0,0 +1,233 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{
+    bindings,
+    lock::RefpropGuard,
+    setup::flags::{flag_unlocked, QUERY},
+    utils::{acquire_lock, check_refprop_error, setup_changed},
+    RefpropError, RefpropFlag, RefpropFunctionLibrary,
+};
+
+/// Whether AGA8 was activated with `SETAGAdll`, which REFPROP cannot report back.
+static AGA8_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Represents the equation of state used for the loaded fluids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EquationOfState {
+    /// The reference equations of the fluid files and the default mixing rules.
+    Default,
+    /// The Peng-Robinson cubic equation of state (`PREOSdll`).
+    PengRobinson,
+    /// The AGA8 DETAIL equation for natural gas (`SETAGAdll`).
+    Aga8,
+    /// The GERG-2008 equations for natural gas (`FLAGSdll` flag "GERG").
+    Gerg2008,
+}
+
+/// Calls `PREOSdll`, which sets the Peng-Robinson flag or reports it for `-1`.
+fn peng_robinson_unlocked(_guard: &RefpropGuard, flag: i32) -> i32 {
+    let mut flag = flag;
+
+    // Call PREOSdll within an unsafe block
+    unsafe {
+        bindings::PREOSdll(&mut flag as *mut i32);
+    }
+
+    flag
+}
+
+/// Deactivates every alternative model and activates `eos`.
+fn set_equation_of_state_unlocked(
+    guard: &RefpropGuard,
+    eos: EquationOfState,
+) -> Result<(), RefpropError> {
+    peng_robinson_unlocked(guard, 0);
+    if AGA8_ACTIVE.swap(false, Ordering::Relaxed) {
+        unsafe {
+            bindings::UNSETAGAdll();
+        }
+    }
+    let result = flag_unlocked(guard, RefpropFlag::Gerg, 0).and_then(|_| match eos {
+        EquationOfState::Default => Ok(()),
+        EquationOfState::PengRobinson => {
+            peng_robinson_unlocked(guard, 1);
+            Ok(())
+        }
+        EquationOfState::Aga8 => {
+            const HERR_LENGTH: usize = 255;
+            let mut ierr: i32 = 0;
+            let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+            let herr_ptr = herr_buffer.as_mut_ptr();
+            let herr_length = HERR_LENGTH as i32;
+
+            // Call SETAGAdll within an unsafe block
+            unsafe {
+                bindings::SETAGAdll(&mut ierr as *mut i32, herr_ptr, herr_length);
+            }
+
+            check_refprop_error(guard, ierr, herr_ptr, herr_length)?;
+            AGA8_ACTIVE.store(true, Ordering::Relaxed);
+            Ok(())
+        }
+        EquationOfState::Gerg2008 => flag_unlocked(guard, RefpropFlag::Gerg, 1).map(|_| ()),
+    });
+    setup_changed(guard);
+
+    result
+}
+
+/// Guard keeping an equation of state active, returned by `RefpropFunctionLibrary::use_equation_of_state`.
+///
+/// The guard holds the global REFPROP lock, so no other thread calculates with the selected model, and
+/// restores the previously active equation of state when dropped.
+pub struct EquationOfStateGuard {
+    previous: EquationOfState,
+    lock: RefpropGuard,
+}
+
+impl EquationOfStateGuard {
+    /// Returns the equation of state that is restored when the guard is dropped.
+    pub fn previous(&self) -> EquationOfState {
+        self.previous
+    }
+}
+
+impl Drop for EquationOfStateGuard {
+    fn drop(&mut self) {
+        let _ = set_equation_of_state_unlocked(&self.lock, self.previous);
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Activates an equation of state for the loaded fluids.
+    ///
+    /// Any previously activated alternative model is deactivated first (`PREOSdll` with 0,
+    /// `UNSETAGAdll`, and the "GERG" flag of `FLAGSdll`). The setting applies until it is changed
+    /// again; prefer `use_equation_of_state` to switch models temporarily.
+    ///
+    /// # Parameters
+    ///
+    /// - `eos`: The equation of state to activate.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::CalculationError` if REFPROP cannot apply the model to the loaded fluids,
+    ///   e.g. AGA8 or GERG-2008 for fluids outside their component lists.
+    ///
+    /// # References
+    ///
+    /// - [PREOSdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/PREOSdll)
+    /// - [SETAGAdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/SETAGAdll)
+    /// - [FLAGSdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/high_level.html#f/_/FLAGSdll)
+    pub fn set_equation_of_state(eos: EquationOfState) -> Result<(), RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let guard = acquire_lock()?;
+
+        set_equation_of_state_unlocked(&guard, eos)
+    }
+
+    /// Returns the equation of state currently active for the loaded fluids.
+    ///
+    /// REFPROP cannot report whether AGA8 is active, so `EquationOfState::Aga8` is only returned if it
+    /// was activated through this crate.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::CalculationError` if REFPROP cannot report the "GERG" flag.
+    pub fn equation_of_state() -> Result<EquationOfState, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let guard = acquire_lock()?;
+
+        if peng_robinson_unlocked(&guard, -1) != 0 {
+            Ok(EquationOfState::PengRobinson)
+        } else if AGA8_ACTIVE.load(Ordering::Relaxed) {
+            Ok(EquationOfState::Aga8)
+        } else if flag_unlocked(&guard, RefpropFlag::Gerg, QUERY)? != 0 {
+            Ok(EquationOfState::Gerg2008)
+        } else {
+            Ok(EquationOfState::Default)
+        }
+    }
+
+    /// Activates an equation of state until the returned guard is dropped.
+    ///
+    /// The guard holds the global REFPROP lock (see `RefpropFunctionLibrary::lock`), and dropping it
+    /// restores the equation of state that was active before, even if the calculations in between fail
+    /// or panic.
+    ///
+    /// # Parameters
+    ///
+    /// - `eos`: The equation of state to activate.
+    ///
+    /// # Returns
+    ///
+    /// - `EquationOfStateGuard`: A guard restoring the previous equation of state when dropped.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `set_equation_of_state`; the previous model is restored in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{EquationOfState, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let guard = RefpropFunctionLibrary::use_equation_of_state(EquationOfState::PengRobinson)?;
+    ///     let cubic = RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z)?;
+    ///     drop(guard);
+    ///
+    ///     assert_eq!(RefpropFunctionLibrary::equation_of_state()?, EquationOfState::Default);
+    ///     println!("Peng-Robinson density: {} mol/L", cubic.D);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn use_equation_of_state(
+        eos: EquationOfState,
+    ) -> Result<EquationOfStateGuard, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access until the guard is dropped
+        let lock = acquire_lock()?;
+        let previous = Self::equation_of_state()?;
+        let guard = EquationOfStateGuard { previous, lock };
+        set_equation_of_state_unlocked(&guard.lock, eos)?;
+
+        Ok(guard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equation_of_state_guard() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+        let reference = RefpropFunctionLibrary::tp_flash(300.0, 1000.0, &z)?;
+
+        {
+            let eos = RefpropFunctionLibrary::use_equation_of_state(EquationOfState::PengRobinson)?;
+            assert_eq!(eos.previous(), EquationOfState::Default);
+            assert_eq!(
+                RefpropFunctionLibrary::equation_of_state()?,
+                EquationOfState::PengRobinson
+            );
+            let cubic = RefpropFunctionLibrary::tp_flash(300.0, 1000.0, &z)?;
+            assert_ne!(cubic.D, reference.D);
+        }
+
+        assert_eq!(
+            RefpropFunctionLibrary::equation_of_state()?,
+            EquationOfState::Default
+        );
+        let restored = RefpropFunctionLibrary::tp_flash(300.0, 1000.0, &z)?;
+        assert_eq!(restored.D, reference.D);
+
+        Ok(())
+    }
+}
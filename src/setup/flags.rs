@@ -0,0 +1,283 @@
+use crate::{
+    bindings,
+    lock::RefpropGuard,
+    utils::{acquire_lock, check_refprop_error, setup_changed},
+    RefpropError, RefpropFunctionLibrary,
+};
+
+/// Value of `jFlag` that makes `FLAGSdll` report a flag without changing it.
+pub(crate) const QUERY: i32 = -999;
+
+/// Flags selecting an equation of state that `equation_of_state` could not report if set directly.
+const MODEL_FLAGS: [&str; 2] = ["AGA8", "Peng-Robinson"];
+
+/// Represents a runtime configuration flag of REFPROP, set with the `FLAGSdll` function.
+///
+/// Most flags are switches taking 0 (off) or 1 (on). The AGA8 and Peng-Robinson models are selected
+/// with `set_equation_of_state` instead, which keeps track of the active equation of state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefpropFlag {
+    /// Caching of repeated calculations ("Cache").
+    Cache,
+    /// Saturation splines for mixtures ("Splines on").
+    Splines,
+    /// GERG-2008 equations for natural gas ("GERG").
+    Gerg,
+    /// Error instead of a warning for states outside the range of the equations ("Error on extrapolation").
+    ErrorOnExtrapolation,
+    /// Debug output of REFPROP ("Debug").
+    Debug,
+    /// Any other flag, given by its name in the `FLAGSdll` documentation.
+    Other(&'static str),
+}
+
+impl RefpropFlag {
+    /// Returns the name of the flag expected by `FLAGSdll`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RefpropFlag::Cache => "Cache",
+            RefpropFlag::Splines => "Splines on",
+            RefpropFlag::Gerg => "GERG",
+            RefpropFlag::ErrorOnExtrapolation => "Error on extrapolation",
+            RefpropFlag::Debug => "Debug",
+            RefpropFlag::Other(name) => name,
+        }
+    }
+}
+
+/// Calls `FLAGSdll` and returns the value of the flag reported by REFPROP.
+///
+/// A `value` of `QUERY` leaves the flag unchanged; any other value may change the results of later
+/// calculations and is recorded as a setup change.
+pub(crate) fn flag_unlocked(
+    guard: &RefpropGuard,
+    flag: RefpropFlag,
+    value: i32,
+) -> Result<i32, RefpropError> {
+    let name = flag.as_str();
+    if name.is_empty() || !name.is_ascii() {
+        return Err(RefpropError::InvalidInput(format!(
+            "Invalid REFPROP flag name '{}'",
+            name
+        )));
+    }
+    if value != QUERY
+        && MODEL_FLAGS
+            .iter()
+            .any(|model| model.eq_ignore_ascii_case(name))
+    {
+        return Err(RefpropError::InvalidInput(format!(
+            "The '{}' flag selects an equation of state; use RefpropFunctionLibrary::set_equation_of_state",
+            name
+        )));
+    }
+
+    // Define buffer sizes as per REFPROP's documentation
+    const HFLAG_LENGTH: usize = 255;
+    const HERR_LENGTH: usize = 255;
+
+    let mut hflag = [b' ' as libc::c_char; HFLAG_LENGTH];
+    for (c, byte) in hflag.iter_mut().zip(name.bytes()) {
+        *c = byte as libc::c_char;
+    }
+
+    let mut jflag = value;
+    let mut kflag: i32 = 0;
+    let mut ierr: i32 = 0;
+    let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+    let herr_ptr = herr_buffer.as_mut_ptr();
+    let herr_length = HERR_LENGTH as i32;
+
+    // Call FLAGSdll within an unsafe block
+    unsafe {
+        bindings::FLAGSdll(
+            hflag.as_mut_ptr(),
+            &mut jflag as *mut i32,
+            &mut kflag as *mut i32,
+            &mut ierr as *mut i32,
+            herr_ptr,
+            HFLAG_LENGTH as i32,
+            herr_length,
+        );
+    }
+    if value != QUERY {
+        setup_changed(guard);
+    }
+
+    check_refprop_error(guard, ierr, herr_ptr, herr_length)?;
+
+    Ok(kflag)
+}
+
+/// Guard keeping a REFPROP flag set, returned by `RefpropFunctionLibrary::use_flag`.
+///
+/// The guard holds the global REFPROP lock, so no other thread calls REFPROP while the flag is changed,
+/// and restores the previous value of the flag when dropped.
+pub struct FlagGuard {
+    flag: RefpropFlag,
+    previous: i32,
+    lock: RefpropGuard,
+}
+
+impl FlagGuard {
+    /// Returns the flag set by the guard.
+    pub fn flag(&self) -> RefpropFlag {
+        self.flag
+    }
+
+    /// Returns the value of the flag that is restored when the guard is dropped.
+    pub fn previous(&self) -> i32 {
+        self.previous
+    }
+}
+
+impl Drop for FlagGuard {
+    fn drop(&mut self) {
+        let _ = flag_unlocked(&self.lock, self.flag, self.previous);
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Returns the current value of a REFPROP flag using the `FLAGSdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `flag`: The flag to query.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the name of a `RefpropFlag::Other` flag is empty or not ASCII.
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not know the flag.
+    ///
+    /// # References
+    ///
+    /// - [FLAGSdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/high_level.html#f/_/FLAGSdll)
+    pub fn flag(flag: RefpropFlag) -> Result<i32, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let guard = acquire_lock()?;
+
+        flag_unlocked(&guard, flag, QUERY)
+    }
+
+    /// Sets a REFPROP flag using the `FLAGSdll` function.
+    ///
+    /// The flag stays set until it is changed again; prefer `use_flag` to change a flag temporarily.
+    ///
+    /// # Parameters
+    ///
+    /// - `flag`: The flag to set.
+    /// - `value`: The new value, usually 0 (off) or 1 (on).
+    ///
+    /// # Returns
+    ///
+    /// - `i32`: The value of the flag reported by REFPROP after the change.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the name of a `RefpropFlag::Other` flag is empty or not
+    ///   ASCII, or if it selects the AGA8 or Peng-Robinson model (see `set_equation_of_state`).
+    /// - Returns `RefpropError::CalculationError` if REFPROP does not know the flag or rejects the value.
+    ///
+    /// # References
+    ///
+    /// - [FLAGSdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/high_level.html#f/_/FLAGSdll)
+    pub fn set_flag(flag: RefpropFlag, value: i32) -> Result<i32, RefpropError> {
+        if value == QUERY {
+            return Err(RefpropError::InvalidInput(format!(
+                "{} is reserved for querying flags; use RefpropFunctionLibrary::flag",
+                QUERY
+            )));
+        }
+
+        // Acquire the mutex lock to ensure exclusive access
+        let guard = acquire_lock()?;
+
+        flag_unlocked(&guard, flag, value)
+    }
+
+    /// Sets a REFPROP flag until the returned guard is dropped.
+    ///
+    /// The guard holds the global REFPROP lock (see `RefpropFunctionLibrary::lock`), and dropping it
+    /// restores the previous value of the flag, even if the calculations in between fail or panic.
+    ///
+    /// # Parameters
+    ///
+    /// - `flag`: The flag to set.
+    /// - `value`: The value to set while the guard is alive, usually 0 (off) or 1 (on).
+    ///
+    /// # Returns
+    ///
+    /// - `FlagGuard`: A guard restoring the previous value of the flag when dropped.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `set_flag`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFlag, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     let cache = RefpropFunctionLibrary::flag(RefpropFlag::Cache)?;
+    ///     {
+    ///         let _guard = RefpropFunctionLibrary::use_flag(RefpropFlag::Cache, 0)?;
+    ///         RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z)?;
+    ///     }
+    ///     assert_eq!(RefpropFunctionLibrary::flag(RefpropFlag::Cache)?, cache);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn use_flag(flag: RefpropFlag, value: i32) -> Result<FlagGuard, RefpropError> {
+        if value == QUERY {
+            return Err(RefpropError::InvalidInput(format!(
+                "{} is reserved for querying flags; use RefpropFunctionLibrary::flag",
+                QUERY
+            )));
+        }
+
+        // Acquire the mutex lock to ensure exclusive access until the guard is dropped
+        let lock = acquire_lock()?;
+        let previous = flag_unlocked(&lock, flag, QUERY)?;
+        let guard = FlagGuard {
+            flag,
+            previous,
+            lock,
+        };
+        flag_unlocked(&guard.lock, flag, value)?;
+
+        Ok(guard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_guard() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        RefpropFunctionLibrary::set_mixture("R454B")?;
+
+        let debug = RefpropFunctionLibrary::flag(RefpropFlag::Debug)?;
+        {
+            let guard = RefpropFunctionLibrary::use_flag(RefpropFlag::Debug, 1 - debug)?;
+            assert_eq!(guard.previous(), debug);
+            assert_eq!(RefpropFunctionLibrary::flag(RefpropFlag::Debug)?, 1 - debug);
+        }
+        assert_eq!(RefpropFunctionLibrary::flag(RefpropFlag::Debug)?, debug);
+
+        assert!(RefpropFunctionLibrary::set_flag(RefpropFlag::Debug, QUERY).is_err());
+        assert!(RefpropFunctionLibrary::flag(RefpropFlag::Other("")).is_err());
+        assert!(matches!(
+            RefpropFunctionLibrary::set_flag(RefpropFlag::Other("aga8"), 1),
+            Err(RefpropError::InvalidInput(_))
+        ));
+
+        Ok(())
+    }
+}
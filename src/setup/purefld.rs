@@ -1,18 +1,225 @@
-use std::ffi::c_int;
+use std::{
+    ffi::c_int,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use crate::{bindings, utils::acquire_lock, RefpropError, RefpropFunctionLibrary};
+use crate::{
+    bindings,
+    lock::RefpropGuard,
+    utils::{acquire_lock, fluids_changed},
+    FlashOutput, InputPair, RefpropError, RefpropFunctionLibrary,
+};
+
+/// Component selected with `PUREFLDdll` (1-based), or 0 in mixture mode.
+static PURE_COMPONENT: AtomicUsize = AtomicUsize::new(0);
+
+/// Records that loading fluids returned REFPROP to mixture mode. Must be called while holding the lock.
+pub(crate) fn reset_pure_component(_guard: &RefpropGuard) {
+    PURE_COMPONENT.store(0, Ordering::SeqCst);
+}
+
+/// Returns the component selected as a pure fluid, or `None` in mixture mode. Must be called while holding the lock.
+pub(crate) fn pure_component_selected(_guard: &RefpropGuard) -> Option<usize> {
+    match PURE_COMPONENT.load(Ordering::SeqCst) {
+        0 => None,
+        icomp => Some(icomp),
+    }
+}
+
+fn pure_fld_unlocked(guard: &RefpropGuard, icomp: usize) {
+    let mut icomp_mut = icomp as i32 as c_int;
+    let icomp_ptr = &mut icomp_mut as *mut c_int;
+    // Call PUREFLDdll within an unsafe block
+    unsafe {
+        bindings::PUREFLDdll(icomp_ptr);
+    }
+    PURE_COMPONENT.store(icomp, Ordering::SeqCst);
+    fluids_changed(guard);
+}
+
+/// Guard keeping REFPROP in pure-component mode, returned by `RefpropFunctionLibrary::use_pure_component`.
+///
+/// While the guard is alive, all calculations treat the selected component as a pure fluid and take
+/// the single-component composition `[1.0]`; compositions of more than one component are rejected
+/// with `RefpropError::InvalidInput`. The guard holds the global REFPROP lock, so no other thread
+/// calculates in pure mode, and restores the previous mode (normally mixture mode) when dropped.
+pub struct PureComponentGuard {
+    component: usize,
+    previous: usize,
+    lock: RefpropGuard,
+}
+
+impl PureComponentGuard {
+    /// Returns the selected component (1-based, in the order the fluids were loaded).
+    pub fn component(&self) -> usize {
+        self.component
+    }
+
+    /// Performs a flash calculation of the selected pure component (see `RefpropFunctionLibrary::flash`).
+    ///
+    /// # Parameters
+    ///
+    /// - `pair`: The pair of independent properties.
+    /// - `a`: The first property of the pair, in the default molar units.
+    /// - `b`: The second property of the pair, in the default molar units.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `RefpropFunctionLibrary::flash`.
+    pub fn flash(&self, pair: InputPair, a: f64, b: f64) -> Result<FlashOutput, RefpropError> {
+        RefpropFunctionLibrary::flash(pair, a, b, [1.0])
+    }
+}
+
+impl Drop for PureComponentGuard {
+    fn drop(&mut self) {
+        pure_fld_unlocked(&self.lock, self.previous);
+    }
+}
 
 impl RefpropFunctionLibrary {
+    /// Switches REFPROP between pure-component and mixture mode using the `PUREFLDdll` function.
+    ///
+    /// In pure-component mode, all calculations treat component `icomp` of the loaded mixture as a
+    /// pure fluid and take the single-component composition `[1.0]`; compositions of more than one
+    /// component are rejected. Prefer `use_pure_component`, which returns to mixture mode automatically.
+    ///
+    /// # Parameters
+    ///
+    /// - `icomp`: Index of the component (1-based, in the order the fluids were loaded), or 0 to
+    ///   return to mixture mode.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if `icomp` exceeds the number of loaded components.
+    ///
+    /// # References
+    ///
+    /// - [PUREFLDdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/PUREFLDdll)
     pub fn pure_fld(icomp: usize) -> Result<(), RefpropError> {
         // Acquire the mutex lock to ensure exclusive access
-        let _guard = acquire_lock()?;
+        let guard = acquire_lock()?;
+
+        Self::check_component(icomp)?;
+        pure_fld_unlocked(&guard, icomp);
+
+        Ok(())
+    }
+
+    /// Returns the component selected with `pure_fld` or `use_pure_component`, or `None` in mixture mode.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn pure_component() -> Result<Option<usize>, RefpropError> {
+        // Acquire the mutex lock to ensure exclusive access
+        let guard = acquire_lock()?;
+
+        Ok(pure_component_selected(&guard))
+    }
+
+    /// Switches REFPROP to pure-component mode for one loaded component until the returned guard is dropped.
+    ///
+    /// The guard holds the global REFPROP lock (see `RefpropFunctionLibrary::lock`), and dropping it
+    /// restores the previous mode, even if the calculations in between fail or panic.
+    ///
+    /// # Parameters
+    ///
+    /// - `icomp`: Index of the component (1-based, in the order the fluids were loaded).
+    ///
+    /// # Returns
+    ///
+    /// - `PureComponentGuard`: A guard restoring the previous mode when dropped.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if `icomp` is 0 or exceeds the number of loaded components.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{InputPair, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     // Saturation pressure of pure R32, the first component of R454B
+    ///     let r32 = RefpropFunctionLibrary::use_pure_component(1)?;
+    ///     let saturated = r32.flash(InputPair::TQ, 273.15, 0.0)?;
+    ///     drop(r32);
+    ///
+    ///     assert_eq!(RefpropFunctionLibrary::pure_component()?, None);
+    ///     println!("Saturation pressure of R32: {} kPa", saturated.P);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn use_pure_component(icomp: usize) -> Result<PureComponentGuard, RefpropError> {
+        if icomp == 0 {
+            return Err(RefpropError::InvalidInput(
+                "Component index is 1-based; use pure_fld(0) for mixture mode".to_string(),
+            ));
+        }
+
+        // Acquire the mutex lock to ensure exclusive access until the guard is dropped
+        let lock = acquire_lock()?;
+
+        Self::check_component(icomp)?;
+        let guard = PureComponentGuard {
+            component: icomp,
+            previous: PURE_COMPONENT.load(Ordering::SeqCst),
+            lock,
+        };
+        pure_fld_unlocked(&guard.lock, icomp);
+
+        Ok(guard)
+    }
 
-        let mut icomp_mut = icomp as i32 as c_int;
-        let icomp_ptr = &mut icomp_mut as *mut c_int;
-        // Call PUREFLDdll within an unsafe block
-        unsafe {
-            bindings::PUREFLDdll(icomp_ptr);
+    /// Checks a component index against the number of loaded components.
+    fn check_component(icomp: usize) -> Result<(), RefpropError> {
+        let ncomp = Self::loaded_component_names()?.len();
+        if icomp > ncomp {
+            return Err(RefpropError::InvalidInput(format!(
+                "Component index {} exceeds the number of loaded components ({})",
+                icomp, ncomp
+            )));
         }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pure_component_guard() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let _guard = RefpropFunctionLibrary::lock()?;
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+        let mixture = RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z)?;
+
+        assert!(RefpropFunctionLibrary::use_pure_component(0).is_err());
+        assert!(RefpropFunctionLibrary::use_pure_component(3).is_err());
+
+        {
+            let r32 = RefpropFunctionLibrary::use_pure_component(1)?;
+            assert_eq!(RefpropFunctionLibrary::pure_component()?, Some(1));
+            let pure = r32.flash(InputPair::TP, 300.0, 101.325)?;
+            assert_ne!(pure.D, mixture.D);
+        }
+
+        {
+            let _r32 = RefpropFunctionLibrary::use_pure_component(1)?;
+            assert!(matches!(
+                RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z),
+                Err(RefpropError::InvalidInput(_))
+            ));
+        }
+
+        assert_eq!(RefpropFunctionLibrary::pure_component()?, None);
+        let restored = RefpropFunctionLibrary::tp_flash(300.0, 101.325, &z)?;
+        assert_eq!(restored.D, mixture.D);
 
         Ok(())
     }
@@ -2,7 +2,8 @@ use std::ffi::CString;
 
 use crate::{
     bindings,
-    utils::{acquire_lock, check_refprop_error},
+    setup::{purefld::reset_pure_component, set_mixture::set_mixture_composition},
+    utils::{acquire_lock, check_refprop_error, fluids_changed},
     RefpropError, RefpropFunctionLibrary,
 };
 
@@ -96,6 +97,9 @@ impl RefpropFunctionLibrary {
         unsafe {
             bindings::SETFLUIDSdll(buffer.as_mut_ptr(), &mut ierr as *mut i32, hfld_length);
         }
+        fluids_changed(&guard);
+        reset_pure_component(&guard);
+        set_mixture_composition(&guard, None);
 
         check_refprop_error(&guard, ierr, herr_ptr, herr_length)?;
 
@@ -0,0 +1,106 @@
+use crate::{
+    bindings,
+    utils::{acquire_lock, check_refprop_error, setup_changed},
+    RefpropError, RefpropFunctionLibrary,
+};
+
+impl RefpropFunctionLibrary {
+    /// Sets the binary mixing model and parameters of a pair of loaded components using the `SETKTVdll` function.
+    ///
+    /// # Parameters
+    ///
+    /// - `icomp`: Index of the first component (1-based, in the order the fluids were loaded).
+    /// - `jcomp`: Index of the second component (1-based).
+    /// - `model`: Three-character mixing model (e.g., "KW0" for the Kunz-Wagner model without departure function).
+    /// - `fij`: Parameters of the mixing model (e.g., betaT, gammaT, betaV, gammaV, Fij for "KW0"). Maximum of 6 values.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if `model` is not three ASCII characters or more than 6 parameters are given.
+    /// - Returns `RefpropError::CalculationError` if REFPROP rejects the model or parameters.
+    ///
+    /// # References
+    ///
+    /// - [SETKTVdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/SETKTVdll)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     RefpropFunctionLibrary::set_fluids("R32;R1234YF")?;
+    ///
+    ///     RefpropFunctionLibrary::set_mixing_parameters(1, 2, "KW0", &[1.0, 1.0, 1.0, 1.0, 0.0])?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_mixing_parameters(
+        icomp: usize,
+        jcomp: usize,
+        model: &str,
+        fij: &[f64],
+    ) -> Result<(), RefpropError> {
+        if model.len() != 3 || !model.is_ascii() {
+            return Err(RefpropError::InvalidInput(
+                "Mixing model must be exactly three ASCII characters long".to_string(),
+            ));
+        }
+        if fij.len() > 6 {
+            return Err(RefpropError::InvalidInput(
+                "At most 6 mixing parameters are supported".to_string(),
+            ));
+        }
+
+        // Acquire the mutex lock to ensure exclusive access
+        let guard = acquire_lock()?;
+
+        // Define buffer sizes as per REFPROP's documentation
+        const HMODIJ_LENGTH: usize = 3;
+        const HFMIX_LENGTH: usize = 255;
+        const HERR_LENGTH: usize = 255;
+
+        let mut hmodij = [0 as libc::c_char; HMODIJ_LENGTH];
+        for (c, byte) in hmodij.iter_mut().zip(model.to_uppercase().bytes()) {
+            *c = byte as libc::c_char;
+        }
+
+        // Generalized mixing parameters are read from the default file
+        let mut hfmix = [b' ' as libc::c_char; HFMIX_LENGTH];
+        for (c, byte) in hfmix.iter_mut().zip(b"HMX.BNC") {
+            *c = *byte as libc::c_char;
+        }
+
+        let mut fij_buffer = [0.0f64; 6];
+        fij_buffer[..fij.len()].copy_from_slice(fij);
+
+        let mut icomp = icomp as i32;
+        let mut jcomp = jcomp as i32;
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call SETKTVdll within an unsafe block
+        unsafe {
+            bindings::SETKTVdll(
+                &mut icomp as *mut i32,
+                &mut jcomp as *mut i32,
+                hmodij.as_mut_ptr(),
+                fij_buffer.as_mut_ptr(),
+                hfmix.as_mut_ptr(),
+                &mut ierr as *mut i32,
+                herr_ptr,
+                HMODIJ_LENGTH as i32,
+                HFMIX_LENGTH as i32,
+                herr_length,
+            );
+        }
+        setup_changed(&guard);
+
+        check_refprop_error(&guard, ierr, herr_ptr, herr_length)?;
+
+        Ok(())
+    }
+}
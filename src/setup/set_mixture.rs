@@ -1,11 +1,71 @@
-use std::ffi::CString;
+use std::{
+    ffi::CString,
+    fs,
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
 
 use crate::{
     bindings,
-    utils::{acquire_lock, check_refprop_error},
+    catalogue::subdirectory,
+    lock::RefpropGuard,
+    setup::{purefld::reset_pure_component, set_path::active_path},
+    utils::{acquire_lock, check_refprop_error, fluids_changed},
     RefpropError, RefpropFunctionLibrary,
 };
 
+/// Composition of the mixture file loaded with `SETMIXTUREdll`, or `None` if fluids were loaded otherwise.
+static MIXTURE_COMPOSITION: Mutex<Option<Vec<f64>>> = Mutex::new(None);
+
+/// Returns the composition of the loaded mixture file, if any.
+pub(crate) fn mixture_composition() -> Option<Vec<f64>> {
+    MIXTURE_COMPOSITION
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Records the composition of a loaded mixture file. Must be called while holding the lock.
+pub(crate) fn set_mixture_composition(_guard: &RefpropGuard, z: Option<Vec<f64>>) {
+    *MIXTURE_COMPOSITION
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = z;
+}
+
+/// Returns whether `name` refers to an existing mixture file, with or without the `.mix` extension
+/// and ignoring case. A name without a directory is looked up in the `MIXTURES` subdirectory of the
+/// path set with `set_path`.
+fn mixture_file_exists(name: &str) -> bool {
+    let path = Path::new(name.trim());
+    let stem = match path.extension() {
+        Some(extension) if extension.eq_ignore_ascii_case("mix") => path.file_stem(),
+        _ => path.file_name(),
+    };
+    let directory = match path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        Some(parent) => Some(parent.to_path_buf()),
+        None => active_path().and_then(|root| subdirectory(&root, "MIXTURES")),
+    };
+    let (Some(stem), Some(directory)) = (stem, directory) else {
+        return false;
+    };
+
+    fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .any(|file| {
+            file.extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("mix"))
+                && file
+                    .file_stem()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(stem))
+        })
+}
+
 impl RefpropFunctionLibrary {
     /// Sets the mixture for the REFPROP library.
     ///
@@ -63,6 +123,9 @@ impl RefpropFunctionLibrary {
                 c_mixture_name.to_bytes_with_nul().len() as i32,
             );
         }
+        fluids_changed(&guard);
+        reset_pure_component(&guard);
+        set_mixture_composition(&guard, None);
 
         // Define buffer sizes as per REFPROP's documentation
         const HERR_LENGTH: usize = 255;
@@ -73,8 +136,93 @@ impl RefpropFunctionLibrary {
         check_refprop_error(&guard, ierr, herr_ptr, herr_length)?;
 
         // Convert the composition array to a Vec and return it
-        Ok(z.into_iter()
+        let z = z
+            .into_iter()
             .take_while(|&zi| zi > 0.0)
-            .collect::<Vec<f64>>())
+            .collect::<Vec<f64>>();
+        set_mixture_composition(&guard, Some(z.clone()));
+
+        Ok(z)
+    }
+
+    /// Loads a predefined mixture, or a pure fluid if no mixture file of that name exists.
+    ///
+    /// The pure fluid is only tried when the mixture file is missing, so that the error of a mixture
+    /// file that exists but fails to load is not replaced by the error of `set_fluids`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of a mixture file (e.g., "R454B") or of a fluid file (e.g., "WATER").
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<f64>`: The mole fractions of the mixture, or `[1.0]` for a pure fluid.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the error of `set_mixture` if the mixture file exists but cannot be loaded.
+    /// - Returns `RefpropError::InvalidInput` with the errors of both `set_mixture` and `set_fluids`
+    ///   if `name` is neither a mixture nor a fluid, or if it is a list of fluids.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///
+    ///     assert_eq!(RefpropFunctionLibrary::set_mixture_or_fluid("R454B")?.len(), 2);
+    ///     assert_eq!(RefpropFunctionLibrary::set_mixture_or_fluid("WATER")?, [1.0]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_mixture_or_fluid(name: &str) -> Result<Vec<f64>, RefpropError> {
+        if name.contains(';') {
+            return Err(RefpropError::InvalidInput(format!(
+                "'{}' is a list of fluids, which requires a composition.",
+                name
+            )));
+        }
+
+        // Keep the setup from changing between the two attempts
+        let _lock = acquire_lock()?;
+        let mixture_error = match Self::set_mixture(name) {
+            Ok(z) => return Ok(z),
+            Err(error) if mixture_file_exists(name) => return Err(error),
+            Err(error) => error,
+        };
+        match Self::set_fluids(name) {
+            Ok(()) => Ok(vec![1.0]),
+            Err(fluid_error) => Err(RefpropError::InvalidInput(format!(
+                "'{}' is neither a mixture ({}) nor a fluid ({}).",
+                name, mixture_error, fluid_error
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_mixture_file_exists() {
+        let directory = env::temp_dir().join(format!("refprop-mixtures-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("R454B.MIX"), "R454B\n").unwrap();
+        fs::write(directory.join("R32.FLD"), "R32\n").unwrap();
+
+        let exists = |name: &str| mixture_file_exists(directory.join(name).to_str().unwrap());
+        let found = [
+            exists("R454B"),
+            exists("r454b.mix"),
+            exists("R32"),
+            exists("R513A"),
+        ];
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(found, [true, true, false, false]);
     }
 }
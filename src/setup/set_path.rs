@@ -1,12 +1,85 @@
-use std::{env, ffi::CString};
-
-use crate::{
-    utils::{acquire_lock, check_refprop_error},
-    RefpropError, RefpropFunctionLibrary,
+use std::{
+    env,
+    ffi::{CString, OsString},
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
 };
 
+use crate::{catalogue::subdirectory, utils::acquire_lock, RefpropError, RefpropFunctionLibrary};
+
 use super::bindings;
 
+/// Directory last passed to `SETPATHdll`.
+static ACTIVE_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Directories tried after `RPPREFIX`, or `None` for `DEFAULT_FALLBACKS`.
+static PATH_FALLBACKS: Mutex<Option<Vec<PathBuf>>> = Mutex::new(None);
+
+/// Common installation directories of REFPROP.
+#[cfg(windows)]
+const DEFAULT_FALLBACKS: &[&str] = &["C:/Program Files (x86)/REFPROP", "C:/Program Files/REFPROP"];
+#[cfg(not(windows))]
+const DEFAULT_FALLBACKS: &[&str] = &["/opt/refprop", "/usr/local/refprop"];
+
+/// Returns the directory last set with `set_path`, if any.
+pub(crate) fn active_path() -> Option<PathBuf> {
+    ACTIVE_PATH
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Checks that a directory contains the `FLUIDS` and `MIXTURES` subdirectories, ignoring case.
+fn check_directory(path: &Path) -> Result<(), String> {
+    if !path.is_dir() {
+        return Err("not a directory".to_string());
+    }
+    for name in ["FLUIDS", "MIXTURES"] {
+        if subdirectory(path, name).is_none() {
+            return Err(format!("no {} subdirectory", name));
+        }
+    }
+    Ok(())
+}
+
+/// Resolves the REFPROP directory from an explicit path, or else from `RPPREFIX` and the fallbacks.
+///
+/// An explicit path is never replaced by another directory, so that a typo is reported rather than
+/// silently using a different installation.
+fn resolve_path(
+    path: Option<&str>,
+    prefix: Option<OsString>,
+    fallbacks: &[PathBuf],
+) -> Result<PathBuf, RefpropError> {
+    let candidates: Vec<PathBuf> = match path {
+        Some(path) => vec![PathBuf::from(path)],
+        None => prefix
+            .filter(|prefix| !prefix.is_empty())
+            .map(PathBuf::from)
+            .into_iter()
+            .chain(fallbacks.iter().cloned())
+            .collect(),
+    };
+    if candidates.is_empty() {
+        return Err(RefpropError::InitializationError(
+            "No REFPROP path given, RPPREFIX is not set, and no fallback directories are configured"
+                .to_string(),
+        ));
+    }
+
+    let mut failures = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        match check_directory(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(reason) => failures.push(format!("{} ({})", candidate.display(), reason)),
+        }
+    }
+    Err(RefpropError::InitializationError(format!(
+        "No valid REFPROP directory found; tried {}",
+        failures.join(", ")
+    )))
+}
+
 impl RefpropFunctionLibrary {
     /// Sets the path where the fluid files are located.
     ///
@@ -15,14 +88,19 @@ impl RefpropFunctionLibrary {
     /// where the DLL is stored if a fluids subdirectory (with the corresponding fluid files) is located there.
     /// For example, `path = "C:/Program Files (x86)/REFPROP"`.
     ///
+    /// Without an explicit path, the `RPPREFIX` environment variable is tried first, followed by the
+    /// directories of `set_path_fallbacks`. The directory must contain the `FLUIDS` and `MIXTURES`
+    /// subdirectories.
+    ///
     /// # Parameters
     ///
-    /// - `path`: The directory path to set for fluid files.
+    /// - `path`: The directory path to set for fluid files, or `None` to search for it.
     ///
     /// # Errors
     ///
-    /// - Returns `RefpropError::InvalidInput` if the provided path contains null bytes.
-    /// - Returns `RefpropError::CalculationError` if REFPROP encounters an error while setting the path.
+    /// - Returns `RefpropError::InitializationError` if the directory does not exist or lacks the
+    ///   `FLUIDS` or `MIXTURES` subdirectory, or if no directory is found without an explicit path.
+    /// - Returns `RefpropError::InvalidInput` if the path contains null bytes or exceeds 254 bytes.
     ///
     /// # References
     ///
@@ -35,38 +113,108 @@ impl RefpropFunctionLibrary {
     ///
     /// fn main() -> Result<(), RefpropError> {
     ///     RefpropFunctionLibrary::set_path(None)?;
+    ///     println!("Using {:?}", RefpropFunctionLibrary::active_path());
     ///     Ok(())
     /// }
     /// ```
     pub fn set_path(path: Option<&str>) -> Result<(), RefpropError> {
-        // Acquire the mutex lock to ensure exclusive access
-        let guard = acquire_lock()?;
+        let directory = resolve_path(path, env::var_os("RPPREFIX"), &Self::path_fallbacks())?;
 
-        // Convert Rust string to CString, ensuring no null bytes
-        let c_path = CString::new(path.unwrap_or(&env::var("RPPREFIX").unwrap()))
+        // Convert the path to a CString, ensuring no null bytes
+        let c_path = CString::new(directory.to_string_lossy().into_owned())
             .map_err(|e| RefpropError::InvalidInput(format!("Path contains null byte: {}", e)))?;
-
-        // Prepare a buffer of 255 characters, initialized to zero
-        let mut buffer = [0 as libc::c_char; 255];
         let c_bytes = c_path.as_bytes_with_nul();
+        if c_bytes.len() > 255 {
+            return Err(RefpropError::InvalidInput(format!(
+                "Path {} exceeds 254 bytes",
+                directory.display()
+            )));
+        }
+
+        // Acquire the mutex lock to ensure exclusive access
+        let _guard = acquire_lock()?;
 
-        // Copy up to 254 bytes into buffer, leaving space for null terminator
-        for (i, &byte) in c_bytes.iter().take(254).enumerate() {
+        // Prepare a buffer of 255 characters, initialized to zero, and copy the path with its terminator
+        let mut buffer = [0 as libc::c_char; 255];
+        for (i, &byte) in c_bytes.iter().enumerate() {
             buffer[i] = byte as libc::c_char;
         }
-        // Ensure null termination
-        buffer[254] = 0;
 
-        // Call SETPATHdll
+        // Call SETPATHdll, which reports no errors; the directory was checked above
         unsafe {
             bindings::SETPATHdll(buffer.as_mut_ptr(), 255);
         }
 
-        // Check for any errors using ERRMSGdll
-        let ierr: i32 = 0;
-        let mut herr = vec![0 as libc::c_char; 256];
-        check_refprop_error(&guard, ierr, herr.as_mut_ptr(), herr.len() as i32)?;
+        *ACTIVE_PATH.lock().unwrap_or_else(PoisonError::into_inner) = Some(directory);
 
         Ok(())
     }
+
+    /// Returns the directory last set with `set_path`, or `None` if no path has been set.
+    pub fn active_path() -> Option<PathBuf> {
+        active_path()
+    }
+
+    /// Sets the directories `set_path(None)` tries, in order, when `RPPREFIX` is not set or invalid.
+    ///
+    /// By default, the common installation directories of the platform are tried
+    /// (`C:/Program Files (x86)/REFPROP` and `C:/Program Files/REFPROP` on Windows, `/opt/refprop` and
+    /// `/usr/local/refprop` elsewhere). An empty list disables the fallbacks.
+    ///
+    /// # Parameters
+    ///
+    /// - `paths`: The fallback directories.
+    pub fn set_path_fallbacks<I, P>(paths: I)
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        *PATH_FALLBACKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) =
+            Some(paths.into_iter().map(Into::into).collect());
+    }
+
+    /// Returns the directories `set_path(None)` tries when `RPPREFIX` is not set or invalid.
+    pub fn path_fallbacks() -> Vec<PathBuf> {
+        PATH_FALLBACKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .unwrap_or_else(|| DEFAULT_FALLBACKS.iter().map(PathBuf::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_resolve_path() {
+        let root = env::temp_dir().join(format!("refprop-path-{}", std::process::id()));
+        let (valid, incomplete) = (root.join("valid"), root.join("incomplete"));
+        fs::create_dir_all(valid.join("fluids")).unwrap();
+        fs::create_dir_all(valid.join("MIXTURES")).unwrap();
+        fs::create_dir_all(incomplete.join("FLUIDS")).unwrap();
+
+        let explicit = resolve_path(incomplete.to_str(), None, std::slice::from_ref(&valid));
+        let prefix = resolve_path(None, Some(valid.clone().into()), &[]);
+        let fallback = resolve_path(
+            None,
+            Some(incomplete.clone().into()),
+            &[root.join("missing"), valid.clone()],
+        );
+        let unset = resolve_path(None, None, &[]);
+        fs::remove_dir_all(&root).unwrap();
+
+        // An explicit path is not replaced by a fallback
+        assert!(
+            matches!(explicit, Err(RefpropError::InitializationError(message))
+            if message.contains("no MIXTURES subdirectory"))
+        );
+        assert_eq!(prefix.unwrap(), valid);
+        assert_eq!(fallback.unwrap(), valid);
+        assert!(matches!(unset, Err(RefpropError::InitializationError(_))));
+    }
 }
@@ -0,0 +1,126 @@
+use crate::{
+    bindings,
+    utils::{acquire_lock, check_refprop_error, setup_changed, validate_composition},
+    RefpropError, RefpropFunctionLibrary,
+};
+
+/// Represents the reference state for enthalpy and entropy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReferenceState {
+    /// Default reference state of each fluid file ("DEF").
+    Default,
+    /// h = 0 and s = 0 for the saturated liquid at the normal boiling point ("NBP").
+    NormalBoilingPoint,
+    /// ASHRAE convention: h = 0 and s = 0 for the saturated liquid at -40 °C ("ASH").
+    Ashrae,
+    /// IIR convention: h = 200 kJ/kg and s = 1 kJ/(kg-K) for the saturated liquid at 0 °C ("IIR").
+    Iir,
+    /// User-specified enthalpy [J/mol] and entropy [J/mol-K] at temperature [K] and pressure [kPa] ("OTH").
+    Other { T0: f64, P0: f64, h0: f64, s0: f64 },
+}
+
+impl ReferenceState {
+    /// Returns the three-character code of the reference state expected by REFPROP.
+    fn code(&self) -> &'static [u8; 3] {
+        match self {
+            ReferenceState::Default => b"DEF",
+            ReferenceState::NormalBoilingPoint => b"NBP",
+            ReferenceState::Ashrae => b"ASH",
+            ReferenceState::Iir => b"IIR",
+            ReferenceState::Other { .. } => b"OTH",
+        }
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Sets the reference state for enthalpy and entropy using the `SETREFdll` function.
+    ///
+    /// The predefined reference states are applied to each loaded component. `ReferenceState::Other`
+    /// is applied to the mixture of composition `z` as a whole.
+    ///
+    /// # Parameters
+    ///
+    /// - `reference`: The reference state.
+    /// - `z`: Composition to which `ReferenceState::Other` applies; ignored for the predefined states. Maximum of 20 components.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the composition is invalid.
+    /// - Returns `RefpropError::CalculationError` if REFPROP cannot apply the reference state.
+    ///
+    /// # References
+    ///
+    /// - [SETREFdll Documentation](https://refprop-docs.readthedocs.io/en/latest/DLL/legacy.html#f/_/SETREFdll)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use refprop_sys::{ReferenceState, RefpropFunctionLibrary, RefpropError};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     RefpropFunctionLibrary::set_reference_state(ReferenceState::Ashrae, &z)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_reference_state(reference: ReferenceState, z: &[f64]) -> Result<(), RefpropError> {
+        let (ixflag, T0, P0, h0, s0) = match reference {
+            ReferenceState::Other { T0, P0, h0, s0 } => {
+                validate_composition(z)?;
+                (2, T0, P0, h0, s0)
+            }
+            _ => (1, 0.0, 0.0, 0.0, 0.0),
+        };
+
+        // Acquire the mutex lock to ensure exclusive access
+        let guard = acquire_lock()?;
+
+        // Define buffer sizes as per REFPROP's documentation
+        const HRF_LENGTH: usize = 3;
+        const HERR_LENGTH: usize = 255;
+
+        let mut hrf = [0 as libc::c_char; HRF_LENGTH];
+        for (c, &byte) in hrf.iter_mut().zip(reference.code()) {
+            *c = byte as libc::c_char;
+        }
+
+        // Convert z slice to a fixed-size array with padding
+        let mut x0 = [0.0f64; 20];
+        x0[..z.len().min(20)].copy_from_slice(&z[..z.len().min(20)]);
+
+        let mut ixflag: i32 = ixflag;
+        let mut T0 = T0;
+        let mut P0 = P0;
+        let mut h0 = h0;
+        let mut s0 = s0;
+        let mut ierr: i32 = 0;
+        let mut herr_buffer = vec![0 as libc::c_char; HERR_LENGTH];
+        let herr_ptr = herr_buffer.as_mut_ptr();
+        let herr_length = HERR_LENGTH as i32;
+
+        // Call SETREFdll within an unsafe block
+        unsafe {
+            bindings::SETREFdll(
+                hrf.as_mut_ptr(),
+                &mut ixflag as *mut i32,
+                x0.as_mut_ptr(),
+                &mut h0 as *mut f64,
+                &mut s0 as *mut f64,
+                &mut T0 as *mut f64,
+                &mut P0 as *mut f64,
+                &mut ierr as *mut i32,
+                herr_ptr,
+                HRF_LENGTH as i32,
+                herr_length,
+            );
+        }
+        setup_changed(&guard);
+
+        check_refprop_error(&guard, ierr, herr_ptr, herr_length)?;
+
+        Ok(())
+    }
+}
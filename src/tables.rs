@@ -0,0 +1,84 @@
+mod saturation;
+mod superheated;
+
+pub use saturation::{
+    SaturatedState, SaturationAxis, SaturationRow, SaturationStatus, SaturationTable,
+};
+pub use superheated::{SuperheatedRow, SuperheatedStatus, SuperheatedTable};
+
+/// Represents the region of a single-phase or two-phase state as reported by the flash routines.
+///
+/// REFPROP encodes the region in the quality returned by the flash routines: `-998` for subcooled
+/// liquid, `998` for superheated vapor, `999` for supercritical states, and `0..=1` for two-phase states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StateRegion {
+    /// Subcooled (compressed) liquid.
+    SubcooledLiquid,
+    /// Two-phase state (including the saturation boundaries).
+    TwoPhase,
+    /// Superheated vapor.
+    SuperheatedVapor,
+    /// Supercritical fluid.
+    Supercritical,
+}
+
+impl StateRegion {
+    /// Classifies a state from the quality returned by a flash routine.
+    pub fn from_quality(q: f64) -> Self {
+        if q >= 999.0 {
+            StateRegion::Supercritical
+        } else if q > 1.0 {
+            StateRegion::SuperheatedVapor
+        } else if q < 0.0 {
+            StateRegion::SubcooledLiquid
+        } else {
+            StateRegion::TwoPhase
+        }
+    }
+
+    /// Returns the lowercase identifier used in the CSV and JSON outputs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StateRegion::SubcooledLiquid => "liquid",
+            StateRegion::TwoPhase => "two-phase",
+            StateRegion::SuperheatedVapor => "vapor",
+            StateRegion::Supercritical => "supercritical",
+        }
+    }
+}
+
+/// Formats an optional value as a CSV cell, leaving the cell empty for missing or non-finite values.
+pub(crate) fn csv_cell(value: Option<f64>) -> String {
+    match value {
+        Some(v) if v.is_finite() => v.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Formats an optional value as a JSON number, using `null` for missing or non-finite values.
+pub(crate) fn json_number(value: Option<f64>) -> String {
+    match value {
+        Some(v) if v.is_finite() => v.to_string(),
+        _ => "null".to_string(),
+    }
+}
+
+/// Escapes a string for inclusion in a JSON document.
+pub(crate) fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -0,0 +1,415 @@
+use std::io::{self, Write};
+
+use crate::{
+    flash_routines::FlashOutput, utils::validate_composition, Basis, CriticalParameters, KrKqFlag,
+    Phase, RefpropError, RefpropFunctionLibrary,
+};
+
+use super::{csv_cell, json_number, json_string};
+
+/// Relative difference between the saturated liquid and vapor densities below which a point is
+/// treated as the critical point.
+const CRITICAL_DENSITY_TOLERANCE: f64 = 1e-4;
+
+/// Column headers of the saturation table, in the order returned by `SaturationRow::values`.
+const COLUMNS: [&str; 14] = [
+    "T_liq [K]",
+    "P_liq [kPa]",
+    "T_vap [K]",
+    "P_vap [kPa]",
+    "Dl [mol/L]",
+    "Dv [mol/L]",
+    "hl [J/mol]",
+    "hv [J/mol]",
+    "sl [J/mol-K]",
+    "sv [J/mol-K]",
+    "eta_l [uPa-s]",
+    "eta_v [uPa-s]",
+    "tcx_l [W/(m-K)]",
+    "tcx_v [W/(m-K)]",
+];
+
+/// Represents the independent variable of a saturation table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SaturationAxis {
+    /// Rows are evaluated at the given temperatures [K] using `tq_flash`.
+    Temperature,
+    /// Rows are evaluated at the given pressures [kPa] using `pq_flash`.
+    Pressure,
+}
+
+impl SaturationAxis {
+    /// Returns the lowercase identifier used in the JSON output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SaturationAxis::Temperature => "temperature",
+            SaturationAxis::Pressure => "pressure",
+        }
+    }
+}
+
+/// Represents the outcome of the calculation of a single row of a saturation table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SaturationStatus {
+    /// Both saturated states were calculated.
+    Converged,
+    /// The saturated liquid and vapor states coincide; transport properties are not evaluated.
+    Critical,
+    /// The requested temperature or pressure lies above the critical point of a pure fluid.
+    AboveCritical,
+    /// REFPROP failed to calculate one of the saturated states.
+    Failed(String),
+}
+
+impl SaturationStatus {
+    /// Returns the identifier used in the CSV and JSON outputs.
+    pub fn as_str(&self) -> &str {
+        match self {
+            SaturationStatus::Converged => "ok",
+            SaturationStatus::Critical => "critical",
+            SaturationStatus::AboveCritical => "above-critical",
+            SaturationStatus::Failed(_) => "failed",
+        }
+    }
+}
+
+/// Represents a saturated liquid or vapor state.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaturatedState {
+    /// Temperature [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_K"))]
+    pub T: f64,
+    /// Pressure [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "P_kPa"))]
+    pub P: f64,
+    /// Density of the saturated phase [mol/L]
+    #[cfg_attr(feature = "serde", serde(rename = "D_mol_per_L"))]
+    pub D: f64,
+    /// Enthalpy of the saturated phase [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "h_J_per_mol"))]
+    pub h: f64,
+    /// Entropy of the saturated phase [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "s_J_per_mol_K"))]
+    pub s: f64,
+    /// Composition of the saturated phase (mole fractions)
+    pub composition: Vec<f64>,
+    /// Dynamic viscosity [uPa-s]
+    #[cfg_attr(feature = "serde", serde(rename = "eta_uPa_s"))]
+    pub eta: Option<f64>, // Not evaluated at the critical point
+    /// Thermal conductivity [W/(m-K)]
+    #[cfg_attr(feature = "serde", serde(rename = "tcx_W_per_m_K"))]
+    pub tcx: Option<f64>, // Not evaluated at the critical point
+}
+
+/// Represents a single row of a saturation table.
+///
+/// For mixtures, the saturated liquid is the bubble point and the saturated vapor is the dew point,
+/// so the temperatures (pressure axis) or pressures (temperature axis) of the two states differ.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaturationRow {
+    /// Value of the independent variable [K or kPa]
+    pub value: f64,
+    /// Saturated liquid (bubble point) state
+    pub liquid: Option<SaturatedState>,
+    /// Saturated vapor (dew point) state
+    pub vapor: Option<SaturatedState>,
+    /// Outcome of the calculation of this row
+    pub status: SaturationStatus,
+}
+
+impl SaturationRow {
+    /// Returns the row values in the order of the table columns.
+    fn values(&self) -> [Option<f64>; 14] {
+        let l = self.liquid.as_ref();
+        let v = self.vapor.as_ref();
+        [
+            l.map(|st| st.T),
+            l.map(|st| st.P),
+            v.map(|st| st.T),
+            v.map(|st| st.P),
+            l.map(|st| st.D),
+            v.map(|st| st.D),
+            l.map(|st| st.h),
+            v.map(|st| st.h),
+            l.map(|st| st.s),
+            v.map(|st| st.s),
+            l.and_then(|st| st.eta),
+            v.and_then(|st| st.eta),
+            l.and_then(|st| st.tcx),
+            v.and_then(|st| st.tcx),
+        ]
+    }
+}
+
+/// Represents a saturation table generated by the `saturation_table` method.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaturationTable {
+    /// Independent variable of the table
+    pub axis: SaturationAxis,
+    /// Overall composition used to generate the table (mole fractions)
+    pub composition: Vec<f64>,
+    /// Critical parameters of the fluid at the table composition
+    pub critical: CriticalParameters,
+    /// Rows of the table, in the order of the requested values
+    pub rows: Vec<SaturationRow>,
+}
+
+impl SaturationTable {
+    /// Writes the table as CSV with a header row containing the column names and units.
+    ///
+    /// Missing values (e.g., rows that failed or transport properties at the critical point) are
+    /// written as empty cells.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{},status", COLUMNS.join(","))?;
+        for row in &self.rows {
+            let cells = row
+                .values()
+                .iter()
+                .map(|&value| csv_cell(value))
+                .collect::<Vec<String>>();
+            writeln!(writer, "{},{}", cells.join(","), row.status.as_str())?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the table as a JSON document.
+    ///
+    /// Each row is an object keyed by the column names (including units), with `null` for missing
+    /// values, a `status` field and, for failed rows, an `error` field with the REFPROP message.
+    pub fn write_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let composition = self
+            .composition
+            .iter()
+            .map(|&zi| json_number(Some(zi)))
+            .collect::<Vec<String>>();
+
+        writeln!(writer, "{{")?;
+        writeln!(writer, "  \"axis\": {},", json_string(self.axis.as_str()))?;
+        writeln!(writer, "  \"composition\": [{}],", composition.join(", "))?;
+        writeln!(
+            writer,
+            "  \"critical\": {{\"Tc [K]\": {}, \"Pc [kPa]\": {}, \"Dc [mol/L]\": {}}},",
+            json_number(Some(self.critical.Tc)),
+            json_number(Some(self.critical.Pc)),
+            json_number(Some(self.critical.Dc)),
+        )?;
+        writeln!(writer, "  \"rows\": [")?;
+        for (i, row) in self.rows.iter().enumerate() {
+            let mut fields = COLUMNS
+                .iter()
+                .zip(row.values())
+                .map(|(name, value)| format!("{}: {}", json_string(name), json_number(value)))
+                .collect::<Vec<String>>();
+            fields.push(format!("\"status\": {}", json_string(row.status.as_str())));
+            if let SaturationStatus::Failed(message) = &row.status {
+                fields.push(format!("\"error\": {}", json_string(message)));
+            }
+            let separator = if i + 1 < self.rows.len() { "," } else { "" };
+            writeln!(writer, "    {{{}}}{}", fields.join(", "), separator)?;
+        }
+        writeln!(writer, "  ]")?;
+        writeln!(writer, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Generates a saturation table over a range of temperatures or pressures.
+    ///
+    /// Each row contains the saturated liquid (bubble point, `q = 0`) and saturated vapor (dew point,
+    /// `q = 1`) states calculated with `tq_flash` or `pq_flash`, along with the transport properties of
+    /// each phase from `transport_properties`. All properties are on a molar basis.
+    ///
+    /// **Note:** Points above the critical temperature or pressure of a pure fluid are reported as
+    /// `SaturationStatus::AboveCritical` without calling REFPROP. Mixtures are always attempted since
+    /// their phase envelope may extend beyond the critical point (retrograde region). Points where the
+    /// liquid and vapor densities coincide are reported as `SaturationStatus::Critical` and their
+    /// transport properties, which diverge at the critical point, are left undefined.
+    ///
+    /// # Parameters
+    ///
+    /// - `axis`: Specifies whether `values` are temperatures [K] or pressures [kPa].
+    /// - `values`: The temperatures or pressures at which to evaluate the table rows.
+    /// - `z`: A slice containing the overall composition (mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `SaturationTable`: A struct containing one row per requested value. Failures at individual
+    ///   points are recorded in the row status rather than aborting the table.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if:
+    ///     - `z` contains more than 20 elements.
+    ///     - The sum of mole fractions in `z` does not equal 1 within a specified tolerance.
+    /// - Returns `RefpropError::CalculationError` if the critical parameters cannot be calculated.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use refprop_sys::{RefpropFunctionLibrary, RefpropError, SaturationAxis};
+    ///
+    /// fn main() -> Result<(), RefpropError> {
+    ///     let _ = RefpropFunctionLibrary::set_path(None);
+    ///     let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+    ///
+    ///     // Saturation table from 250 K to 340 K in 10 K steps
+    ///     let temperatures = (0..10).map(|i| 250.0 + 10.0 * i as f64).collect::<Vec<f64>>();
+    ///     let table = RefpropFunctionLibrary::saturation_table(SaturationAxis::Temperature, &temperatures, &z)?;
+    ///
+    ///     table.write_csv(std::io::stdout()).unwrap();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn saturation_table(
+        axis: SaturationAxis,
+        values: &[f64],
+        z: &[f64],
+    ) -> Result<SaturationTable, RefpropError> {
+        // Validate composition slice
+        validate_composition(z)?;
+
+        // The critical point bounds the saturation curve of a pure fluid
+        let critical = Self::critical_parameters(z)?;
+        let is_pure = z.iter().filter(|&&zi| zi > 0.0).count() == 1;
+
+        let rows = values
+            .iter()
+            .map(|&value| {
+                let limit = match axis {
+                    SaturationAxis::Temperature => critical.Tc,
+                    SaturationAxis::Pressure => critical.Pc,
+                };
+                if is_pure && value > limit {
+                    return SaturationRow {
+                        value,
+                        liquid: None,
+                        vapor: None,
+                        status: SaturationStatus::AboveCritical,
+                    };
+                }
+                Self::saturation_row(axis, value, z)
+            })
+            .collect::<Vec<SaturationRow>>();
+
+        Ok(SaturationTable {
+            axis,
+            composition: z.to_vec(),
+            critical,
+            rows,
+        })
+    }
+
+    /// Calculates the saturated liquid and vapor states of a single table row.
+    fn saturation_row(axis: SaturationAxis, value: f64, z: &[f64]) -> SaturationRow {
+        let flash = |q: f64| match axis {
+            SaturationAxis::Temperature => Self::tq_flash(
+                value,
+                q,
+                z,
+                Basis::Molar,
+                Phase::Unknown,
+                KrKqFlag::QualityMolar,
+            ),
+            SaturationAxis::Pressure => Self::pq_flash(
+                value,
+                q,
+                z,
+                Basis::Molar,
+                Phase::Unknown,
+                KrKqFlag::QualityMolar,
+            ),
+        };
+
+        let (liquid, vapor) = match (flash(0.0), flash(1.0)) {
+            (Ok(liquid), Ok(vapor)) => (liquid, vapor),
+            (Err(e), _) | (_, Err(e)) => {
+                return SaturationRow {
+                    value,
+                    liquid: None,
+                    vapor: None,
+                    status: SaturationStatus::Failed(e.to_string()),
+                }
+            }
+        };
+
+        // Identical phase densities indicate the critical point, where transport properties diverge
+        let is_critical = (liquid.Dl - vapor.Dv).abs()
+            <= CRITICAL_DENSITY_TOLERANCE * liquid.Dl.abs().max(vapor.Dv.abs());
+
+        let liquid = Self::saturated_state(&liquid, liquid.Dl, &liquid.x, is_critical);
+        let vapor = Self::saturated_state(&vapor, vapor.Dv, &vapor.y, is_critical);
+
+        SaturationRow {
+            value,
+            liquid: Some(liquid),
+            vapor: Some(vapor),
+            status: if is_critical {
+                SaturationStatus::Critical
+            } else {
+                SaturationStatus::Converged
+            },
+        }
+    }
+
+    /// Builds a saturated state from a flash result and the density and composition of the phase.
+    fn saturated_state(
+        flash: &FlashOutput,
+        D: f64,
+        composition: &[f64],
+        is_critical: bool,
+    ) -> SaturatedState {
+        // Transport properties are optional; they may be unavailable for some fluids
+        let transport = if is_critical {
+            None
+        } else {
+            Self::transport_properties(flash.T, D, composition).ok()
+        };
+
+        SaturatedState {
+            T: flash.T,
+            P: flash.P,
+            D,
+            h: flash.h,
+            s: flash.s,
+            composition: composition.to_vec(),
+            eta: transport.as_ref().map(|t| t.eta),
+            tcx: transport.as_ref().map(|t| t.tcx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saturation_table() -> Result<(), RefpropError> {
+        let _ = RefpropFunctionLibrary::set_path(None);
+        let z = RefpropFunctionLibrary::set_mixture("R454B")?;
+
+        let temperatures = [250.0, 275.0, 300.0, 325.0];
+        let table = RefpropFunctionLibrary::saturation_table(
+            SaturationAxis::Temperature,
+            &temperatures,
+            &z,
+        )?;
+
+        assert_eq!(table.rows.len(), temperatures.len());
+        assert_eq!(table.rows[0].status, SaturationStatus::Converged);
+
+        let mut csv = Vec::new();
+        table.write_csv(&mut csv).unwrap();
+        println!("{}", String::from_utf8(csv).unwrap());
+
+        Ok(())
+    }
+}
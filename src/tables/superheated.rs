@@ -0,0 +1,302 @@
+use std::io::{self, Write};
+
+use crate::{utils::validate_composition, RefpropError, RefpropFunctionLibrary};
+
+use super::{csv_cell, json_number, json_string, StateRegion};
+
+/// Column headers of the superheated table, in the order returned by `SuperheatedRow::values`.
+const COLUMNS: [&str; 10] = [
+    "P [kPa]",
+    "T [K]",
+    "D [mol/L]",
+    "h [J/mol]",
+    "s [J/mol-K]",
+    "Cv [J/mol-K]",
+    "Cp [J/mol-K]",
+    "w [m/s]",
+    "eta [uPa-s]",
+    "tcx [W/(m-K)]",
+];
+
+/// Represents the outcome of the calculation of a single point of a superheated vapor table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SuperheatedStatus {
+    /// The state was calculated.
+    Converged,
+    /// REFPROP failed to calculate the state.
+    Failed(String),
+}
+
+impl SuperheatedStatus {
+    /// Returns the identifier used in the CSV and JSON outputs.
+    pub fn as_str(&self) -> &str {
+        match self {
+            SuperheatedStatus::Converged => "ok",
+            SuperheatedStatus::Failed(_) => "failed",
+        }
+    }
+}
+
+/// Represents a single (P, T) point of a superheated vapor table.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuperheatedRow {
+    /// Pressure [kPa]
+    #[cfg_attr(feature = "serde", serde(rename = "P_kPa"))]
+    pub P: f64,
+    /// Temperature [K]
+    #[cfg_attr(feature = "serde", serde(rename = "T_K"))]
+    pub T: f64,
+    /// Density [mol/L]
+    #[cfg_attr(feature = "serde", serde(rename = "D_mol_per_L"))]
+    pub D: Option<f64>,
+    /// Enthalpy [J/mol]
+    #[cfg_attr(feature = "serde", serde(rename = "h_J_per_mol"))]
+    pub h: Option<f64>,
+    /// Entropy [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "s_J_per_mol_K"))]
+    pub s: Option<f64>,
+    /// Isochoric heat capacity [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "Cv_J_per_mol_K"))]
+    pub Cv: Option<f64>,
+    /// Isobaric heat capacity [J/mol-K]
+    #[cfg_attr(feature = "serde", serde(rename = "Cp_J_per_mol_K"))]
+    pub Cp: Option<f64>,
+    /// Speed of sound [m/s]
+    #[cfg_attr(feature = "serde", serde(rename = "w_m_per_s"))]
+    pub w: Option<f64>,
+    /// Dynamic viscosity [uPa-s]
+    #[cfg_attr(feature = "serde", serde(rename = "eta_uPa_s"))]
+    pub eta: Option<f64>, // Not evaluated for two-phase states
+    /// Thermal conductivity [W/(m-K)]
+    #[cfg_attr(feature = "serde", serde(rename = "tcx_W_per_m_K"))]
+    pub tcx: Option<f64>, // Not evaluated for two-phase states
+    /// Region of the state; points that are not superheated are kept so the grid stays rectangular
+    pub region: Option<StateRegion>, // Not known for failed points
+    /// Outcome of the calculation of this point
+    pub status: SuperheatedStatus,
+}
+
+impl SuperheatedRow {
+    /// Returns a row for a point that REFPROP failed to calculate.
+    fn failed(P: f64, T: f64, message: String) -> Self {
+        SuperheatedRow {
+            P,
+            T,
+            D: None,
+            h: None,
+            s: None,
+            Cv: None,
+            Cp: None,
+            w: None,
+            eta: None,
+            tcx: None,
+            region: None,
+            status: SuperheatedStatus::Failed(message),
+        }
+    }
+
+    /// Returns the row values in the order of the table columns.
+    fn values(&self) -> [Option<f64>; 10] {
+        [
+            Some(self.P),
+            Some(self.T),
+            self.D,
+            self.h,
+            self.s,
+            self.Cv,
+            self.Cp,
+            self.w,
+            self.eta,
+            self.tcx,
+        ]
+    }
+}
+
+/// Represents a superheated vapor table generated by the `superheated_table` method.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuperheatedTable {
+    /// Overall composition used to generate the table (mole fractions)
+    pub composition: Vec<f64>,
+    /// Rows of the table, ordered by pressure and then by temperature
+    pub rows: Vec<SuperheatedRow>,
+}
+
+impl SuperheatedTable {
+    /// Returns an iterator over the rows that lie in the superheated vapor or supercritical region.
+    pub fn superheated(&self) -> impl Iterator<Item = &SuperheatedRow> {
+        self.rows.iter().filter(|row| {
+            matches!(
+                row.region,
+                Some(StateRegion::SuperheatedVapor | StateRegion::Supercritical)
+            )
+        })
+    }
+
+    /// Writes the table as CSV with a header row containing the column names and units.
+    ///
+    /// Missing values (e.g., points that failed) are written as empty cells.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{},region,status", COLUMNS.join(","))?;
+        for row in &self.rows {
+            let cells = row
+                .values()
+                .iter()
+                .map(|&value| csv_cell(value))
+                .collect::<Vec<String>>();
+            writeln!(
+                writer,
+                "{},{},{}",
+                cells.join(","),
+                row.region.map_or("", |region| region.as_str()),
+                row.status.as_str()
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the table as a JSON document with one object per (P, T) point.
+    ///
+    /// Each point has a `status` field and, for failed points, an `error` field with the REFPROP message.
+    pub fn write_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let composition = self
+            .composition
+            .iter()
+            .map(|&zi| json_number(Some(zi)))
+            .collect::<Vec<String>>();
+
+        writeln!(writer, "{{")?;
+        writeln!(writer, "  \"composition\": [{}],", composition.join(", "))?;
+        writeln!(writer, "  \"rows\": [")?;
+        for (i, row) in self.rows.iter().enumerate() {
+            let mut fields = COLUMNS
+                .iter()
+                .zip(row.values())
+                .map(|(name, value)| format!("{}: {}", json_string(name), json_number(value)))
+                .collect::<Vec<String>>();
+            let region = row
+                .region
+                .map_or("null".to_string(), |region| json_string(region.as_str()));
+            fields.push(format!("\"region\": {}", region));
+            fields.push(format!("\"status\": {}", json_string(row.status.as_str())));
+            if let SuperheatedStatus::Failed(message) = &row.status {
+                fields.push(format!("\"error\": {}", json_string(message)));
+            }
+            let separator = if i + 1 < self.rows.len() { "," } else { "" };
+            writeln!(writer, "    {{{}}}{}", fields.join(", "), separator)?;
+        }
+        writeln!(writer, "  ]")?;
+        writeln!(writer, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl RefpropFunctionLibrary {
+    /// Generates a superheated vapor table over a grid of pressures and temperatures.
+    ///
+    /// Each (P, T) point is calculated with `tp_flash` and `transport_properties`. Points that fall in
+    /// the liquid or two-phase region are kept in the table with their `region` set accordingly, so
+    /// that the grid remains rectangular; use `SuperheatedTable::superheated` to iterate over the
+    /// superheated points only. All properties are on a molar basis.
+    ///
+    /// Failures at individual points are recorded in the row status rather than aborting the table.
+    ///
+    /// # Parameters
+    ///
+    /// - `pressures`: The pressures [kPa] of the grid.
+    /// - `temperatures`: The temperatures [K] of the grid.
+    /// - `z`: A slice containing the overall composition (mole fractions). Maximum of 20 components.
+    ///
+    /// # Returns
+    ///
+    /// - `SuperheatedTable`: A struct containing one row per (P, T) point, ordered by pressure.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if:
+    ///     - `z` contains more than 20 elements.
+    ///     - The sum of mole fractions in `z` does not equal 1 within a specified tolerance.
+    /// - Returns `RefpropError::MutexPoisoned` if the REFPROP mutex is poisoned.
+    pub fn superheated_table(
+        pressures: &[f64],
+        temperatures: &[f64],
+        z: &[f64],
+    ) -> Result<SuperheatedTable, RefpropError> {
+        // Validate composition slice
+        validate_composition(z)?;
+
+        let mut rows = Vec::with_capacity(pressures.len() * temperatures.len());
+        for &P in pressures {
+            for &T in temperatures {
+                let state = match Self::tp_flash(T, P, z) {
+                    Ok(state) => state,
+                    Err(e) => {
+                        rows.push(SuperheatedRow::failed(P, T, e.to_string()));
+                        continue;
+                    }
+                };
+                let region = StateRegion::from_quality(state.q);
+
+                // Transport properties are only defined for single-phase states
+                let transport = if region == StateRegion::TwoPhase {
+                    None
+                } else {
+                    Self::transport_properties(state.T, state.D, z).ok()
+                };
+
+                rows.push(SuperheatedRow {
+                    P: state.P,
+                    T: state.T,
+                    D: Some(state.D),
+                    h: Some(state.h),
+                    s: Some(state.s),
+                    Cv: state.Cv,
+                    Cp: state.Cp,
+                    w: Some(state.w),
+                    eta: transport.as_ref().map(|t| t.eta),
+                    tcx: transport.as_ref().map(|t| t.tcx),
+                    region: Some(region),
+                    status: SuperheatedStatus::Converged,
+                });
+            }
+        }
+
+        Ok(SuperheatedTable {
+            composition: z.to_vec(),
+            rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_rows_are_recorded() {
+        let table = SuperheatedTable {
+            composition: vec![1.0],
+            rows: vec![SuperheatedRow::failed(
+                100.0,
+                5000.0,
+                "Temperature above limit".to_string(),
+            )],
+        };
+        assert_eq!(table.superheated().count(), 0);
+
+        let mut csv = Vec::new();
+        table.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.lines().nth(1).unwrap().ends_with(",,failed"));
+
+        let mut json = Vec::new();
+        table.write_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.contains("\"region\": null"));
+        assert!(json.contains("\"error\": \"Temperature above limit\""));
+    }
+}
@@ -0,0 +1,205 @@
+use std::str::FromStr;
+
+use crate::{RefpropError, Units};
+
+/// Represents a physical quantity whose units depend on the unit system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quantity {
+    /// Temperature (default: K).
+    Temperature,
+    /// Pressure (default: kPa).
+    Pressure,
+    /// Density (default: mol/L).
+    Density,
+    /// Internal energy or enthalpy (default: J/mol).
+    Energy,
+    /// Entropy or heat capacity (default: J/mol-K).
+    Entropy,
+    /// Vapor quality on a molar basis (mol/mol in every unit system).
+    Quality,
+    /// Speed of sound (default: m/s).
+    Speed,
+    /// Dynamic viscosity (default: uPa-s).
+    Viscosity,
+    /// Thermal conductivity (default: W/(m-K)).
+    ThermalConductivity,
+}
+
+/// Linear conversion `value = default * scale + offset` from the default units to a unit system.
+struct Conversion {
+    scale: f64,
+    offset: f64,
+    label: &'static str,
+}
+
+impl Units {
+    /// Converts a value from the default REFPROP units (K, kPa, mol/L, J/mol, ...) to this unit system.
+    ///
+    /// Only the SI unit systems (`Default`, `MolarSI`, `MassSI`, `SIWithC`, `MolarBaseSI`, and
+    /// `MassBaseSI`) are supported.
+    ///
+    /// # Parameters
+    ///
+    /// - `quantity`: The physical quantity of `value`.
+    /// - `value`: The value in the default units.
+    /// - `molar_mass`: Molar mass of the fluid [g/mol], used by the mass-based unit systems.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the unit system is not supported.
+    pub fn from_default(
+        &self,
+        quantity: Quantity,
+        value: f64,
+        molar_mass: f64,
+    ) -> Result<f64, RefpropError> {
+        let conversion = self.conversion(quantity, molar_mass)?;
+        Ok(value * conversion.scale + conversion.offset)
+    }
+
+    /// Converts a value from this unit system to the default REFPROP units (K, kPa, mol/L, J/mol, ...).
+    ///
+    /// # Parameters
+    ///
+    /// - `quantity`: The physical quantity of `value`.
+    /// - `value`: The value in this unit system.
+    /// - `molar_mass`: Molar mass of the fluid [g/mol], used by the mass-based unit systems.
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the unit system is not supported.
+    pub fn to_default(
+        &self,
+        quantity: Quantity,
+        value: f64,
+        molar_mass: f64,
+    ) -> Result<f64, RefpropError> {
+        let conversion = self.conversion(quantity, molar_mass)?;
+        Ok((value - conversion.offset) / conversion.scale)
+    }
+
+    /// Returns the unit of a quantity in this unit system (e.g., `"kJ/kg"`).
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RefpropError::InvalidInput` if the unit system is not supported.
+    pub fn label(&self, quantity: Quantity) -> Result<&'static str, RefpropError> {
+        Ok(self.conversion(quantity, 1.0)?.label)
+    }
+
+    fn conversion(&self, quantity: Quantity, molar_mass: f64) -> Result<Conversion, RefpropError> {
+        if !matches!(
+            self,
+            Units::Default
+                | Units::MolarSI
+                | Units::MassSI
+                | Units::SIWithC
+                | Units::MolarBaseSI
+                | Units::MassBaseSI
+        ) {
+            return Err(RefpropError::InvalidInput(format!(
+                "Unit conversion is not supported for {:?} units.",
+                self
+            )));
+        }
+
+        let mass = matches!(self, Units::MassSI | Units::SIWithC | Units::MassBaseSI);
+        let base = matches!(self, Units::MolarBaseSI | Units::MassBaseSI);
+        let M = molar_mass;
+
+        let (scale, offset, label) = match quantity {
+            Quantity::Temperature if matches!(self, Units::SIWithC) => (1.0, -273.15, "°C"),
+            Quantity::Temperature => (1.0, 0.0, "K"),
+            Quantity::Pressure if matches!(self, Units::Default) => (1.0, 0.0, "kPa"),
+            Quantity::Pressure if base => (1e3, 0.0, "Pa"),
+            Quantity::Pressure => (1e-3, 0.0, "MPa"),
+            Quantity::Density => match (mass, base) {
+                (false, false) => (1.0, 0.0, "mol/L"),
+                (false, true) => (1e3, 0.0, "mol/m³"),
+                (true, _) => (M, 0.0, "kg/m³"),
+            },
+            Quantity::Energy => match (mass, base) {
+                (false, _) => (1.0, 0.0, "J/mol"),
+                (true, false) => (1.0 / M, 0.0, "kJ/kg"),
+                (true, true) => (1e3 / M, 0.0, "J/kg"),
+            },
+            Quantity::Entropy => match (mass, base) {
+                (false, _) => (1.0, 0.0, "J/mol-K"),
+                (true, false) => (1.0 / M, 0.0, "kJ/kg-K"),
+                (true, true) => (1e3 / M, 0.0, "J/kg-K"),
+            },
+            Quantity::Quality => (1.0, 0.0, "mol/mol"),
+            Quantity::Speed => (1.0, 0.0, "m/s"),
+            Quantity::Viscosity if base => (1e-6, 0.0, "Pa-s"),
+            Quantity::Viscosity => (1.0, 0.0, "uPa-s"),
+            Quantity::ThermalConductivity if matches!(self, Units::Default) || base => {
+                (1.0, 0.0, "W/(m-K)")
+            }
+            Quantity::ThermalConductivity => (1e3, 0.0, "mW/(m-K)"),
+        };
+
+        Ok(Conversion {
+            scale,
+            offset,
+            label,
+        })
+    }
+}
+
+impl FromStr for Units {
+    type Err = RefpropError;
+
+    /// Parses a unit system from its REFPROP name (e.g., `"MASS SI"`), ignoring case and accepting
+    /// `-` or `_` in place of spaces.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_uppercase().replace(['-', '_'], " ");
+        let units = match name.as_str() {
+            "DEFAULT" => Units::Default,
+            "MOLAR SI" => Units::MolarSI,
+            "MASS SI" => Units::MassSI,
+            "SI WITH C" => Units::SIWithC,
+            "MOLAR BASE SI" => Units::MolarBaseSI,
+            "MASS BASE SI" => Units::MassBaseSI,
+            "ENGLISH" => Units::English,
+            "MOLAR ENGLISH" => Units::MolarEnglish,
+            "MKS" => Units::MKS,
+            "CGS" => Units::CGS,
+            "MIXED" => Units::Mixed,
+            "MEUNITS" => Units::MEUnits,
+            _ => {
+                return Err(RefpropError::InvalidInput(format!(
+                    "Unknown unit system: {}",
+                    s
+                )))
+            }
+        };
+
+        Ok(units)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_conversions() -> Result<(), RefpropError> {
+        let M = 18.015268;
+        let units: Units = "si-with-c".parse()?;
+
+        assert!((units.from_default(Quantity::Temperature, 373.15, M)? - 100.0).abs() < 1e-12);
+        assert!((units.from_default(Quantity::Pressure, 101.325, M)? - 0.101325).abs() < 1e-12);
+        assert!((units.from_default(Quantity::Density, 55.5, M)? - 55.5 * M).abs() < 1e-9);
+        assert_eq!(units.label(Quantity::Energy)?, "kJ/kg");
+
+        for units in [Units::MolarSI, Units::MassBaseSI] {
+            let value = units.from_default(Quantity::Entropy, 120.0, M)?;
+            assert!((units.to_default(Quantity::Entropy, value, M)? - 120.0).abs() < 1e-9);
+        }
+        assert!(Units::English.label(Quantity::Pressure).is_err());
+        assert!("furlongs".parse::<Units>().is_err());
+
+        Ok(())
+    }
+}
@@ -1,20 +1,90 @@
 use std::{
     ffi::{c_char, c_int, CStr},
-    sync::{Mutex, MutexGuard},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
-use crate::{bindings, RefpropError, REFPROP_MUTEX};
+use crate::{
+    bindings, lock::RefpropGuard, setup::purefld::pure_component_selected, RefpropError,
+    REFPROP_LOCK,
+};
+
+pub(crate) fn acquire_lock() -> Result<RefpropGuard, RefpropError> {
+    REFPROP_LOCK.acquire()
+}
+
+/// Counter incremented whenever the loaded fluids or the pure-component selection of REFPROP may have changed.
+static FLUIDS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Counter incremented whenever the fluids, reference state, mixing parameters, or flags of REFPROP may have changed.
+static SETUP_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the current fluids generation, to detect changes of the loaded fluids between calls.
+pub(crate) fn fluids_generation() -> u64 {
+    FLUIDS_GENERATION.load(Ordering::SeqCst)
+}
+
+/// Returns the current setup generation, to detect any change of the REFPROP setup between calls.
+pub(crate) fn setup_generation() -> u64 {
+    SETUP_GENERATION.load(Ordering::SeqCst)
+}
 
-pub(crate) fn acquire_lock<'a>() -> Result<MutexGuard<'a, ()>, RefpropError> {
-    let lock = REFPROP_MUTEX
-        .get_or_init(|| Mutex::new(()))
-        .lock()
-        .map_err(|_| RefpropError::MutexPoisoned)?;
+/// Records that the loaded fluids may have changed, which also changes the setup. Must be called while holding the lock.
+pub(crate) fn fluids_changed(guard: &RefpropGuard) {
+    FLUIDS_GENERATION.fetch_add(1, Ordering::SeqCst);
+    setup_changed(guard);
+}
 
-    Ok(lock)
+/// Records that the REFPROP setup may have changed. Must be called while holding the lock.
+pub(crate) fn setup_changed(_guard: &RefpropGuard) {
+    SETUP_GENERATION.fetch_add(1, Ordering::SeqCst);
 }
 
+/// Validates a composition passed to a calculation with the fluids currently loaded in REFPROP.
+///
+/// In addition to `validate_fractions`, a composition of more than one component is rejected while
+/// a pure component is selected with `pure_fld` or `use_pure_component`.
 pub(crate) fn validate_composition(z: &[f64]) -> Result<(), RefpropError> {
+    // Waits for a `PureComponentGuard` of another thread to restore mixture mode
+    let guard = acquire_lock()?;
+    validate_composition_unlocked(&guard, z)
+}
+
+/// Validates a composition like `validate_composition`, with the lock already held by the caller.
+pub(crate) fn validate_composition_unlocked(
+    guard: &RefpropGuard,
+    z: &[f64],
+) -> Result<(), RefpropError> {
+    validate_fractions(z)?;
+
+    if let Some(icomp) = pure_component_selected(guard) {
+        if z.len() != 1 {
+            return Err(RefpropError::InvalidInput(format!(
+                "Component {} is selected as a pure fluid, which requires a single-component composition; got {} components.",
+                icomp,
+                z.len()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validates a composition and pads it to the fixed-size array expected by REFPROP.
+///
+/// Taking the guard ensures that the pure-component selection checked by `validate_composition` cannot
+/// change before the composition is passed to REFPROP.
+pub(crate) fn padded_composition(
+    guard: &RefpropGuard,
+    z: &[f64],
+) -> Result<[f64; 20], RefpropError> {
+    validate_composition_unlocked(guard, z)?;
+
+    let mut z_buffer = [0.0f64; 20];
+    z_buffer[..z.len()].copy_from_slice(z);
+    Ok(z_buffer)
+}
+
+/// Validates the number and the sum of the fractions of a composition, independently of REFPROP.
+pub(crate) fn validate_fractions(z: &[f64]) -> Result<(), RefpropError> {
     if z.len() > 20 {
         return Err(RefpropError::InvalidInput(
             "Composition slice 'z' length exceeds 20.".to_string(),
@@ -34,7 +104,7 @@ pub(crate) fn validate_composition(z: &[f64]) -> Result<(), RefpropError> {
 ///
 /// # Parameters
 ///
-/// - `guard`: Reference to the mutex guard ensuring exclusive access to REFPROP.
+/// - `guard`: Reference to the lock guard ensuring exclusive access to REFPROP.
 /// - `ierr`: Error code returned by the REFPROP function.
 /// - `herr_ptr`: Pointer to the error message buffer.
 /// - `herr_length`: Length of the error message buffer.
@@ -50,7 +120,7 @@ pub(crate) fn validate_composition(z: &[f64]) -> Result<(), RefpropError> {
 /// This function contains unsafe code due to FFI interactions.
 /// It should only be called with valid pointers and buffer lengths as per REFPROP's specifications.
 pub(crate) fn check_refprop_error(
-    _guard: &MutexGuard<()>,
+    _guard: &RefpropGuard,
     ierr: i32,
     herr_ptr: *mut c_char,
     herr_length: c_int,